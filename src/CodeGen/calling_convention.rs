@@ -15,12 +15,14 @@ impl MachineCallingConvention {
         match self.call_conv {
             CallConv::WindowsFastCall => {
                 match arch {
+                    Arch::X86_64 if ty.float() => Reg::x64(x64Reg::Xmm0),
                     Arch::X86_64 => Reg::x64(x64Reg::Rax.sub_ty(ty)),
                     _ => todo!()
                 }
             },
             CallConv::SystemV => {
                 match arch {
+                    Arch::X86_64 if ty.float() => Reg::x64(x64Reg::Xmm0),
                     Arch::X86_64 => Reg::x64(x64Reg::Rax.sub_ty(ty)),
                     _ => todo!()
                 }
@@ -46,7 +48,7 @@ impl MachineCallingConvention {
                 match arch {
                     Arch::X86_64 => vec![
                         Reg::x64(x64Reg::Rdi), Reg::x64(x64Reg::Rsi), 
-                        Reg::x64(x64Reg::Rcx), Reg::x64(x64Reg::Rdx), 
+                        Reg::x64(x64Reg::Rdx), Reg::x64(x64Reg::Rcx), 
                         Reg::x64(x64Reg::R8), Reg::x64(x64Reg::R9)
                     ],
                     _ => todo!()
@@ -57,6 +59,68 @@ impl MachineCallingConvention {
         }
     }
     
+    /// returns the floating point args for the specifc architecture
+    pub fn fp_args(&self, arch: Arch) -> Vec<Reg> {
+        match self.call_conv {
+            CallConv::WindowsFastCall => {
+                match arch {
+                    Arch::X86_64 => vec![
+                        Reg::x64(x64Reg::Xmm0), Reg::x64(x64Reg::Xmm1), 
+                        Reg::x64(x64Reg::Xmm2), Reg::x64(x64Reg::Xmm3)
+                    ],
+                    _ => todo!()
+                }
+            },
+            CallConv::SystemV => {
+                match arch {
+                    Arch::X86_64 => vec![
+                        Reg::x64(x64Reg::Xmm0), Reg::x64(x64Reg::Xmm1), 
+                        Reg::x64(x64Reg::Xmm2), Reg::x64(x64Reg::Xmm3), 
+                        Reg::x64(x64Reg::Xmm4), Reg::x64(x64Reg::Xmm5), 
+                        Reg::x64(x64Reg::Xmm6), Reg::x64(x64Reg::Xmm7)
+                    ],
+                    _ => todo!()
+                }
+            },
+            CallConv::AppleAarch64 => todo!(),
+            CallConv::WasmBasicCAbi => todo!(),
+        }
+    }
+
+    /// returns the registers in which arguments of the given types are passed
+    /// (`None` if the argument is passed over the stack)
+    pub fn arg_regs(&self, arch: Arch, tys: &[TypeMetadata]) -> Vec<Option<Reg>> {
+        let args = self.args(arch);
+        let fp_args = self.fp_args(arch);
+
+        let mut out = vec![];
+
+        let mut int_num = 0;
+        let mut fp_num = 0;
+
+        for (index, ty) in tys.iter().enumerate() {
+            // windows counts the argument position, system v counts each register class on it's own
+            if self.call_conv == CallConv::WindowsFastCall {
+                int_num = index;
+                fp_num = index;
+            }
+
+            let reg = if ty.float() {
+                fp_num += 1;
+                fp_args.get(fp_num - 1)
+            } else {
+                int_num += 1;
+                args.get(int_num - 1)
+            };
+
+            out.push(reg.map(|reg| match reg {
+                Reg::x64(x64) => Reg::x64(x64.sub_ty(*ty)),
+            }));
+        }
+
+        out
+    }
+    
     /// returns how many arguments are stored in registers
     pub fn num_reg_args(&self, arch: Arch) -> usize {
         self.args(arch).len()
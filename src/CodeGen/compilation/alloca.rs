@@ -0,0 +1,31 @@
+use crate::prelude::{Alloca, Ir};
+use crate::IR::{Block, TypeMetadata, Var};
+use super::{CompilationHelper, VarLocation};
+use crate::CodeGen::{MachineInstr, MachineMnemonic, MachineOperand};
+
+impl CompilationHelper {
+    #[allow(missing_docs)]
+    pub(crate) fn compile_alloca<T: Clone + 'static>(&mut self, node: &Alloca<Var, T>, mc_sink: &mut Vec<MachineInstr>, block: &Block) where Alloca<Var, T>: Ir {
+        let boxed: Box<dyn Ir> = Box::new(node.clone());
+
+        if !block.isVarUsedAfterNode(&boxed, &node.inner1) {
+            return;
+        }
+
+        let slot = *self.stack.get(&node.inner1.name).expect("expected a stack slot for the alloca");
+
+        let out = self.alloc(&node.inner1);
+
+        let mut instr = MachineInstr::new(MachineMnemonic::Alloca);
+
+        instr.add_operand(MachineOperand::Stack(slot));
+
+        match out {
+            VarLocation::Reg(reg) => instr.set_out(MachineOperand::Reg(reg)),
+        }
+
+        instr.meta = TypeMetadata::ptr;
+
+        mc_sink.push(instr);
+    }
+}
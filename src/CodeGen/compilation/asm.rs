@@ -0,0 +1,110 @@
+use crate::prelude::{InlineAsm, Ir};
+use crate::IR::{Block, TypeMetadata};
+use super::{CompilationHelper, VarLocation};
+use crate::CodeGen::reg::Reg;
+use crate::CodeGen::{MachineInstr, MachineMnemonic, MachineOperand};
+
+impl CompilationHelper {
+    /// parses the register of an inline assembly constraint (as the size of the type)
+    fn asm_reg(&self, name: &str, ty: TypeMetadata) -> Reg {
+        match Reg::parse(self.arch, name) {
+            Some(Reg::x64(reg)) if !reg.is_xmm() => Reg::x64(reg.sub_ty(ty)),
+            Some(reg) => reg,
+            None => unreachable!("unknown register {} in the constraints of the inline assembly (they are checked before the block is compiled)", name),
+        }
+    }
+
+    #[allow(missing_docs)]
+    pub(crate) fn compile_inline_asm(&mut self, node: &InlineAsm, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+        let boxed: Box<dyn Ir> = Box::new(node.clone());
+
+        let mut touched = node.ins.iter().map(|(reg, var)| self.asm_reg(reg, var.ty)).collect::<Vec<_>>();
+
+        if let Some((reg, out)) = &node.out {
+            touched.push(self.asm_reg(reg, out.ty));
+        }
+
+        for reg in &node.clobbers {
+            touched.push(self.asm_reg(reg, TypeMetadata::i64));
+        }
+
+        // the variables which are held in a register the assembly uses
+        // get saved into their own stack slot
+        // (naked functions have no stack frame, so there the assembly owns the registers)
+        let mut names = if self.naked { vec![] } else { self.vars.keys().cloned().collect::<Vec<_>>() };
+        names.sort();
+
+        let mut saved: Vec<(Reg, i64)> = vec![];
+
+        for name in names {
+            let VarLocation::Reg(reg) = self.vars[&name];
+
+            if !touched.iter().any(|touched| touched.same_as(&reg)) { continue; }
+            if saved.iter().any(|(saved, _)| *saved == reg) { continue; }
+
+            let slot = self.spill + 8 * (saved.len() as i64 + 1);
+
+            let mut instr = MachineInstr::new(MachineMnemonic::Store);
+            instr.add_operand(MachineOperand::Stack(slot));
+            instr.add_operand(MachineOperand::Reg(reg));
+            instr.meta = if reg.is_fp() { TypeMetadata::f64 } else { TypeMetadata::i64 };
+            mc_sink.push( instr );
+
+            saved.push((reg, slot));
+        }
+
+        for (reg, input) in &node.ins {
+            let VarLocation::Reg(src) = *self.vars.get(&input.name).expect("expected valid variable");
+
+            // saved inputs are loaded from their slot, so binding one input can't override another one
+            let mut instr = if let Some((_, slot)) = saved.iter().find(|(saved, _)| *saved == src) {
+                let mut instr = MachineInstr::new(MachineMnemonic::Load);
+                instr.add_operand(MachineOperand::Stack(*slot));
+                instr
+            } else {
+                let mut instr = MachineInstr::new(MachineMnemonic::Move);
+                instr.add_operand(MachineOperand::Reg(src));
+                instr
+            };
+
+            instr.set_out(MachineOperand::Reg(self.asm_reg(reg, input.ty)));
+            instr.meta = input.ty;
+
+            mc_sink.push( instr );
+        }
+
+        mc_sink.push( MachineInstr::new(MachineMnemonic::InlineAsm(node.asm.to_owned())) );
+
+        for (_, input) in &node.ins {
+            if !block.isVarUsedAfterNode(&boxed, input) {
+                self.free(input);
+            }
+        }
+
+        let mut out_reg = None;
+
+        if let Some((reg, out)) = node.out.as_ref().filter(|(_, out)| block.isVarUsedAfterNode(&boxed, out)) {
+            let mut instr = MachineInstr::new(MachineMnemonic::Move);
+            instr.add_operand(MachineOperand::Reg(self.asm_reg(reg, out.ty)));
+
+            let VarLocation::Reg(location) = self.alloc(out);
+            out_reg = Some(location);
+
+            instr.set_out(MachineOperand::Reg(location));
+            instr.meta = out.ty;
+
+            mc_sink.push( instr );
+        }
+
+        for (reg, slot) in saved {
+            // the register could be reused for the output after an input died
+            if out_reg.is_some_and(|out| reg.same_as(&out)) { continue; }
+
+            let mut instr = MachineInstr::new(MachineMnemonic::Load);
+            instr.add_operand(MachineOperand::Stack(slot));
+            instr.set_out(MachineOperand::Reg(reg));
+            instr.meta = if reg.is_fp() { TypeMetadata::f64 } else { TypeMetadata::i64 };
+            mc_sink.push( instr );
+        }
+    }
+}
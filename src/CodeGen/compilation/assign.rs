@@ -1,5 +1,5 @@
 use crate::prelude::{Assign, Ir};
-use crate::IR::{Block, Const, Type, Var};
+use crate::IR::{Block, Const, Function, Type, Var};
 use super::{CompilationHelper, VarLocation};
 use crate::CodeGen::{MachineInstr, MachineMnemonic, MachineOperand};
 
@@ -68,6 +68,32 @@ impl CompilationHelper {
 
         let location = self.alloc(&node.inner1);
 
+        let mut instr = MachineInstr::new(match node.inner2.thread_local {
+            Some(model) => MachineMnemonic::TlsAdressLoad(node.inner2.name.to_string(), model),
+            None => MachineMnemonic::AdressLoad(node.inner2.name.to_string()),
+        });
+
+        match location {
+            VarLocation::Reg(reg) => instr.set_out(MachineOperand::Reg(reg)),
+        }
+
+        
+        instr.meta = node.inner1.ty; // is a pointer but i just wrote it here
+
+        mc_sink.push( instr );
+    }
+
+    #[allow(missing_docs)]
+    pub fn compile_assign_var_func(&mut self, node: &Assign<Var, Function>, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+        let boxed: Box<dyn Ir> = Box::new(node.clone());
+
+        if !block.isVarUsedAfterNode(&boxed,&node.inner1) {
+            return; // dead code elimination
+        }
+
+        let location = self.alloc(&node.inner1);
+
+        // the adress of a function is loaded the same way as the adress of a constant
         let mut instr = MachineInstr::new(
             MachineMnemonic::AdressLoad(node.inner2.name.to_string())
         );
@@ -76,9 +102,8 @@ impl CompilationHelper {
             VarLocation::Reg(reg) => instr.set_out(MachineOperand::Reg(reg)),
         }
 
-        
-        instr.meta = node.inner1.ty; // is a pointer but i just wrote it here
+        instr.meta = node.inner1.ty;
 
         mc_sink.push( instr );
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,110 @@
+use crate::prelude::{AtomicCmpXchg, AtomicLoad, AtomicRmw, AtomicStore, Fence, Ir};
+use crate::IR::{Block, Var};
+use super::{CompilationHelper, VarLocation};
+use crate::CodeGen::{MachineInstr, MachineMnemonic, MachineOperand};
+
+impl CompilationHelper {
+    #[allow(missing_docs)]
+    pub(crate) fn compile_atomic_load(&mut self, node: &AtomicLoad, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+        let ptr = self.ptr_operand(&node.ptr);
+
+        let boxed: Box<dyn Ir> = Box::new(node.clone());
+
+        if !block.isVarUsedAfterNode(&boxed, &node.ptr) {
+            self.free(&node.ptr)
+        }
+        if !block.isVarUsedAfterNode(&boxed, &node.out) {
+            return;
+        }
+
+        let out = self.alloc(&node.out);
+
+        let mut instr = MachineInstr::new(MachineMnemonic::AtomicLoad(node.order));
+
+        instr.add_operand(ptr);
+
+        match out {
+            VarLocation::Reg(reg) => instr.set_out(MachineOperand::Reg(reg)),
+        }
+
+        instr.meta = node.out.ty;
+
+        mc_sink.push(instr);
+    }
+
+    #[allow(missing_docs)]
+    pub(crate) fn compile_atomic_store(&mut self, node: &AtomicStore, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+        let mut instr = MachineInstr::new(MachineMnemonic::AtomicStore(node.order));
+
+        instr.add_operand(self.ptr_operand(&node.ptr));
+        instr.add_operand(self.var_operand(&node.val));
+
+        instr.meta = node.val.ty;
+
+        mc_sink.push(instr);
+
+        let boxed: Box<dyn Ir> = Box::new(node.clone());
+
+        if !block.isVarUsedAfterNode(&boxed, &node.ptr) {
+            self.free(&node.ptr)
+        }
+        if !block.isVarUsedAfterNode(&boxed, &node.val) {
+            self.free(&node.val)
+        }
+    }
+
+    #[allow(missing_docs)]
+    pub(crate) fn compile_atomic_rmw(&mut self, node: &AtomicRmw, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+        let mut instr = MachineInstr::new(MachineMnemonic::AtomicRmw(node.op, node.order));
+
+        instr.add_operand(self.ptr_operand(&node.ptr));
+        instr.add_operand(self.var_operand(&node.val));
+
+        self.compile_atomic_out(instr, &node.out, node.clone_box(), &[&node.ptr, &node.val], mc_sink, block);
+    }
+
+    #[allow(missing_docs)]
+    pub(crate) fn compile_atomic_cmpxchg(&mut self, node: &AtomicCmpXchg, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+        let mut instr = MachineInstr::new(MachineMnemonic::AtomicCmpXchg(node.order));
+
+        instr.add_operand(self.ptr_operand(&node.ptr));
+        instr.add_operand(self.var_operand(&node.expected));
+        instr.add_operand(self.var_operand(&node.new));
+
+        self.compile_atomic_out(instr, &node.out, node.clone_box(), &[&node.ptr, &node.expected, &node.new], mc_sink, block);
+    }
+
+    #[allow(missing_docs)]
+    pub(crate) fn compile_fence(&mut self, node: &Fence, mc_sink: &mut Vec<MachineInstr>, _: &Block) {
+        mc_sink.push(MachineInstr::new(MachineMnemonic::Fence(node.order)));
+    }
+
+    /// returns the register operand of the variable
+    pub(super) fn var_operand(&self, var: &Var) -> MachineOperand {
+        match *self.vars.get(&var.name).expect("expected valid variable") {
+            VarLocation::Reg(reg) => MachineOperand::Reg(reg),
+        }
+    }
+
+    /// emits the atomic instruction which returns the old value of the memory
+    /// 
+    /// the memory is modified even if the old value isn't used, so the instruction is always emitted
+    fn compile_atomic_out(&mut self, mut instr: MachineInstr, out: &Var, boxed: Box<dyn Ir>, ops: &[&Var], mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+        for op in ops {
+            if !block.isVarUsedAfterNode(&boxed, op) {
+                self.free(op);
+            }
+        }
+
+        // the lowering reads every operand before the output is written, so the output can reuse their registers
+        if block.isVarUsedAfterNode(&boxed, out) {
+            match self.alloc(out) {
+                VarLocation::Reg(reg) => instr.set_out(MachineOperand::Reg(reg)),
+            }
+        }
+
+        instr.meta = out.ty;
+
+        mc_sink.push(instr);
+    }
+}
@@ -20,15 +20,20 @@ impl CompilationHelper {
     pub fn compile_br_cond(&mut self, node: &BrCond<Var, Block, Block>, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
         let boxed: Box<dyn Ir> = Box::new(node.clone());
 
+        if self.fused_branch.as_ref() == Some(&node.inner1.name) {
+            self.fused_branch = None;
+            return; // the branch was already emitted by the overflow checked arithmetic
+        }
+
         let iftrue = node.inner2.name.to_owned();
         let iffalse = node.inner3.name.to_owned();
 
+        let src = *self.vars.get(&node.inner1.name).expect("expected valid variable");
+
         if !block.isVarUsedAfterNode(&boxed, &node.inner1) {
             self.free(&node.inner1);
         }
 
-        let src = *self.vars.get(&node.inner1.name).expect("expected valid variable");
-
         let src = match src {
             super::VarLocation::Reg(reg) => MachineOperand::Reg(reg),
         };
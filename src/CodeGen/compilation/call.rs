@@ -1,55 +1,249 @@
-use crate::{prelude::{Call, Ir}, CodeGen::{MachineMnemonic, MachineOperand}};
-use crate::IR::{Block, Function, Var};
+use crate::{prelude::{Call, CallIndirect, Ir, TailCall}, CodeGen::{MachineMnemonic, MachineOperand}};
+use crate::IR::{Block, Function, FunctionAttr, FunctionType, Type, TypeMetadata, Var};
+use crate::Target::CallConv;
 use super::{CompilationHelper, VarLocation};
+use crate::CodeGen::reg::Reg;
 use crate::CodeGen::MachineInstr;
 
+/// An argument of a call
+pub(crate) enum CallArg {
+    /// the argument is a variable
+    Var(Var),
+    /// the argument is a constant (used for calls to runtime functions)
+    Imm(Type),
+}
+
+impl CallArg {
+    pub(crate) fn ty(&self) -> TypeMetadata {
+        match self {
+            CallArg::Var(var) => var.ty,
+            CallArg::Imm(imm) => (*imm).into(),
+        }
+    }
+}
+
+/// wraps the arguments of a call node
+fn var_args(args: &[Var]) -> Vec<CallArg> {
+    args.iter().map(|arg| CallArg::Var(arg.to_owned())).collect()
+}
+
 impl CompilationHelper {
     #[allow(missing_docs)]
     pub fn compile_call(&mut self, node: &Call<Function, Vec<Var>, Var>, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
         let boxed: Box<dyn Ir> = Box::new(node.clone());
 
-        let mut reg_args = 0;
+        // a pure function has no side effects, so the call is dead if its result isn't used
+        if node.inner1.has_attr(FunctionAttr::Pure) && !block.isVarUsedAfterNode(&boxed, &node.inner3) {
+            for arg in &node.inner2 {
+                if !block.isVarUsedAfterNode(&boxed, arg) {
+                    self.free(arg);
+                }
+            }
+
+            return;
+        }
+
+        let call = MachineInstr::new(
+            MachineMnemonic::Call(node.inner1.name.to_string())
+        );
+
+        self.compile_call_with(call, &node.inner1.ty, None, &var_args(&node.inner2), Some(&node.inner3), boxed, mc_sink, block);
+    }
+
+    #[allow(missing_docs)]
+    pub(crate) fn compile_call_indirect(&mut self, node: &CallIndirect, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+        let call = MachineInstr::new(MachineMnemonic::CallIndirect);
+
+        self.compile_call_with(call, &node.ty, Some(&node.ptr), &var_args(&node.args), Some(&node.out), node.clone_box(), mc_sink, block);
+    }
+
+    #[allow(missing_docs)]
+    pub(crate) fn compile_tail_call(&mut self, node: &TailCall<Function, Vec<Var>>, mc_sink: &mut Vec<MachineInstr>, _: &Block) {
+        let tys = node.inner2.iter().map(|arg| arg.ty).collect::<Vec<_>>();
+
+        let conv = self.call_conv(&node.inner1.ty);
+
+        let args = conv.arg_regs(self.arch, &tys);
 
-        let args = self.call.args(self.arch);
+        // the arguments are moved over their own stack slots, so moving one
+        // argument into its register can't override the source of another one
+        let mut saved: Vec<(Reg, i64)> = vec![];
 
         for arg in &node.inner2 {
-            let mut instr = MachineInstr::new(MachineMnemonic::Move);
-            
-            let arg_reg = args.get(reg_args);
+            let VarLocation::Reg(reg) = *self.vars.get(&arg.name).expect("expected valid variable");
+
+            if saved.iter().any(|(saved, _)| *saved == reg) { continue; }
+
+            let slot = self.spill + 8 * (saved.len() as i64 + 1);
+
+            let mut instr = MachineInstr::new(MachineMnemonic::Store);
+            instr.add_operand(MachineOperand::Stack(slot));
+            instr.add_operand(MachineOperand::Reg(reg));
+            instr.meta = if reg.is_fp() { TypeMetadata::f64 } else { TypeMetadata::i64 };
+            mc_sink.push( instr );
+
+            saved.push((reg, slot));
+        }
+
+        let mut fp_args = 0;
+
+        for (arg, arg_reg) in node.inner2.iter().zip(args) {
+            let mut instr = MachineInstr::new(MachineMnemonic::Load);
 
             if let Some(arg) = arg_reg {
-                instr.set_out(MachineOperand::Reg(*arg));
+                if arg.is_fp() { fp_args += 1; }
+                instr.set_out(MachineOperand::Reg(arg));
+            } else {
+                unreachable!("tail calls which pass arguments over the stack are rejected before the block is compiled");
+            }
+
+            let VarLocation::Reg(src) = *self.vars.get(&arg.name).expect("expected valid variable");
+
+            let slot = saved.iter().find(|(reg, _)| *reg == src).map(|(_, slot)| *slot).expect("expected saved variable");
+
+            instr.add_operand(MachineOperand::Stack(slot));
+
+            instr.meta = arg.ty;
+
+            mc_sink.push( instr );
+        }
+
+        // the callee returns directly to our caller
+        mc_sink.push( MachineInstr::new(MachineMnemonic::Epilog) );
+
+        let mut call = MachineInstr::new(MachineMnemonic::TailCall(node.inner1.name.to_string()));
+
+        if conv.call_conv.reset_eax() {
+            call.add_operand(MachineOperand::Imm(fp_args));
+        }
+
+        mc_sink.push( call );
+    }
+
+    /// saves the registers, moves the arguments into place and emits the call
+    /// (the pointer of indirect calls is passed as the stack slot it was saved into)
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn compile_call_with(&mut self, mut call: MachineInstr, ty: &FunctionType, ptr: Option<&Var>, node_args: &[CallArg], out: Option<&Var>, boxed: Box<dyn Ir>, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+        let conv = self.call_conv(ty);
+        let ret = ty.ret;
+
+        let tys = node_args.iter().map(|arg| arg.ty()).collect::<Vec<_>>();
+
+        let args = conv.arg_regs(self.arch, &tys);
+
+        // the callee is allowed to override the registers, so every variable
+        // which is currently held in a register gets saved into its own stack slot
+        let mut names = self.vars.keys().cloned().collect::<Vec<_>>();
+        names.sort();
+
+        let mut saved: Vec<(Reg, i64)> = vec![];
+
+        for name in names {
+            let VarLocation::Reg(reg) = self.vars[&name];
+
+            if saved.iter().any(|(saved, _)| *saved == reg) { continue; }
+
+            let slot = self.spill + 8 * (saved.len() as i64 + 1);
+
+            let mut instr = MachineInstr::new(MachineMnemonic::Store);
+            instr.add_operand(MachineOperand::Stack(slot));
+            instr.add_operand(MachineOperand::Reg(reg));
+            instr.meta = if reg.is_fp() { TypeMetadata::f64 } else { TypeMetadata::i64 };
+            mc_sink.push( instr );
+
+            saved.push((reg, slot));
+        }
+
+        let mut fp_args = 0;
+
+        // windows expects the floats which are passed to variadic functions also in the general purpose registers
+        let duplicate_fp = ty.any_args && conv.call_conv == CallConv::WindowsFastCall;
+        let gp_args = conv.args(self.arch);
+
+        for (index, (arg, arg_reg)) in node_args.iter().zip(args).enumerate() {
+            // the arguments are loaded from the saved slots, so moving one argument
+            // can't override the source of another one
+            let mut instr = match arg {
+                CallArg::Var(arg) => {
+                    let mut instr = MachineInstr::new(MachineMnemonic::Load);
+
+                    let VarLocation::Reg(src) = *self.vars.get(&arg.name).expect("expected valid variable");
+
+                    let slot = saved.iter().find(|(reg, _)| *reg == src).map(|(_, slot)| *slot).expect("expected saved variable");
+
+                    instr.add_operand(MachineOperand::Stack(slot));
+                    instr
+                },
+                CallArg::Imm(imm) => {
+                    let mut instr = MachineInstr::new(MachineMnemonic::Move);
+                    instr.add_operand(MachineOperand::Imm(imm.val() as i64));
+                    instr
+                },
+            };
+
+            if let Some(arg) = arg_reg {
+                if arg.is_fp() { fp_args += 1; }
+                instr.set_out(MachineOperand::Reg(arg));
             } else {
                 todo!("implemt arguments which are passed over the stack");
             }
 
-            let src = self.vars.get(&arg.name).expect("expected valid variable");
+            instr.meta = arg.ty();
 
-            match src {
-                VarLocation::Reg(reg) => instr.add_operand(MachineOperand::Reg(*reg)),
+            if duplicate_fp && instr.meta.float() {
+                let mut dup = instr.clone();
+                dup.set_out(MachineOperand::Reg(gp_args[index]));
+                dup.meta = TypeMetadata::i64;
+                mc_sink.push( dup );
             }
 
             mc_sink.push( instr );
+        }
 
-            reg_args += 1;
+        if let Some(ptr) = ptr {
+            let VarLocation::Reg(src) = *self.vars.get(&ptr.name).expect("expected valid variable");
+
+            let slot = saved.iter().find(|(reg, _)| *reg == src).map(|(_, slot)| *slot).expect("expected saved variable");
+
+            call.add_operand(MachineOperand::Stack(slot));
         }
 
-        mc_sink.push(MachineInstr::new(
-            MachineMnemonic::Call(node.inner1.name.to_string())
-        ));
+        // the count of floating point registers which are used (needed for variadic functions)
+        if conv.call_conv.reset_eax() {
+            call.add_operand(MachineOperand::Imm(fp_args));
+        }
+
+        mc_sink.push( call );
 
-        if block.isVarUsedAfterNode(&boxed, &node.inner3) {
+        // the registers of arguments which aren't used after the call can hold the return value
+        let dead = node_args.iter()
+            .filter_map(|arg| if let CallArg::Var(arg) = arg { Some(arg) } else { None })
+            .chain(ptr)
+            .filter(|arg| !block.isVarUsedAfterNode(&boxed, arg))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for arg in &dead {
+            self.free(arg);
+        }
+
+        let mut out_reg = None;
+
+        if let Some(out) = out.filter(|out| block.isVarUsedAfterNode(&boxed, out)) {
             let mut instr = MachineInstr::new(MachineMnemonic::Move);
 
-            let loc = self.alloc(&node.inner3);
+            let loc = self.alloc(out);
+
+            let VarLocation::Reg(reg) = loc;
+            out_reg = Some(reg);
 
             instr.add_operand(
                 MachineOperand::Reg(
-                    self.call.return_reg(self.arch, node.inner1.ty.ret)
+                    conv.return_reg(self.arch, ret)
                 )
             );
 
-            instr.meta = node.inner1.ty.ret;
+            instr.meta = ret;
 
             match loc {
                 VarLocation::Reg(reg) => instr.set_out(MachineOperand::Reg(reg)),
@@ -57,5 +251,16 @@ impl CompilationHelper {
 
             mc_sink.push(instr);
         }
+
+        for (reg, slot) in saved {
+            // the register could be reused for the return value after a variable died
+            if out_reg.is_some_and(|out| reg.same_as(&out)) { continue; }
+
+            let mut instr = MachineInstr::new(MachineMnemonic::Load);
+            instr.add_operand(MachineOperand::Stack(slot));
+            instr.set_out(MachineOperand::Reg(reg));
+            instr.meta = if reg.is_fp() { TypeMetadata::f64 } else { TypeMetadata::i64 };
+            mc_sink.push( instr );
+        }
     }
 }
\ No newline at end of file
@@ -21,12 +21,14 @@ impl CompilationHelper {
 
         let op = {
 
-        if node.inner1.ty.bitSize() > node.inner2.bitSize() {
-            MachineMnemonic::Zext
-        } else if node.inner1.ty.bitSize() < node.inner2.bitSize(){
+        if node.inner1.ty.float() || node.inner2.float() {
+            MachineMnemonic::FCast(node.inner1.ty)
+        } else if node.inner1.ty.bitSize() < node.inner2.bitSize() {
+            if node.inner1.ty.signed() { MachineMnemonic::Sext } else { MachineMnemonic::Zext }
+        } else if node.inner1.ty.bitSize() > node.inner2.bitSize(){
             MachineMnemonic::Downcast
         } else {
-            return;
+            MachineMnemonic::Move
         }
         
         };
@@ -32,11 +32,6 @@ impl CompilationHelper {
             super::VarLocation::Reg(reg) => MachineOperand::Reg(reg),
         };
 
-        let mut reset = MachineInstr::new(MachineMnemonic::Move);
-        reset.set_out(out);
-        reset.add_operand(MachineOperand::Imm(0));
-        mc_sink.push( reset );
-
         let mut cmp = MachineInstr::new(MachineMnemonic::Compare(node.mode) );
         
         cmp.add_operand(ls);
@@ -44,6 +39,8 @@ impl CompilationHelper {
 
         cmp.set_out(out);
 
+        cmp.meta = node.ls.ty;
+
         mc_sink.push( cmp );
     }
 }
\ No newline at end of file
@@ -0,0 +1,65 @@
+use crate::prelude::{Gep, Ir};
+use crate::IR::{Block, Type, TypeMetadata, Var};
+use super::{CompilationHelper, VarLocation};
+use crate::CodeGen::{MachineInstr, MachineMnemonic, MachineOperand};
+
+impl CompilationHelper {
+    #[allow(missing_docs)]
+    pub(crate) fn compile_gep_var(&mut self, node: &Gep<Var>, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+        let index = *self.vars.get(&node.index.name).expect("expected valid variable");
+
+        let elem = node.ty.element(0).expect("a gep into an empty array").size(self.arch);
+
+        let index = match index {
+            VarLocation::Reg(reg) => MachineOperand::Reg(reg),
+        };
+
+        let boxed: Box<dyn Ir> = Box::new(node.clone());
+
+        if !block.isVarUsedAfterNode(&boxed, &node.index) {
+            self.free(&node.index);
+        }
+
+        self.compile_gep(node, index, elem as i64, node.index.ty, mc_sink, block);
+    }
+
+    #[allow(missing_docs)]
+    pub(crate) fn compile_gep_type(&mut self, node: &Gep<Type>, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+        let offset = node.ty.offset(node.index.val() as usize, self.arch);
+
+        self.compile_gep(node, MachineOperand::Imm(offset as i64), 1, TypeMetadata::ptr, mc_sink, block);
+    }
+
+    /// emits the adress computation `ptr + index * scale` (`meta` is the type of the index)
+    fn compile_gep<T>(&mut self, node: &Gep<T>, index: MachineOperand, scale: i64, meta: TypeMetadata, mc_sink: &mut Vec<MachineInstr>, block: &Block) where Gep<T>: Ir + Clone {
+        let boxed: Box<dyn Ir> = Box::new(node.clone());
+
+        let ptr = *self.vars.get(&node.ptr.name).expect("expected valid variable");
+
+        if !block.isVarUsedAfterNode(&boxed, &node.ptr) {
+            self.free(&node.ptr);
+        }
+        if !block.isVarUsedAfterNode(&boxed, &node.out) {
+            return; // dead code elimination
+        }
+
+        let out = self.alloc(&node.out);
+
+        let mut instr = MachineInstr::new(MachineMnemonic::Gep);
+
+        match ptr {
+            VarLocation::Reg(reg) => instr.add_operand(MachineOperand::Reg(reg)),
+        }
+
+        instr.add_operand(index);
+        instr.add_operand(MachineOperand::Imm(scale));
+
+        match out {
+            VarLocation::Reg(reg) => instr.set_out(MachineOperand::Reg(reg)),
+        }
+
+        instr.meta = meta;
+
+        mc_sink.push( instr );
+    }
+}
@@ -0,0 +1,94 @@
+use std::any::Any;
+
+use crate::prelude::{Ir, MemCpy, MemSet};
+use crate::IR::{Block, FunctionType, Type, TypeMetadata, Var};
+use super::{call::CallArg, CompilationHelper, VarLocation};
+use crate::CodeGen::{MachineInstr, MachineMnemonic, MachineOperand};
+
+/// the biggest constant size of a memory intrinsic which gets expanded inline
+/// (bigger or variable sizes call the runtime function)
+pub(crate) const MAX_INLINE_MEM_SIZE: u64 = 64;
+
+/// returns if the node is a memory intrinsic which gets compiled to a call of the runtime function
+pub(crate) fn calls_runtime(node: &dyn Any) -> bool {
+    let inline = |size: &Type| size.val() as u64 <= MAX_INLINE_MEM_SIZE;
+
+    if let Some(node) = node.downcast_ref::<MemCpy<Type>>() {
+        !inline(&node.size)
+    } else if let Some(node) = node.downcast_ref::<MemSet<Type>>() {
+        !inline(&node.size)
+    } else {
+        node.is::<MemCpy<Var>>() || node.is::<MemSet<Var>>()
+    }
+}
+
+impl CompilationHelper {
+    #[allow(missing_docs)]
+    pub(crate) fn compile_memcpy_var(&mut self, node: &MemCpy<Var>, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+        let args = [CallArg::Var(node.dst.to_owned()), CallArg::Var(node.src.to_owned()), CallArg::Var(node.size.to_owned())];
+
+        self.compile_mem_call("memcpy", &args, node.clone_box(), mc_sink, block);
+    }
+
+    #[allow(missing_docs)]
+    pub(crate) fn compile_memcpy_type(&mut self, node: &MemCpy<Type>, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+        if node.size.val() as u64 > MAX_INLINE_MEM_SIZE {
+            let args = [CallArg::Var(node.dst.to_owned()), CallArg::Var(node.src.to_owned()), CallArg::Imm(node.size)];
+
+            return self.compile_mem_call("memcpy", &args, node.clone_box(), mc_sink, block);
+        }
+
+        self.compile_mem_inline(MachineMnemonic::MemCpy, &node.dst, &node.src, node.size, node.clone_box(), mc_sink, block);
+    }
+
+    #[allow(missing_docs)]
+    pub(crate) fn compile_memset_var(&mut self, node: &MemSet<Var>, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+        let args = [CallArg::Var(node.dst.to_owned()), CallArg::Var(node.val.to_owned()), CallArg::Var(node.size.to_owned())];
+
+        self.compile_mem_call("memset", &args, node.clone_box(), mc_sink, block);
+    }
+
+    #[allow(missing_docs)]
+    pub(crate) fn compile_memset_type(&mut self, node: &MemSet<Type>, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+        if node.size.val() as u64 > MAX_INLINE_MEM_SIZE {
+            let args = [CallArg::Var(node.dst.to_owned()), CallArg::Var(node.val.to_owned()), CallArg::Imm(node.size)];
+
+            return self.compile_mem_call("memset", &args, node.clone_box(), mc_sink, block);
+        }
+
+        self.compile_mem_inline(MachineMnemonic::MemSet, &node.dst, &node.val, node.size, node.clone_box(), mc_sink, block);
+    }
+
+    /// calls the runtime function (the returned pointer is ignored)
+    fn compile_mem_call(&mut self, func: &str, args: &[CallArg], boxed: Box<dyn Ir>, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+        let call = MachineInstr::new(MachineMnemonic::Call(func.to_string()));
+
+        let ty = FunctionType::new(args.iter().map(|arg| arg.ty()).collect(), TypeMetadata::ptr);
+
+        self.compile_call_with(call, &ty, None, args, None, boxed, mc_sink, block);
+    }
+
+    /// expands the intrinsic into moves
+    #[allow(clippy::too_many_arguments)]
+    fn compile_mem_inline(&mut self, mnemonic: MachineMnemonic, dst: &Var, op: &Var, size: Type, boxed: Box<dyn Ir>, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+        let VarLocation::Reg(dst_reg) = *self.vars.get(&dst.name).expect("expected valid variable");
+        let VarLocation::Reg(op_reg) = *self.vars.get(&op.name).expect("expected valid variable");
+
+        let mut instr = MachineInstr::new(mnemonic);
+
+        instr.add_operand(MachineOperand::Reg(dst_reg));
+        instr.add_operand(MachineOperand::Reg(op_reg));
+        instr.add_operand(MachineOperand::Imm(size.val() as i64));
+
+        instr.meta = op.ty;
+
+        mc_sink.push( instr );
+
+        if !block.isVarUsedAfterNode(&boxed, dst) {
+            self.free(dst);
+        }
+        if !block.isVarUsedAfterNode(&boxed, op) {
+            self.free(op);
+        }
+    }
+}
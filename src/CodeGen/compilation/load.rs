@@ -0,0 +1,47 @@
+use crate::prelude::{Ir, Load};
+use crate::IR::{Block, TypeMetadata, Var};
+use super::{CompilationHelper, VarLocation};
+use crate::CodeGen::{MachineInstr, MachineMnemonic, MachineOperand};
+
+impl CompilationHelper {
+    #[allow(missing_docs)]
+    pub fn compile_load(&mut self, node: &Load<Var, Var, TypeMetadata>, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+        let ptr = self.ptr_operand(&node.inner2);
+
+        let boxed: Box<dyn Ir> = Box::new(node.clone());
+
+        if !block.isVarUsedAfterNode(&boxed, &node.inner2) {
+            self.free(&node.inner2)
+        }
+        if !block.isVarUsedAfterNode(&boxed, &node.inner1) {
+            return;
+        }
+
+        let out = self.alloc(&node.inner1);
+
+        let mut instr = MachineInstr::new(MachineMnemonic::Load);
+
+        instr.add_operand(ptr);
+
+        match out {
+            VarLocation::Reg(reg) => instr.set_out(MachineOperand::Reg(reg)),
+        }
+
+        instr.meta = node.inner3;
+
+        mc_sink.push(instr);
+    }
+
+    /// returns the operand for the memory the pointer points to
+    /// 
+    /// pointers of allocas directly use their stack slot, so they also work in other blocks
+    pub(crate) fn ptr_operand(&self, ptr: &Var) -> MachineOperand {
+        if let Some(slot) = self.stack.get(&ptr.name) {
+            return MachineOperand::Stack(*slot);
+        }
+
+        match *self.vars.get(&ptr.name).expect("expected valid variable") {
+            VarLocation::Reg(reg) => MachineOperand::Reg(reg),
+        }
+    }
+}
@@ -55,6 +55,10 @@ MathVarVar!(compile_mul_var_var, Mul, MachineMnemonic::Mul);
 MathVarVar!(compile_or_var_var, Or, MachineMnemonic::Or);
 MathVarVar!(compile_sub_var_var, Sub, MachineMnemonic::Sub);
 MathVarVar!(compile_xor_var_var, Xor, MachineMnemonic::Xor);
+MathVarVar!(compile_rem_var_var, Rem, MachineMnemonic::Rem);
+MathVarVar!(compile_shl_var_var, Shl, MachineMnemonic::Shl);
+MathVarVar!(compile_lshr_var_var, Lshr, MachineMnemonic::Lshr);
+MathVarVar!(compile_ashr_var_var, Ashr, MachineMnemonic::Ashr);
 
 
 macro_rules! MathVarType {
@@ -104,6 +108,10 @@ MathVarType!(compile_mul_var_type, Mul, MachineMnemonic::Mul);
 MathVarType!(compile_or_var_type, Or, MachineMnemonic::Or);
 MathVarType!(compile_sub_var_type, Sub, MachineMnemonic::Sub);
 MathVarType!(compile_xor_var_type, Xor, MachineMnemonic::Xor);
+MathVarType!(compile_rem_var_type, Rem, MachineMnemonic::Rem);
+MathVarType!(compile_shl_var_type, Shl, MachineMnemonic::Shl);
+MathVarType!(compile_lshr_var_type, Lshr, MachineMnemonic::Lshr);
+MathVarType!(compile_ashr_var_type, Ashr, MachineMnemonic::Ashr);
 
 
 macro_rules! MathTypeType {
@@ -145,3 +153,88 @@ MathTypeType!(compile_mul_type_type, Mul, MachineMnemonic::Mul);
 MathTypeType!(compile_or_type_type, Or, MachineMnemonic::Or);
 MathTypeType!(compile_sub_type_type, Sub, MachineMnemonic::Sub);
 MathTypeType!(compile_xor_type_type, Xor, MachineMnemonic::Xor);
+MathTypeType!(compile_rem_type_type, Rem, MachineMnemonic::Rem);
+MathTypeType!(compile_shl_type_type, Shl, MachineMnemonic::Shl);
+MathTypeType!(compile_lshr_type_type, Lshr, MachineMnemonic::Lshr);
+MathTypeType!(compile_ashr_type_type, Ashr, MachineMnemonic::Ashr);
+
+
+macro_rules! UnaryVar {
+    ($func:ident, $node:ident, $mnemonic:expr) => {
+        impl CompilationHelper {
+            #[allow(missing_docs)]
+            pub(crate) fn $func(&mut self, node: &$node<Var, Var>, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+                let src = *self.vars.get(&node.inner1.name).expect("expected valid variable");
+
+                let boxed: Box<dyn Ir> = Box::new(node.clone());
+
+                if !block.isVarUsedAfterNode(&boxed, &node.inner1) { // op
+                    self.free(&node.inner1);
+                }
+                if !block.isVarUsedAfterNode(&boxed, &node.inner2) { // out
+                    return; 
+                }
+
+                let out = self.alloc(&node.inner2);
+
+                let mut instr = MachineInstr::new($mnemonic);
+
+                match src {
+                    VarLocation::Reg(reg) => instr.add_operand(MachineOperand::Reg(reg)),
+                }
+
+                match out {
+                    VarLocation::Reg(reg) => instr.set_out(MachineOperand::Reg(reg)),
+                }
+
+                instr.meta = node.inner2.ty;
+
+                mc_sink.push(instr);
+            }
+        }
+    };
+}
+
+UnaryVar!(compile_neg_var, Neg, MachineMnemonic::Neg);
+UnaryVar!(compile_not_var, Not, MachineMnemonic::Not);
+UnaryVar!(compile_bswap_var, Bswap, MachineMnemonic::Bswap);
+UnaryVar!(compile_ctpop_var, Ctpop, MachineMnemonic::Ctpop);
+UnaryVar!(compile_ctlz_var, Ctlz, MachineMnemonic::Ctlz);
+UnaryVar!(compile_cttz_var, Cttz, MachineMnemonic::Cttz);
+
+
+macro_rules! UnaryType {
+    ($func:ident, $node:ident, $mnemonic:expr) => {
+        impl CompilationHelper {
+            #[allow(missing_docs)]
+            pub(crate) fn $func(&mut self, node: &$node<Type, Var>, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+                let boxed: Box<dyn Ir> = Box::new(node.clone());
+
+                if !block.isVarUsedAfterNode(&boxed, &node.inner2) { // out
+                    return; 
+                }
+
+                let out = self.alloc(&node.inner2);
+
+                let mut instr = MachineInstr::new($mnemonic);
+
+                instr.add_operand(MachineOperand::Imm(node.inner1.val() as i64));
+
+                match out {
+                    VarLocation::Reg(reg) => instr.set_out(MachineOperand::Reg(reg)),
+                }
+
+                instr.meta = node.inner2.ty;
+
+                mc_sink.push(instr);
+            }
+        }
+    };
+}
+
+UnaryType!(compile_neg_type, Neg, MachineMnemonic::Neg);
+UnaryType!(compile_not_type, Not, MachineMnemonic::Not);
+UnaryType!(compile_bswap_type, Bswap, MachineMnemonic::Bswap);
+UnaryType!(compile_ctpop_type, Ctpop, MachineMnemonic::Ctpop);
+UnaryType!(compile_ctlz_type, Ctlz, MachineMnemonic::Ctlz);
+UnaryType!(compile_cttz_type, Cttz, MachineMnemonic::Cttz);
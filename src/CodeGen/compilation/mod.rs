@@ -1,8 +1,8 @@
 use std::collections::HashMap;
 
-use crate::{Target::{Arch, CallConv}, IR::{Function, Var}};
+use crate::{prelude::{Alloca, Block, Call, CallIndirect, InlineAsm, Phi, TailCall}, Target::{Arch, CallConv}, IR::{AggregateType, Function, FunctionType, TypeMetadata, Var}};
 
-use super::{calling_convention::MachineCallingConvention, reg::Reg, reg_vec::RegVec, MCInstr, MachineInstr};
+use super::{calling_convention::MachineCallingConvention, reg::Reg, reg_vec::RegVec, MCInstr, MachineInstr, MachineMnemonic, MachineOperand};
 
 mod math;
 mod cast;
@@ -11,41 +11,89 @@ mod ret;
 mod assign;
 mod br;
 mod cmp;
+mod alloca;
+mod load;
+mod store;
+mod phi;
+mod switch;
+mod select;
+mod gep;
+pub(crate) mod intrinsic;
+mod atomic;
+mod overflow;
+mod va;
+mod asm;
 
 /// helps with compilation
 pub struct CompilationHelper {
     pub(crate) regs: RegVec,
+    pub(crate) fp_regs: RegVec,
     pub(crate) arch: Arch,
     pub(crate) lower: Option<fn(CallConv, Vec<MachineInstr>) -> Vec<Box<dyn MCInstr>>>,
 
     pub(crate) call: MachineCallingConvention,
+    /// the calling convention of the function which is currently compiled
+    pub(crate) func_call: MachineCallingConvention,
 
     pub(crate) vars: HashMap<String, VarLocation>,
+
+    /// the stack slots of the allocas (the offset from the frame base)
+    pub(crate) stack: HashMap<String, i64>,
+    /// the size of the stack frame
+    pub(crate) stack_size: i64,
+    /// the start of the area in the stack frame in which registers are saved across calls
+    pub(crate) spill: i64,
+
+    /// the name of the function which is currently compiled
+    pub(crate) func: String,
+    /// if the function which is currently compiled is naked (it has no prolog and epilog)
+    pub(crate) naked: bool,
+
+    /// the overflow flag of which the conditional branch was already fused into the overflow checked arithmetic
+    pub(crate) fused_branch: Option<String>,
+
+    /// the count of general purpose and floating point registers which hold the named arguments
+    pub(crate) named_args: (i64, i64),
+    /// the stack slot of the register save area of a variadic function
+    pub(crate) va_save: i64,
 }
 
 impl CompilationHelper {
     pub(crate) fn new(arch: Arch, call: MachineCallingConvention) -> Self {
         Self {
             regs: RegVec::new(),
+            fp_regs: RegVec::new(),
             arch: arch,
             vars: HashMap::new(),
+            stack: HashMap::new(),
+            stack_size: 0,
+            spill: 0,
+            func: String::new(),
+            naked: false,
             call: call,
+            func_call: call,
             lower: None,
+            fused_branch: None,
+            named_args: (0, 0),
+            va_save: 0,
         }
     }
 
     /// frees the resources of the variable
     pub(crate) fn free(&mut self, var: &Var) {
-        if let Some(location) = self.vars.get(&var.name) {
+        if let Some(location) = self.vars.remove(&var.name) {
             match location {
-                VarLocation::Reg(reg) => self.regs.push(reg.arch(), reg.clone()),
+                VarLocation::Reg(reg) if reg.is_fp() => self.fp_regs.push(reg.arch(), reg),
+                VarLocation::Reg(reg) => self.regs.push(reg.arch(), reg),
             }
         }
     }
 
     /// allocates resources for a new variable
     pub(crate) fn alloc(&mut self, var: &Var) -> VarLocation {
-        let location = if let Some(reg) = self.regs.pop(self.arch) {
+        let regs = if var.ty.float() { &mut self.fp_regs } else { &mut self.regs };
+
+        let location = if let Some(reg) = regs.pop(self.arch) {
             VarLocation::Reg(match reg {
                 Reg::x64(x64) => Reg::x64(x64.sub_ty(var.ty)),
             })
@@ -58,18 +106,35 @@ impl CompilationHelper {
         location
     }
 
+    /// returns the calling convention of the function type (the one of the target if it doesn't specify one)
+    pub(crate) fn call_conv(&self, ty: &FunctionType) -> MachineCallingConvention {
+        match ty.callconv {
+            Some(conv) => MachineCallingConvention { call_conv: conv },
+            None => self.call,
+        }
+    }
+
     /// passes the arguments into the right register
     pub(crate) fn build_argument_preprocessing(&mut self, func: &Function) {
         let func = &func.ty;
 
         let mut num = 0;
 
-        for ty in &func.args {
+        self.named_args = (0, 0);
+
+        for reg in self.func_call.arg_regs(self.arch, &func.args) {
+            match reg {
+                Some(reg) if reg.is_fp() => self.named_args.1 += 1,
+                Some(_) => self.named_args.0 += 1,
+                None => {},
+            }
+
             let location = {
-                if let Some(reg) = self.call.args(self.arch).get(num) {
-                    VarLocation::Reg(match reg {
-                        Reg::x64(x64) => Reg::x64(x64.sub_ty(*ty)),
-                    })
+                if let Some(reg) = reg {
+                    // the argument lives in its register, so it mustn't be handed out to another variable
+                    if reg.is_fp() { self.fp_regs.remove(self.arch, reg) } else { self.regs.remove(self.arch, reg) }
+
+                    VarLocation::Reg(reg)
                 } else {
                     todo!("The new system currently doesn't support memory")
                 }
@@ -83,6 +148,81 @@ impl CompilationHelper {
             num += 1;
         }
     }
+
+    /// assignes every alloca and phi of the function a stack slot and sets up the stack frame in the entry block
+    pub(crate) fn build_stack_preprocessing(&mut self, func: &Function, block: &Block, mc_sink: &mut Vec<MachineInstr>) {
+        let mut offset: usize = 0;
+
+        for node in func.blocks.iter().flat_map(|block| block.nodes.iter()) {
+            if let Some(alloca) = node.as_any().downcast_ref::<Alloca<Var, TypeMetadata>>() {
+                let size = alloca.inner2.byteSize().max(1);
+
+                offset = (offset + size).next_multiple_of(size);
+
+                self.stack.insert(alloca.inner1.name.to_owned(), offset as i64);
+            } else if let Some(alloca) = node.as_any().downcast_ref::<Alloca<Var, AggregateType>>() {
+                let size = alloca.inner2.size(self.arch).max(1);
+
+                offset = (offset + size).next_multiple_of(alloca.inner2.align(self.arch));
+
+                self.stack.insert(alloca.inner1.name.to_owned(), offset as i64);
+            } else if let Some(phi) = node.as_any().downcast_ref::<Phi>() {
+                let size = phi.out.ty.byteSize().max(1);
+
+                offset = (offset + size).next_multiple_of(size);
+
+                self.stack.insert(phi::phi_slot(&phi.out).name, offset as i64);
+            }
+        }
+
+        // every register which can hold a variable needs a slot to be saved in across calls (and inline assembly)
+        if func.blocks.iter().flat_map(|block| block.nodes.iter()).any(|node| {
+            let node = node.as_any();
+            node.is::<Call<Function, Vec<Var>, Var>>() || node.is::<CallIndirect>() || node.is::<TailCall<Function, Vec<Var>>>()
+                || intrinsic::calls_runtime(node) || node.is::<InlineAsm>()
+        }) {
+            offset = offset.next_multiple_of(8);
+            self.spill = offset as i64;
+
+            offset += 8 * (self.regs.len(self.arch) + self.fp_regs.len(self.arch) + func.ty.args.len());
+        }
+
+        // the prolog of a variadic system v function spills the argument registers into the register save area
+        if func.ty.any_args && self.func_call.call_conv == CallConv::SystemV {
+            offset = offset.next_multiple_of(16) + va::SYSV_REG_SAVE_AREA_SIZE;
+            self.va_save = offset as i64;
+        }
+
+        // a windows callee may spill its register arguments into the 32 bytes above the return address,
+        // so they are kept free at the bottom of the stack frame
+        if func.blocks.iter().flat_map(|block| block.nodes.iter()).any(|node| {
+            let node = node.as_any();
+
+            let conv = if let Some(call) = node.downcast_ref::<Call<Function, Vec<Var>, Var>>() {
+                self.call_conv(&call.inner1.ty)
+            } else if let Some(call) = node.downcast_ref::<CallIndirect>() {
+                self.call_conv(&call.ty)
+            } else if intrinsic::calls_runtime(node) {
+                self.call
+            } else { return false; };
+
+            conv.call_conv == CallConv::WindowsFastCall
+        }) {
+            offset += 32;
+        }
+
+        self.stack_size = offset.next_multiple_of(16) as i64;
+
+        if !self.naked && func.blocks.front().map(|entry| entry.name == block.name).unwrap_or(false) {
+            let mut instr = MachineInstr::new(MachineMnemonic::Prolog);
+            instr.add_operand(MachineOperand::Imm(self.stack_size));
+            mc_sink.push(instr);
+
+            if func.ty.any_args {
+                self.build_va_spill(mc_sink);
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
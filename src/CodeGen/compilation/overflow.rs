@@ -0,0 +1,67 @@
+use crate::prelude::{BrCond, Ir, WithOverflow};
+use crate::IR::{Block, Var};
+use super::{CompilationHelper, VarLocation};
+use crate::CodeGen::{MachineInstr, MachineMnemonic, MachineOperand};
+
+impl CompilationHelper {
+    #[allow(missing_docs)]
+    pub(crate) fn compile_with_overflow(&mut self, node: &WithOverflow, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+        let boxed: Box<dyn Ir> = Box::new(node.clone());
+
+        let mut instr = MachineInstr::new(MachineMnemonic::Overflow(node.op));
+
+        instr.add_operand(self.var_operand(&node.ls));
+        instr.add_operand(self.var_operand(&node.rs));
+
+        if !block.isVarUsedAfterNode(&boxed, &node.ls) {
+            self.free(&node.ls);
+        }
+        if !block.isVarUsedAfterNode(&boxed, &node.rs) {
+            self.free(&node.rs);
+        }
+
+        // the lowering reads every operand before the output is written, so the output can reuse their registers
+        if block.isVarUsedAfterNode(&boxed, &node.out) {
+            match self.alloc(&node.out) {
+                VarLocation::Reg(reg) => instr.set_out(MachineOperand::Reg(reg)),
+            }
+        }
+
+        instr.meta = node.out.ty;
+
+        mc_sink.push(instr);
+
+        let mut flag = if let Some(br) = Self::fusable_branch(node, block) {
+            self.fused_branch = Some(node.overflow.name.to_owned());
+
+            MachineInstr::new(MachineMnemonic::BrOverflow(br.inner2.name.to_owned(), br.inner3.name.to_owned()))
+        } else {
+            let mut flag = MachineInstr::new(MachineMnemonic::SetOverflow);
+
+            if block.isVarUsedAfterNode(&boxed, &node.overflow) {
+                match self.alloc(&node.overflow) {
+                    VarLocation::Reg(reg) => flag.set_out(MachineOperand::Reg(reg)),
+                }
+            }
+
+            flag
+        };
+
+        flag.meta = node.out.ty;
+
+        mc_sink.push(flag);
+    }
+
+    /// returns the conditional branch which directly follows the node if it is the only user of the overflow flag
+    fn fusable_branch<'a>(node: &WithOverflow, block: &'a Block) -> Option<&'a BrCond<Var, Block, Block>> {
+        let next = block.nodes.iter().skip_while(|other| !node.is(other)).nth(1)?;
+
+        let br = next.as_any().downcast_ref::<BrCond<Var, Block, Block>>()?;
+
+        if br.inner1.name != node.overflow.name || block.isVarUsedAfterNode(next, &node.overflow) {
+            return None;
+        }
+
+        Some(br)
+    }
+}
@@ -0,0 +1,76 @@
+use crate::prelude::{Br, BrCond, Ir, Phi, Store, Switch};
+use crate::IR::{Block, Function, TypeMetadata, Var};
+use super::{CompilationHelper, VarLocation};
+use crate::CodeGen::{MachineInstr, MachineMnemonic, MachineOperand};
+
+/// Returns the pointer to the stack slot which holds the value of the phi node
+pub(crate) fn phi_slot(out: &Var) -> Var {
+    Var {
+        name: format!("{}.phi", out.name),
+        ty: TypeMetadata::ptr,
+    }
+}
+
+impl CompilationHelper {
+    #[allow(missing_docs)]
+    pub fn compile_phi(&mut self, node: &Phi, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+        let boxed: Box<dyn Ir> = Box::new(node.clone());
+
+        if !block.isVarUsedAfterNode(&boxed, &node.out) {
+            return;
+        }
+
+        let slot = *self.stack.get(&phi_slot(&node.out).name).expect("expected a stack slot for the phi");
+
+        let out = self.alloc(&node.out);
+
+        let mut instr = MachineInstr::new(MachineMnemonic::Load);
+
+        instr.add_operand(MachineOperand::Stack(slot));
+
+        match out {
+            VarLocation::Reg(reg) => instr.set_out(MachineOperand::Reg(reg)),
+        }
+
+        instr.meta = node.out.ty;
+
+        mc_sink.push(instr);
+    }
+
+    /// Inserts the moves of the incoming edges of all phi nodes before the branch of the block
+    /// 
+    /// Every phi owns a seperate stack slot, so the moves are executed in parallel 
+    /// (the values are all read before a phi reads its slot at the start of its block)
+    pub(crate) fn build_phi_moves(&self, func: &Function, block: &Block) -> Block {
+        let mut moves: Vec<Box<dyn Ir>> = vec![];
+
+        for node in func.blocks.iter().flat_map(|block| block.nodes.iter()) {
+            if let Some(phi) = node.as_any().downcast_ref::<Phi>() {
+                for (from, var) in &phi.recive_from_blocks {
+                    if from.name == block.name {
+                        moves.push(Store::new(phi_slot(&phi.out), var.to_owned()));
+                    }
+                }
+            }
+        }
+
+        let mut block = block.clone();
+
+        if moves.is_empty() {
+            return block;
+        }
+
+        let is_branch = |node: &dyn Ir| {
+            node.as_any().is::<Br<Box<Block>>>() || node.as_any().is::<BrCond<Var, Block, Block>>() || node.as_any().is::<Switch>()
+        };
+
+        let at = match block.nodes.last() {
+            Some(last) if is_branch(last.as_ref()) => block.nodes.len() - 1,
+            _ => block.nodes.len(),
+        };
+
+        block.nodes.splice(at..at, moves);
+
+        block
+    }
+}
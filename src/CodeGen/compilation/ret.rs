@@ -1,4 +1,4 @@
-use crate::{prelude::Return, CodeGen::{MachineInstr, MachineMnemonic, MachineOperand}, IR::{Block, Type, Var}};
+use crate::{prelude::{Return, Unreachable}, CodeGen::{MachineInstr, MachineMnemonic, MachineOperand}, IR::{Block, Type, Var}};
 
 use super::CompilationHelper;
 
@@ -7,11 +7,16 @@ impl CompilationHelper {
     pub fn compile_ret_ty(&mut self, node: &Return<Type>, mc_sink: &mut Vec<MachineInstr>, _: &Block) {
         let mut instr = MachineInstr::new(MachineMnemonic::Move);
 
-        instr.set_out(MachineOperand::Reg(self.call.return_reg(self.arch, node.inner1.into())) );
+        instr.set_out(MachineOperand::Reg(self.func_call.return_reg(self.arch, node.inner1.into())) );
         instr.add_operand(MachineOperand::Imm(node.inner1.val() as i64));
 
+        instr.meta = node.inner1.into();
+
         mc_sink.push( instr );
         
+        if !self.naked {
+            mc_sink.push( MachineInstr::new(MachineMnemonic::Epilog) );
+        }
         mc_sink.push( MachineInstr::new(MachineMnemonic::Return) );
     }
 
@@ -21,14 +26,23 @@ impl CompilationHelper {
         
         let mut instr = MachineInstr::new(MachineMnemonic::Move);
 
-        instr.set_out(MachineOperand::Reg(self.call.return_reg(self.arch, node.inner1.ty)) );
+        instr.set_out(MachineOperand::Reg(self.func_call.return_reg(self.arch, node.inner1.ty)) );
         
         match src {
             super::VarLocation::Reg(reg) => instr.add_operand(MachineOperand::Reg(reg)),
         }
 
+        instr.meta = node.inner1.ty;
+
         mc_sink.push(instr);
 
+        if !self.naked {
+            mc_sink.push( MachineInstr::new(MachineMnemonic::Epilog) );
+        }
         mc_sink.push( MachineInstr::new(MachineMnemonic::Return) );
     }
-}
\ No newline at end of file
+    #[allow(missing_docs)]
+    pub(crate) fn compile_unreachable(&mut self, _: &Unreachable, mc_sink: &mut Vec<MachineInstr>, _: &Block) {
+        mc_sink.push( MachineInstr::new(MachineMnemonic::Unreachable) );
+    }
+}
@@ -0,0 +1,63 @@
+use crate::prelude::{Ir, Select, SelectValue};
+use crate::IR::Block;
+use super::{CompilationHelper, VarLocation};
+use crate::CodeGen::{MachineInstr, MachineMnemonic, MachineOperand};
+
+impl CompilationHelper {
+    #[allow(missing_docs)]
+    pub(crate) fn compile_select<T: SelectValue, U: SelectValue>(&mut self, node: &Select<T, U>, mc_sink: &mut Vec<MachineInstr>, block: &Block) where Select<T, U>: Ir {
+        let boxed: Box<dyn Ir> = Box::new(node.clone());
+
+        let cond = *self.vars.get(&node.cond.name).expect("expected valid variable");
+
+        let yes = self.select_operand(&node.yes);
+        let no = self.select_operand(&node.no);
+
+        if !block.isVarUsedAfterNode(&boxed, &node.cond) {
+            self.free(&node.cond);
+        }
+        if let Some(var) = node.yes.var() {
+            if !block.isVarUsedAfterNode(&boxed, var) {
+                self.free(var);
+            }
+        }
+        if let Some(var) = node.no.var() {
+            if !block.isVarUsedAfterNode(&boxed, var) {
+                self.free(var);
+            }
+        }
+        if !block.isVarUsedAfterNode(&boxed, &node.out) {
+            return; // dead code elimination
+        }
+
+        let out = self.alloc(&node.out);
+
+        let mut instr = MachineInstr::new(MachineMnemonic::Select);
+
+        match cond {
+            VarLocation::Reg(reg) => instr.add_operand(MachineOperand::Reg(reg)),
+        }
+
+        instr.add_operand(yes);
+        instr.add_operand(no);
+
+        match out {
+            VarLocation::Reg(reg) => instr.set_out(MachineOperand::Reg(reg)),
+        }
+
+        instr.meta = node.out.ty;
+
+        mc_sink.push( instr );
+    }
+
+    /// returns the machine operand of the value (the register of a variable or an imm)
+    fn select_operand<T: SelectValue>(&self, value: &T) -> MachineOperand {
+        if let Some(var) = value.var() {
+            match *self.vars.get(&var.name).expect("expected valid variable") {
+                VarLocation::Reg(reg) => MachineOperand::Reg(reg),
+            }
+        } else {
+            MachineOperand::Imm(value.constant().expect("a select value is either a variable or a constant").val() as i64)
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use crate::prelude::{Ir, Store};
+use crate::IR::{Block, Type, Var};
+use super::{CompilationHelper, VarLocation};
+use crate::CodeGen::{MachineInstr, MachineMnemonic, MachineOperand};
+
+impl CompilationHelper {
+    #[allow(missing_docs)]
+    pub fn compile_store_var(&mut self, node: &Store<Var, Var>, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+        let ptr = self.ptr_operand(&node.inner1);
+        let value = *self.vars.get(&node.inner2.name).expect("expected valid variable");
+
+        let boxed: Box<dyn Ir> = Box::new(node.clone());
+
+        if !block.isVarUsedAfterNode(&boxed, &node.inner1) {
+            self.free(&node.inner1)
+        }
+        if !block.isVarUsedAfterNode(&boxed, &node.inner2) {
+            self.free(&node.inner2)
+        }
+
+        let mut instr = MachineInstr::new(MachineMnemonic::Store);
+
+        instr.add_operand(ptr);
+
+        match value {
+            VarLocation::Reg(reg) => instr.add_operand(MachineOperand::Reg(reg)),
+        }
+
+        instr.meta = node.inner2.ty;
+
+        mc_sink.push(instr);
+    }
+
+    #[allow(missing_docs)]
+    pub fn compile_store_ty(&mut self, node: &Store<Var, Type>, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+        let ptr = self.ptr_operand(&node.inner1);
+
+        let boxed: Box<dyn Ir> = Box::new(node.clone());
+
+        if !block.isVarUsedAfterNode(&boxed, &node.inner1) {
+            self.free(&node.inner1)
+        }
+
+        let mut instr = MachineInstr::new(MachineMnemonic::Store);
+
+        instr.add_operand(ptr);
+        instr.add_operand(MachineOperand::Imm(node.inner2.val() as i64));
+
+        instr.meta = node.inner2.into();
+
+        mc_sink.push(instr);
+    }
+}
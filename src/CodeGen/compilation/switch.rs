@@ -0,0 +1,30 @@
+use crate::prelude::Switch;
+use crate::IR::Block;
+use super::{CompilationHelper, VarLocation};
+use crate::CodeGen::{MachineInstr, MachineMnemonic, MachineOperand};
+
+impl CompilationHelper {
+    #[allow(missing_docs)]
+    pub fn compile_switch(&mut self, node: &Switch, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+        let src = *self.vars.get(&node.var.name).expect("expected valid variable");
+
+        // every block has at most one switch (it ends the block), so the name of the jump table is unique
+        let table = format!("{}.{}.switch", self.func, block.name);
+
+        let cases = node.cases.iter().map(|(value, block)| {
+            (value.val() as i64, block.name.to_owned())
+        }).collect();
+
+        let mut instr = MachineInstr::new(
+            MachineMnemonic::Switch(table, node.default.name.to_owned(), cases)
+        );
+
+        match src {
+            VarLocation::Reg(reg) => instr.add_operand(MachineOperand::Reg(reg)),
+        }
+
+        instr.meta = node.var.ty;
+
+        mc_sink.push( instr );
+    }
+}
@@ -0,0 +1,101 @@
+use crate::prelude::{Ir, VaArg, VaEnd, VaStart};
+use crate::Target::CallConv;
+use crate::IR::{Block, TypeMetadata};
+use super::{CompilationHelper, VarLocation};
+use crate::CodeGen::{MachineInstr, MachineMnemonic, MachineOperand};
+
+/// the size of the register save area of system v (6 general purpose registers and 8 xmm registers)
+pub(crate) const SYSV_REG_SAVE_AREA_SIZE: usize = 6 * 8 + 8 * 16;
+
+impl CompilationHelper {
+    /// spills the argument registers of a variadic function, so `va_arg` can read every argument from memory
+    pub(crate) fn build_va_spill(&self, mc_sink: &mut Vec<MachineInstr>) {
+        let mut spill = |slot: i64, reg, ty| {
+            let mut instr = MachineInstr::new(MachineMnemonic::Store);
+            instr.add_operand(MachineOperand::Stack(slot));
+            instr.add_operand(MachineOperand::Reg(reg));
+            instr.meta = ty;
+            mc_sink.push(instr);
+        };
+
+        let args = self.func_call.args(self.arch);
+
+        if self.func_call.call_conv == CallConv::WindowsFastCall {
+            // the home slots of the caller (floats are also passed in the general purpose registers)
+            for (index, reg) in args.into_iter().enumerate() {
+                spill(-16 - 8 * index as i64, reg, TypeMetadata::i64);
+            }
+        } else {
+            for (index, reg) in args.into_iter().enumerate() {
+                spill(self.va_save - 8 * index as i64, reg, TypeMetadata::i64);
+            }
+
+            for (index, reg) in self.func_call.fp_args(self.arch).into_iter().enumerate() {
+                spill(self.va_save - 6 * 8 - 16 * index as i64, reg, TypeMetadata::f64);
+            }
+        }
+    }
+
+    #[allow(missing_docs)]
+    pub(crate) fn compile_va_start(&mut self, node: &VaStart, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+        let conv = self.func_call.call_conv;
+
+        let mut instr = MachineInstr::new(MachineMnemonic::VaStart(conv));
+
+        instr.add_operand(self.ptr_operand(&node.list));
+
+        let (gp, fp) = self.named_args;
+
+        if conv == CallConv::WindowsFastCall {
+            // the caller spilled every argument next to the ones passed over the stack
+            instr.add_operand(MachineOperand::Stack(-16 - 8 * (gp + fp)));
+        } else {
+            instr.add_operand(MachineOperand::Stack(-16));
+            instr.add_operand(MachineOperand::Imm(8 * gp));
+            instr.add_operand(MachineOperand::Imm(6 * 8 + 16 * fp));
+            instr.add_operand(MachineOperand::Stack(self.va_save));
+        }
+
+        mc_sink.push(instr);
+
+        let boxed: Box<dyn Ir> = Box::new(node.clone());
+
+        if !block.isVarUsedAfterNode(&boxed, &node.list) {
+            self.free(&node.list)
+        }
+    }
+
+    #[allow(missing_docs)]
+    pub(crate) fn compile_va_arg(&mut self, node: &VaArg, mc_sink: &mut Vec<MachineInstr>, block: &Block) {
+        let mut instr = MachineInstr::new(MachineMnemonic::VaArg(self.func_call.call_conv));
+
+        instr.add_operand(self.ptr_operand(&node.list));
+
+        let boxed: Box<dyn Ir> = Box::new(node.clone());
+
+        if !block.isVarUsedAfterNode(&boxed, &node.list) {
+            self.free(&node.list)
+        }
+
+        // the list is advanced even if the argument isn't used
+        if block.isVarUsedAfterNode(&boxed, &node.out) {
+            match self.alloc(&node.out) {
+                VarLocation::Reg(reg) => instr.set_out(MachineOperand::Reg(reg)),
+            }
+        }
+
+        instr.meta = node.out.ty;
+
+        mc_sink.push(instr);
+    }
+
+    #[allow(missing_docs)]
+    pub(crate) fn compile_va_end(&mut self, node: &VaEnd, _: &mut Vec<MachineInstr>, block: &Block) {
+        // the va_list doesn't hold any resources on the supported calling conventions
+        let boxed: Box<dyn Ir> = Box::new(node.clone());
+
+        if !block.isVarUsedAfterNode(&boxed, &node.list) {
+            self.free(&node.list)
+        }
+    }
+}
@@ -1,8 +1,9 @@
 use std::error::Error;
 use std::fmt::{Debug, Display};
 use std::any::Any;
-use crate::prelude::CmpMode;
-use crate::Obj::Link;
+use crate::prelude::{AtomicOrdering, AtomicRmwOp, CmpMode, OverflowOp};
+use crate::Obj::{Link, TlsModel};
+use crate::Target::CallConv;
 use crate::IR::TypeMetadata;
 
 use super::reg::Reg;
@@ -68,6 +69,8 @@ pub enum MachineOperand {
     Imm(i64),
     /// a register
     Reg(Reg),
+    /// a stack slot (the offset from the frame base)
+    Stack(i64),
 }
 
 impl Display for MachineOperand {
@@ -75,6 +78,7 @@ impl Display for MachineOperand {
         write!(f, "{}", match self {
             MachineOperand::Imm(imm) => format!("{:#x?}", imm),
             MachineOperand::Reg(reg) => format!("{:?}", reg),
+            MachineOperand::Stack(off) => format!("[stack - {:#x?}]", off),
         })
     }
 }
@@ -92,18 +96,95 @@ pub enum MachineMnemonic {
     Or,
     Sub,
     Xor,
+    Rem,
+    Shl,
+    Lshr,
+    Ashr,
+
+    Neg,
+    Not,
+
+    /// reverses the byte order
+    Bswap,
+    /// counts the set bits
+    Ctpop,
+    /// counts the leading zero bits (the bit size for zero)
+    Ctlz,
+    /// counts the trailing zero bits (the bit size for zero)
+    Cttz,
 
     BrCond(/*if yes*/String, /*if no*/String),
     Compare(CmpMode),
+    /// chooses the second operand if the condition (first operand) is true, else the third operand
+    Select,
 
     Zext,
+    Sext,
     Downcast,
+    /// a cast from or to a floating point type (the type is the source type)
+    FCast(TypeMetadata),
 
     Call(String),
+    /// calls the function the pointer points to (operands: the count of used floating point registers, the stack slot of the pointer)
+    CallIndirect,
+    /// jumps to the function after the stack frame was destroyed (operand: the count of used floating point registers)
+    TailCall(String),
     Br(String),
     Return,
+    /// traps, because the code should never be executed
+    Unreachable,
+    /// jumps to the block of the matching case (the jump table, the default block, the cases)
+    Switch(String, String, Vec<(i64, String)>),
 
     AdressLoad(String),
+    /// loads the adress of the thread local symbol (of the current thread)
+    TlsAdressLoad(String, TlsModel),
+
+    /// sets up the stack frame (operand: the number of bytes to reserve for stack slots)
+    Prolog,
+    /// destroys the stack frame
+    Epilog,
+    /// the adress of a stack slot (operand: the offset of the slot from the frame base)
+    Alloca,
+    /// loads the value the pointer points to
+    Load,
+    /// stores the value (second operand) into the memory the pointer (first operand) points to
+    Store,
+    /// computes the adress `pointer + index * scale` (operands: the pointer, the index, the scale)
+    Gep,
+    /// copies memory (operands: the destination pointer, the source pointer, the constant byte count)
+    MemCpy,
+    /// fills memory with a byte (operands: the destination pointer, the byte value, the constant byte count)
+    MemSet,
+
+    /// atomically loads the value the pointer points to
+    AtomicLoad(AtomicOrdering),
+    /// atomically stores the value (second operand) into the memory the pointer (first operand) points to
+    AtomicStore(AtomicOrdering),
+    /// atomically combines the memory with the value (operands: the pointer, the value), the output is the old value
+    AtomicRmw(AtomicRmwOp, AtomicOrdering),
+    /// atomically replaces the memory if it holds the expected value (operands: the pointer, the expected value, the new value),
+    /// the output is the old value
+    AtomicCmpXchg(AtomicOrdering),
+    /// orders the memory accesses before against the ones after it
+    Fence(AtomicOrdering),
+
+    /// computes the operation (operands: ls, rs) and leaves the overflow in the flags,
+    /// it needs to be directly followed by a `SetOverflow` or `BrOverflow`
+    Overflow(OverflowOp),
+    /// sets the output to true if the preceding `Overflow` overflowed (without output it only ends the sequence)
+    SetOverflow,
+    /// jumps to the first block if the preceding `Overflow` overflowed, else to the second one
+    BrOverflow(String, String),
+
+    /// initializes the va_list (operands: the pointer to the list, the area of the arguments passed over the stack
+    /// and for system v the offsets of the first variadic general purpose and floating point register in the register save area and the area itself)
+    VaStart(CallConv),
+    /// reads the next variadic argument as the output type (operands: the pointer to the list)
+    VaArg(CallConv),
+
+    /// the assembly string which is assembled by the target registry
+    InlineAsm(String),
 }
 
 impl MachineMnemonic {
@@ -118,14 +199,51 @@ impl MachineMnemonic {
             MachineMnemonic::Or => "or",
             MachineMnemonic::Sub => "sub",
             MachineMnemonic::Xor => "xor",
+            MachineMnemonic::Rem => "rem",
+            MachineMnemonic::Shl => "shl",
+            MachineMnemonic::Lshr => "lshr",
+            MachineMnemonic::Ashr => "ashr",
+            MachineMnemonic::Neg => "neg",
+            MachineMnemonic::Not => "not",
+            MachineMnemonic::Bswap => "bswap",
+            MachineMnemonic::Ctpop => "ctpop",
+            MachineMnemonic::Ctlz => "ctlz",
+            MachineMnemonic::Cttz => "cttz",
             MachineMnemonic::Zext => "zext",
+            MachineMnemonic::Sext => "sext",
             MachineMnemonic::Downcast => "dwcast",
+            MachineMnemonic::FCast(_) => "fcast",
             MachineMnemonic::Call(_) => "call",
             MachineMnemonic::Return => "return",
             MachineMnemonic::AdressLoad(_) => "adrload",
+            MachineMnemonic::TlsAdressLoad(_, _) => "tls_adrload",
             MachineMnemonic::Br(_) => "br",
             MachineMnemonic::BrCond(_, _) => "comparebr",
+            MachineMnemonic::Switch(_, _, _) => "switch",
             MachineMnemonic::Compare(_) => "compare",
+            MachineMnemonic::Select => "select",
+            MachineMnemonic::Prolog => "prolog",
+            MachineMnemonic::Epilog => "epilog",
+            MachineMnemonic::Alloca => "alloca",
+            MachineMnemonic::Load => "load",
+            MachineMnemonic::Store => "store",
+            MachineMnemonic::Gep => "gep",
+            MachineMnemonic::MemCpy => "memcpy",
+            MachineMnemonic::MemSet => "memset",
+            MachineMnemonic::AtomicLoad(_) => "atomic_load",
+            MachineMnemonic::AtomicStore(_) => "atomic_store",
+            MachineMnemonic::AtomicRmw(_, _) => "atomic_rmw",
+            MachineMnemonic::AtomicCmpXchg(_) => "atomic_cmpxchg",
+            MachineMnemonic::Fence(_) => "fence",
+            MachineMnemonic::Overflow(_) => "overflow",
+            MachineMnemonic::SetOverflow => "set_overflow",
+            MachineMnemonic::BrOverflow(_, _) => "overflowbr",
+            MachineMnemonic::CallIndirect => "indirect_call",
+            MachineMnemonic::TailCall(_) => "tailcall",
+            MachineMnemonic::Unreachable => "unreachable",
+            MachineMnemonic::VaStart(_) => "va_start",
+            MachineMnemonic::VaArg(_) => "va_arg",
+            MachineMnemonic::InlineAsm(_) => "inline_asm",
         }.to_string()
     }
 }
@@ -134,7 +252,19 @@ impl Display for MachineMnemonic {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", match self {
             MachineMnemonic::Call(target) => format!("{} {}", self.name(), target),
+            MachineMnemonic::TailCall(target) => format!("{} {}", self.name(), target),
             MachineMnemonic::AdressLoad(adr) => format!("{} {}", self.name(), adr),
+            MachineMnemonic::TlsAdressLoad(adr, model) => format!("{} {} {}", self.name(), adr, model),
+            MachineMnemonic::FCast(from) => format!("{} {}", self.name(), from),
+            MachineMnemonic::AtomicLoad(order) | MachineMnemonic::AtomicStore(order) |
+            MachineMnemonic::AtomicCmpXchg(order) | MachineMnemonic::Fence(order) => format!("{} {}", self.name(), order),
+            MachineMnemonic::AtomicRmw(op, order) => format!("{} {} {}", self.name(), op, order),
+            MachineMnemonic::Overflow(op) => format!("{} {}", self.name(), op),
+            MachineMnemonic::VaStart(conv) | MachineMnemonic::VaArg(conv) => format!("{} {}", self.name(), conv),
+            MachineMnemonic::InlineAsm(asm) => format!("{} {:?}", self.name(), asm),
+            MachineMnemonic::Switch(_, default, cases) => format!("{} {}{}", self.name(), default, 
+                cases.iter().map(|(value, block)| format!(", [{}, {}]", value, block)).collect::<String>()
+            ),
             _ => self.name()
         })
     }
@@ -147,4 +277,29 @@ pub trait MCInstr: Any + Debug + Display {
 
     /// encodes the instruction
     fn encode(&self) -> Result<(Vec<u8>, Option<Link>), Box<dyn Error>>;
-}
\ No newline at end of file
+}
+
+/// machine code which was assembled out of inline assembly
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsmMCInstr {
+    /// the assembled instructions
+    pub asm: Vec<String>,
+    /// the machine code of the instructions
+    pub code: Vec<u8>,
+}
+
+impl Display for AsmMCInstr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.asm.join("\n"))
+    }
+}
+
+impl MCInstr for AsmMCInstr {
+    fn dump(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        Ok(self.asm.clone())
+    }
+
+    fn encode(&self) -> Result<(Vec<u8>, Option<Link>), Box<dyn Error>> {
+        Ok((self.code.clone(), None))
+    }
+}
@@ -8,10 +8,39 @@ pub enum Reg {
 }
 
 impl Reg {
+    /// parses the name of a register of the architecture
+    pub fn parse(arch: Arch, name: &str) -> Option<Reg> {
+        match arch {
+            Arch::X86_64 => x64Reg::parse(name.to_string()).map(Reg::x64),
+            _ => None,
+        }
+    }
+
     /// returns the architecture of the register
     pub fn arch(&self) -> Arch {
         match self {
             Reg::x64(_) => Arch::X86_64,
         }
     }
+
+    /// returns if the register is a floating point register
+    pub fn is_fp(&self) -> bool {
+        match self {
+            Reg::x64(x64) => x64.is_xmm(),
+        }
+    }
+
+    /// returns if the register needs to be preserved across calls
+    pub fn is_callee_saved(&self) -> bool {
+        match self {
+            Reg::x64(x64) => x64.is_callee_saved(),
+        }
+    }
+
+    /// returns if both registers are (parts of) the same physical register
+    pub fn same_as(&self, other: &Reg) -> bool {
+        match (self, other) {
+            (Reg::x64(ls), Reg::x64(rs)) => ls.sub64() == rs.sub64(),
+        }
+    }
 }
\ No newline at end of file
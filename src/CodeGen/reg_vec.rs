@@ -34,6 +34,18 @@ impl RegVec {
         } else { None }
     }
 
+    /// removes the register (in any size) from the registers of the arch
+    pub fn remove(&mut self, arch: Arch, reg: Reg) {
+        if let Some(entry) = self.regs.get_mut(&arch) {
+            entry.retain(|entry| !entry.same_as(&reg));
+        }
+    }
+
+    /// returns the count of registers of the arch
+    pub fn len(&self, arch: Arch) -> usize {
+        self.regs.get(&arch).map(|entry| entry.len()).unwrap_or(0)
+    }
+
     /// reversees the registers of the arch
     pub fn reverse(&mut self, arch: Arch) {
         if let Some(entry) = self.regs.get_mut(&arch) {
@@ -0,0 +1,149 @@
+use std::fmt::Display;
+
+use crate::Target::Arch;
+
+use super::TypeMetadata;
+
+/// The type of a struct field or an array element
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ElementType {
+    /// A scalar type
+    Scalar(TypeMetadata),
+    /// A nested struct or array
+    Aggregate(AggregateType),
+}
+
+/// A type which is built out of other types (structs and arrays)
+///
+/// The memory layout (size, alignment and field offsets) depends on the target architecture
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AggregateType {
+    /// A named struct with its field types
+    Struct {
+        /// the name of the struct
+        name: String,
+        /// the types of the fields
+        fields: Vec<ElementType>,
+    },
+    /// An array with a fixed number of elements
+    Array {
+        /// the type of the elements
+        elem: Box<ElementType>,
+        /// the number of elements
+        len: usize,
+    },
+}
+
+impl ElementType {
+    /// Returns the size of the type in bytes for the given architecture
+    pub fn size(&self, arch: Arch) -> usize {
+        match self {
+            ElementType::Scalar(TypeMetadata::ptr) => arch.ptrSize(),
+            ElementType::Scalar(ty) => ty.byteSize(),
+            ElementType::Aggregate(ty) => ty.size(arch),
+        }
+    }
+
+    /// Returns the alignment of the type in bytes for the given architecture
+    pub fn align(&self, arch: Arch) -> usize {
+        match self {
+            ElementType::Scalar(_) => self.size(arch).clamp(1, arch.maxScalarAlign()),
+            ElementType::Aggregate(ty) => ty.align(arch),
+        }
+    }
+}
+
+impl AggregateType {
+    /// Returns the number of fields or elements
+    pub fn len(&self) -> usize {
+        match self {
+            AggregateType::Struct { fields, .. } => fields.len(),
+            AggregateType::Array { len, .. } => *len,
+        }
+    }
+
+    /// Returns if the type has no fields or elements
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the type of the field or element at the given index
+    pub fn element(&self, index: usize) -> Option<&ElementType> {
+        match self {
+            AggregateType::Struct { fields, .. } => fields.get(index),
+            AggregateType::Array { elem, len } => if index < *len { Some(elem) } else { None },
+        }
+    }
+
+    /// Returns the offset in bytes of the field or element at the given index
+    pub fn offset(&self, index: usize, arch: Arch) -> usize {
+        match self {
+            AggregateType::Struct { fields, .. } => {
+                let mut offset: usize = 0;
+
+                for field in fields.iter().take(index) {
+                    offset = offset.next_multiple_of(field.align(arch)) + field.size(arch);
+                }
+
+                if let Some(field) = fields.get(index) {
+                    offset = offset.next_multiple_of(field.align(arch));
+                }
+
+                offset
+            },
+            AggregateType::Array { elem, .. } => index * elem.size(arch),
+        }
+    }
+
+    /// Returns the size of the type in bytes (including the tail padding) for the given architecture
+    pub fn size(&self, arch: Arch) -> usize {
+        match self {
+            AggregateType::Struct { fields, .. } => {
+                let end = match fields.last() {
+                    Some(last) => self.offset(fields.len() - 1, arch) + last.size(arch),
+                    None => 0,
+                };
+
+                end.next_multiple_of(self.align(arch))
+            },
+            AggregateType::Array { elem, len } => elem.size(arch) * len,
+        }
+    }
+
+    /// Returns the alignment of the type in bytes for the given architecture
+    pub fn align(&self, arch: Arch) -> usize {
+        match self {
+            AggregateType::Struct { fields, .. } => fields.iter().map(|field| field.align(arch)).max().unwrap_or(1),
+            AggregateType::Array { elem, .. } => elem.align(arch),
+        }
+    }
+
+    /// Returns the definition of the type like it is written in a module level type statement
+    pub fn body(&self) -> String {
+        match self {
+            AggregateType::Struct { fields, .. } => {
+                let fields = fields.iter().map(|field| field.to_string()).collect::<Vec<String>>();
+                format!("{{ {} }}", fields.join(", "))
+            },
+            AggregateType::Array { .. } => self.to_string(),
+        }
+    }
+}
+
+impl Display for ElementType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ElementType::Scalar(ty) => write!(f, "{}", ty),
+            ElementType::Aggregate(ty) => write!(f, "{}", ty),
+        }
+    }
+}
+
+impl Display for AggregateType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AggregateType::Struct { name, .. } => write!(f, "{}", name),
+            AggregateType::Array { elem, len } => write!(f, "[{} x {}]", elem, len),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::Support::{ColorProfile, Colorize};
 
-use super::{ir::Ir, Function, Var, VerifyError};
+use super::{ir::{Ir, Phi}, Function, Var, VerifyError};
 
 /// A basic block: stores ir of a specific area of a function
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -63,9 +63,26 @@ impl Block {
 
     /// Verifys if the block and all of its ir nodes are correct:
     ///  * Checks if the return type is the actual specified return type of the function
+    ///  * Checks if all phi nodes are at the start of the block and only recive values from existing blocks
     pub fn verify(&self, func: &Function) -> Result<(), VerifyError> {
+        let mut phis_allowed = true;
+
         for node in &self.nodes {
             node.verify(func.ty.clone())?;
+
+            if let Some(phi) = node.as_any().downcast_ref::<Phi>() {
+                if !phis_allowed {
+                    Err(VerifyError::PhiIsntAtBlockStart)?
+                }
+
+                for (block, _) in &phi.recive_from_blocks {
+                    if !func.blocks.iter().any(|func_block| func_block.name == block.name) {
+                        Err(VerifyError::PhiFromUnknownBlock)?
+                    }
+                }
+            } else {
+                phis_allowed = false;
+            }
         }
 
         Ok(())
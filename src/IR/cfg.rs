@@ -0,0 +1,304 @@
+use std::collections::HashMap;
+
+use super::{ir::Opcode, Function};
+
+/// The control flow graph of a function
+///
+/// The blocks are referred to by their index in the function (the entry block is `0`).
+/// A block which doesn't end in a branch, return, tail call or unreachable node falls through
+/// into the next block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cfg {
+    names: HashMap<String, usize>,
+    succs: Vec<Vec<usize>>,
+    preds: Vec<Vec<usize>>,
+    rpo: Vec<usize>,
+    reachable: Vec<bool>,
+}
+
+impl Cfg {
+    /// Builds the control flow graph of the function
+    pub fn new(func: &Function) -> Self {
+        let names = func.blocks.iter()
+            .enumerate()
+            .map(|(index, block)| (block.name.to_owned(), index))
+            .collect::<HashMap<_, _>>();
+
+        let mut succs = vec![vec![]; func.blocks.len()];
+        let mut preds = vec![vec![]; func.blocks.len()];
+
+        for (index, block) in func.blocks.iter().enumerate() {
+            let targets = match block.nodes.last() {
+                Some(node) if matches!(node.opcode(), Opcode::Br | Opcode::BrCond | Opcode::Switch) => {
+                    node.successors().iter().filter_map(|target| names.get(&target.name).copied()).collect()
+                },
+                Some(node) if matches!(node.opcode(), Opcode::Ret | Opcode::TailCall | Opcode::Unreachable) => vec![],
+                _ if index + 1 < func.blocks.len() => vec![index + 1],
+                _ => vec![],
+            };
+
+            for target in targets {
+                // a switch can have multiple cases with the same block
+                if !succs[index].contains(&target) {
+                    succs[index].push(target);
+                    preds[target].push(index);
+                }
+            }
+        }
+
+        let mut cfg = Self {
+            names: names,
+            succs: succs,
+            preds: preds,
+            rpo: vec![],
+            reachable: vec![false; func.blocks.len()],
+        };
+
+        cfg.compute_rpo();
+
+        cfg
+    }
+
+    /// Computes the reverse post order of the blocks which are reachable from the entry block
+    fn compute_rpo(&mut self) {
+        if self.succs.is_empty() {
+            return;
+        }
+
+        let mut post_order = vec![];
+
+        // the block and the index of the next successor which gets visited
+        let mut stack = vec![(0, 0)];
+        self.reachable[0] = true;
+
+        while let Some((block, next)) = stack.last_mut() {
+            if let Some(&succ) = self.succs[*block].get(*next) {
+                *next += 1;
+
+                if !self.reachable[succ] {
+                    self.reachable[succ] = true;
+                    stack.push((succ, 0));
+                }
+            } else {
+                post_order.push(*block);
+                stack.pop();
+            }
+        }
+
+        post_order.reverse();
+        self.rpo = post_order;
+    }
+
+    /// Returns the index of the block with the name
+    pub fn block(&self, name: &str) -> Option<usize> {
+        self.names.get(name).copied()
+    }
+
+    /// Returns the amount of blocks in the graph
+    pub fn len(&self) -> usize {
+        self.succs.len()
+    }
+
+    /// Returns if the function has no blocks
+    pub fn is_empty(&self) -> bool {
+        self.succs.is_empty()
+    }
+
+    /// Returns the blocks which the block can branch to
+    pub fn successors(&self, block: usize) -> &[usize] {
+        &self.succs[block]
+    }
+
+    /// Returns the blocks which can branch to the block
+    pub fn predecessors(&self, block: usize) -> &[usize] {
+        &self.preds[block]
+    }
+
+    /// Returns the blocks which are reachable from the entry block in reverse post order
+    /// (every block comes before its successors, except for the targets of back edges)
+    pub fn reverse_post_order(&self) -> &[usize] {
+        &self.rpo
+    }
+
+    /// Returns if the block can be reached from the entry block
+    pub fn is_reachable(&self, block: usize) -> bool {
+        self.reachable[block]
+    }
+}
+
+/// The dominator tree of a function
+///
+/// A block dominates another block if every path from the entry block to the other block goes through it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DomTree {
+    idoms: Vec<Option<usize>>,
+    children: Vec<Vec<usize>>,
+    /// the index of the blocks in the reverse post order
+    order: Vec<usize>,
+}
+
+impl DomTree {
+    /// Builds the dominator tree out of the control flow graph
+    /// (using the algorithm of Cooper, Harvey and Kennedy)
+    pub fn new(cfg: &Cfg) -> Self {
+        let mut order = vec![usize::MAX; cfg.len()];
+
+        for (index, block) in cfg.reverse_post_order().iter().enumerate() {
+            order[*block] = index;
+        }
+
+        // the entry block is its own immediate dominator while the tree gets built
+        let mut idoms: Vec<Option<usize>> = vec![None; cfg.len()];
+
+        if let Some(&entry) = cfg.reverse_post_order().first() {
+            idoms[entry] = Some(entry);
+        }
+
+        let intersect = |idoms: &[Option<usize>], mut ls: usize, mut rs: usize| {
+            while ls != rs {
+                while order[ls] > order[rs] {
+                    ls = idoms[ls].expect("processed blocks have an immediate dominator");
+                }
+
+                while order[rs] > order[ls] {
+                    rs = idoms[rs].expect("processed blocks have an immediate dominator");
+                }
+            }
+
+            ls
+        };
+
+        let mut changed = true;
+
+        while changed {
+            changed = false;
+
+            for &block in cfg.reverse_post_order().iter().skip(1) {
+                let mut new_idom = None;
+
+                for &pred in cfg.predecessors(block) {
+                    if idoms[pred].is_none() {
+                        continue;
+                    }
+
+                    new_idom = Some(match new_idom {
+                        Some(idom) => intersect(&idoms, pred, idom),
+                        None => pred,
+                    });
+                }
+
+                if new_idom.is_some() && idoms[block] != new_idom {
+                    idoms[block] = new_idom;
+                    changed = true;
+                }
+            }
+        }
+
+        if let Some(&entry) = cfg.reverse_post_order().first() {
+            idoms[entry] = None;
+        }
+
+        let mut children = vec![vec![]; cfg.len()];
+
+        for &block in cfg.reverse_post_order() {
+            if let Some(idom) = idoms[block] {
+                children[idom].push(block);
+            }
+        }
+
+        Self {
+            idoms: idoms,
+            children: children,
+            order: order,
+        }
+    }
+
+    /// Returns the immediate dominator of the block
+    /// (`None` for the entry block and unreachable blocks)
+    pub fn idom(&self, block: usize) -> Option<usize> {
+        self.idoms[block]
+    }
+
+    /// Returns the blocks which are immediately dominated by the block
+    pub fn children(&self, block: usize) -> &[usize] {
+        &self.children[block]
+    }
+
+    /// Returns if the block dominates the other block (every block dominates itself)
+    pub fn dominates(&self, block: usize, other: usize) -> bool {
+        // unreachable blocks aren't part of the tree
+        if self.order[block] == usize::MAX || self.order[other] == usize::MAX {
+            return false;
+        }
+
+        let mut current = Some(other);
+
+        while let Some(dominator) = current {
+            if dominator == block {
+                return true;
+            }
+
+            current = self.idoms[dominator];
+        }
+
+        false
+    }
+}
+
+/// The dominance frontiers of the blocks of a function
+///
+/// The dominance frontier of a block are the blocks where its dominance ends
+/// (they are the places where phi nodes are needed for the variables which are defined in the block)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DominanceFrontiers {
+    frontiers: Vec<Vec<usize>>,
+}
+
+impl DominanceFrontiers {
+    /// Computes the dominance frontiers out of the control flow graph and the dominator tree
+    pub fn new(cfg: &Cfg, dom: &DomTree) -> Self {
+        let mut frontiers = vec![vec![]; cfg.len()];
+
+        for &block in cfg.reverse_post_order() {
+            if cfg.predecessors(block).len() < 2 {
+                continue;
+            }
+
+            for &pred in cfg.predecessors(block) {
+                if !cfg.is_reachable(pred) {
+                    continue;
+                }
+
+                let mut runner = Some(pred);
+
+                while let Some(current) = runner {
+                    if Some(current) == dom.idom(block) {
+                        break;
+                    }
+
+                    if !frontiers[current].contains(&block) {
+                        frontiers[current].push(block);
+                    }
+
+                    runner = dom.idom(current);
+                }
+            }
+        }
+
+        Self {
+            frontiers: frontiers,
+        }
+    }
+
+    /// Returns the dominance frontier of the block
+    pub fn frontier(&self, block: usize) -> &[usize] {
+        &self.frontiers[block]
+    }
+}
+
+impl Function {
+    /// Builds the control flow graph of the function
+    pub fn cfg(&self) -> Cfg {
+        Cfg::new(self)
+    }
+}
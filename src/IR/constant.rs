@@ -1,5 +1,81 @@
-use crate::Obj::Linkage;
+use crate::Obj::{Linkage, TlsModel, Visibility};
 
+/// The address of a symbol (plus an addend) inside of the data of a constant or global
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolRef {
+    /// the offset of the 8 byte address in the data
+    pub at: usize,
+    /// the symbol of which the address is used
+    pub symbol: String,
+    /// the addend to the address of the symbol
+    pub addend: i64,
+}
+
+/// Returns the data with the symbol references like it is written in the ir
+/// (`is_func` tells if a symbol is a function)
+pub(crate) fn fmt_data(data: &[u8], refs: &[SymbolRef], is_func: &dyn Fn(&str) -> bool) -> String {
+    let mut entries = vec![];
+
+    let mut index = 0;
+
+    while index < data.len() {
+        if let Some(sym) = refs.iter().find(|sym| sym.at == index) {
+            let prefix = if is_func(&sym.symbol) { "@" } else { "" };
+
+            entries.push(match sym.addend {
+                0 => format!("ptr {}{}", prefix, sym.symbol),
+                addend if addend < 0 => format!("ptr {}{}{}", prefix, sym.symbol, addend),
+                addend => format!("ptr {}{}+{}", prefix, sym.symbol, addend),
+            });
+
+            index += 8;
+        } else {
+            entries.push(data[index].to_string());
+            index += 1;
+        }
+    }
+
+    format!("[{}]", entries.join(", "))
+}
+
+/// Returns the linkage and the visibility like they are written before the name of a constant or global
+pub(crate) fn fmt_linkage(linkage: Linkage, visibility: Visibility) -> String {
+    let mut fmt = String::new();
+
+    if linkage != Linkage::External {
+        fmt += &format!("{} ", linkage);
+    }
+
+    if visibility != Visibility::Default {
+        fmt += &format!("{} ", visibility);
+    }
+
+    fmt
+}
+
+/// Returns the thread local marker like it is written before the name of a constant or global
+pub(crate) fn fmt_thread_local(thread_local: Option<TlsModel>) -> String {
+    match thread_local {
+        Some(TlsModel::LocalExec) => "thread_local ".to_owned(),
+        Some(model) => format!("thread_local({}) ", model),
+        None => String::new(),
+    }
+}
+
+/// Returns the section and the alignment like they are written after a function signature or the data of a constant or global
+pub(crate) fn fmt_placement(section: &Option<String>, align: Option<u64>) -> String {
+    let mut fmt = String::new();
+
+    if let Some(section) = section {
+        fmt += &format!(" section \"{}\"", section);
+    }
+
+    if let Some(align) = align {
+        fmt += &format!(" align {}", align);
+    }
+
+    fmt
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 /// A constant
@@ -7,6 +83,15 @@ pub struct Const {
     pub(crate) name: String,
     pub(crate) data: Vec<u8>,
     pub(crate) linkage: Linkage,
+    pub(crate) visibility: Visibility,
+    /// the model which is used to access it if it is thread local
+    pub(crate) thread_local: Option<TlsModel>,
+    /// the section in which it is placed (if it isn't placed in the default one)
+    pub(crate) section: Option<String>,
+    /// the alignment of it
+    pub(crate) align: Option<u64>,
+    /// the addresses of symbols which are placed in the data
+    pub(crate) refs: Vec<SymbolRef>,
 }
 
 impl Const {
@@ -16,6 +101,11 @@ impl Const {
             name: name,
             data: vec![],
             linkage: Linkage::Internal,
+            visibility: Visibility::Default,
+            thread_local: None,
+            section: None,
+            align: None,
+            refs: vec![],
         }
     }
 
@@ -28,6 +118,13 @@ impl Const {
     pub fn get(&mut self) -> Vec<u8>{
         self.data.clone()
     }
+
+    /// appends the address of the symbol (plus the addend) to the data
+    /// (the address is filled in by the linker)
+    pub fn add_ref(&mut self, symbol: &str, addend: i64) {
+        self.refs.push(SymbolRef { at: self.data.len(), symbol: symbol.to_owned(), addend: addend });
+        self.data.extend_from_slice(&[0; 8]);
+    }
     
     /// Sets that the constant is externally visible
     pub fn extrn(&mut self) {
@@ -43,4 +140,40 @@ impl Const {
     pub fn private(&mut self) {
         self.linkage = Linkage::Internal;
     }
+
+    /// Sets that the constant is externally visible, but can be overridden by a non weak definition of another object file
+    pub fn weak(&mut self) {
+        self.linkage = Linkage::Weak;
+    }
+
+    /// Sets that the constant is externally visible and that the linker only keeps one of its definitions (used for e.g: generic instances)
+    pub fn linkonce(&mut self) {
+        self.linkage = Linkage::LinkOnce;
+    }
+
+    /// Sets the visibility of the constant (e.g: `hidden` ones aren't exported from shared libraries)
+    pub fn set_visibility(&mut self, visibility: Visibility) {
+        self.visibility = visibility;
+    }
+
+    /// Sets that the constant is thread local (every thread has its own copy of it) and how its adress is computed
+    pub fn set_thread_local(&mut self, model: TlsModel) {
+        self.thread_local = Some(model);
+    }
+
+    /// Returns if the constant is thread local
+    pub fn is_thread_local(&self) -> bool {
+        self.thread_local.is_some()
+    }
+
+    /// Places the constant in the section with the given name (e.g: `.init_array`)
+    pub fn set_section(&mut self, section: &str) {
+        self.section = Some(section.to_owned());
+    }
+
+    /// Sets the alignment of the constant (needs to be a power of two)
+    pub fn set_align(&mut self, align: u64) {
+        assert!(align.is_power_of_two(), "the alignment needs to be a power of two");
+        self.align = Some(align);
+    }
 }
\ No newline at end of file
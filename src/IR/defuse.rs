@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+use super::{ir::OperandMut, Function, Var};
+
+/// The position of an ir node in a function
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeRef {
+    /// the index of the block in the function
+    pub block: usize,
+    /// the index of the node in the block
+    pub node: usize,
+}
+
+/// The def-use chains of a function: which node defines a variable and which nodes use it
+///
+/// The chains refer to the nodes by their position, so they need to be rebuilt
+/// if nodes are inserted or removed
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DefUse {
+    defs: HashMap<String, NodeRef>,
+    uses: HashMap<String, Vec<NodeRef>>,
+}
+
+impl DefUse {
+    /// Builds the def-use chains of the function
+    pub fn new(func: &Function) -> Self {
+        let mut chains = Self::default();
+
+        for (block_index, block) in func.blocks.iter().enumerate() {
+            for (node_index, node) in block.nodes.iter().enumerate() {
+                let at = NodeRef { block: block_index, node: node_index };
+
+                for def in node.results() {
+                    chains.defs.insert(def.name.to_owned(), at);
+                }
+
+                for operand in node.operands() {
+                    let Some(var) = operand.var() else { continue };
+
+                    let users = chains.uses.entry(var.name.to_owned()).or_default();
+
+                    // a node which uses the variable more than once is only one user
+                    if users.last() != Some(&at) {
+                        users.push(at);
+                    }
+                }
+            }
+        }
+
+        chains
+    }
+
+    /// Returns the nodes which use the variable (in the order they appear in the function)
+    pub fn users_of(&self, var: &Var) -> &[NodeRef] {
+        self.uses.get(&var.name).map(|users| users.as_slice()).unwrap_or(&[])
+    }
+
+    /// Returns the node which defines the variable
+    /// (`None` for the arguments of the function and for undefined variables)
+    pub fn definition_of(&self, var: &Var) -> Option<NodeRef> {
+        self.defs.get(&var.name).copied()
+    }
+
+    /// Replaces every use of the old variable in the function with the new variable
+    /// (the definition of the old variable is kept)
+    pub fn replace_all_uses_with(&mut self, func: &mut Function, old: &Var, new: &Var) {
+        assert_eq!(old.ty, new.ty, "the uses of a variable can only be replaced with a variable of the same type");
+
+        let Some(users) = self.uses.remove(&old.name) else { return };
+
+        for at in &users {
+            let node = func.blocks.get_mut(at.block)
+                .and_then(|block| block.nodes.get_mut(at.node))
+                .expect("the def-use chains are out of date");
+
+            for operand in node.operands_mut() {
+                if let OperandMut::Var(var) = operand {
+                    if var.name == old.name {
+                        *var = new.to_owned();
+                    }
+                }
+            }
+        }
+
+        let new_users = self.uses.entry(new.name.to_owned()).or_default();
+        new_users.extend(users);
+        new_users.sort();
+        new_users.dedup();
+    }
+}
+
+impl Function {
+    /// Builds the def-use chains of the function
+    pub fn def_use(&self) -> DefUse {
+        DefUse::new(self)
+    }
+}
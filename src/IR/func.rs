@@ -1,13 +1,19 @@
 use std::collections::VecDeque;
 
+use super::ir::{Alloca, Call, CallIndirect, Phi, Return, TailCall};
+use super::constant::{fmt_linkage, fmt_placement};
+use super::AggregateType;
 use super::Block;
+use super::Type;
 use super::TypeMetadata;
 use super::Var;
 use super::VerifyError;
 use crate::prelude::PassManager;
-use crate::Obj::Linkage;
+use crate::CodeGen::compilation::intrinsic;
+use crate::Obj::{Linkage, Visibility};
 use crate::Support::ColorClass;
 use crate::Support::ColorProfile;
+use crate::Target::CallConv;
 
 /// Stores the function type
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -18,6 +24,8 @@ pub struct FunctionType {
     pub ret: TypeMetadata,
     /// After the given arguments any argument type can be supplied (like the printf function - is in c ...)
     pub any_args: bool,
+    /// The calling convention of the function (if it is `None` the one of the target is used)
+    pub callconv: Option<CallConv>,
 }
 
 impl FunctionType {
@@ -27,6 +35,21 @@ impl FunctionType {
             args: args,
             ret: ret,
             any_args: false,
+            callconv: None,
+        }
+    }
+
+    /// Sets the calling convention which is used for the function
+    /// instead of the one of the target
+    pub fn set_callconv(&mut self, conv: CallConv) {
+        self.callconv = Some(conv);
+    }
+
+    /// Returns the calling convention formatted like it is written in front of the return type
+    pub(crate) fn dump_callconv(&self) -> String {
+        match self.callconv {
+            Some(conv) => format!("{} ", conv),
+            None => String::new(),
         }
     }
 
@@ -52,6 +75,67 @@ impl FunctionType {
     }
 }
 
+impl std::fmt::Display for FunctionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut args = self.args.iter().map(|arg| arg.to_string()).collect::<Vec<String>>();
+
+        if self.any_args {
+            args.push("...".to_owned());
+        }
+
+        write!(f, "{}{} ({})", self.dump_callconv(), self.ret, args.join(", "))
+    }
+}
+
+/// An attribute of a function or a call site
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FunctionAttr {
+    /// the function should be inlined
+    Inline,
+    /// the function needs to be inlined
+    AlwaysInline,
+    /// the function must not be inlined
+    NoInline,
+    /// the function never returns to its caller (like `exit`)
+    NoReturn,
+    /// the function is rarely executed (it is placed in a seperate section)
+    Cold,
+    /// the function has no side effects and its result only depends on the arguments
+    Pure,
+    /// the function has no prolog and epilog (so it can't use a stack frame)
+    Naked,
+}
+
+impl FunctionAttr {
+    /// Parses the attribute from its name (without the `#`)
+    pub fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "inline" => FunctionAttr::Inline,
+            "alwaysinline" => FunctionAttr::AlwaysInline,
+            "noinline" => FunctionAttr::NoInline,
+            "noreturn" => FunctionAttr::NoReturn,
+            "cold" => FunctionAttr::Cold,
+            "pure" => FunctionAttr::Pure,
+            "naked" => FunctionAttr::Naked,
+            _ => return None,
+        })
+    }
+}
+
+impl std::fmt::Display for FunctionAttr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{}", match self {
+            FunctionAttr::Inline => "inline",
+            FunctionAttr::AlwaysInline => "alwaysinline",
+            FunctionAttr::NoInline => "noinline",
+            FunctionAttr::NoReturn => "noreturn",
+            FunctionAttr::Cold => "cold",
+            FunctionAttr::Pure => "pure",
+            FunctionAttr::Naked => "naked",
+        })
+    }
+}
+
 /// A ir function with a known variable and arg size and count
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Function {
@@ -61,7 +145,15 @@ pub struct Function {
     pub(crate) name: String,
     
     pub(crate) linkage: Linkage,
+    pub(crate) visibility: Visibility,
     pub(crate) blocks: VecDeque<Block>,
+
+    pub(crate) attrs: Vec<FunctionAttr>,
+
+    /// the section in which the function is placed (if it isn't placed in `.text`)
+    pub(crate) section: Option<String>,
+    /// the alignment of the function
+    pub(crate) align: Option<u64>,
 }
 
 impl Function {
@@ -75,6 +167,12 @@ impl Function {
             name: name,
 
             linkage: Linkage::Internal,
+            visibility: Visibility::Default,
+
+            attrs: vec![],
+
+            section: None,
+            align: None,
         }
     }
 
@@ -93,6 +191,63 @@ impl Function {
         self.linkage = Linkage::Internal;
     }
 
+    /// Sets that the function is externally visible, but can be overridden by a non weak definition of another object file
+    pub fn weak(&mut self) {
+        self.linkage = Linkage::Weak;
+    }
+
+    /// Sets that the function is externally visible and that the linker only keeps one of its definitions (used for e.g: generic instances)
+    pub fn linkonce(&mut self) {
+        self.linkage = Linkage::LinkOnce;
+    }
+
+    /// Sets the visibility of the function (e.g: `hidden` ones aren't exported from shared libraries)
+    pub fn set_visibility(&mut self, visibility: Visibility) {
+        self.visibility = visibility;
+    }
+
+    /// Places the function in the section with the given name (e.g: `.text.startup`)
+    pub fn set_section(&mut self, section: &str) {
+        self.section = Some(section.to_owned());
+    }
+
+    /// Sets the alignment of the function (needs to be a power of two)
+    pub fn set_align(&mut self, align: u64) {
+        assert!(align.is_power_of_two(), "the alignment needs to be a power of two");
+        self.align = Some(align);
+    }
+
+    /// Adds the attribute to the function (if it doesn't have it already)
+    pub fn add_attr(&mut self, attr: FunctionAttr) {
+        if !self.has_attr(attr) {
+            self.attrs.push(attr);
+        }
+    }
+
+    /// Returns if the function has the attribute
+    pub fn has_attr(&self, attr: FunctionAttr) -> bool {
+        self.attrs.contains(&attr)
+    }
+
+    /// Returns the attributes of the function
+    pub fn attrs(&self) -> &[FunctionAttr] {
+        &self.attrs
+    }
+
+    /// Returns the attributes formatted like they are written after the function signature
+    fn dump_attrs(&self) -> String {
+        self.attrs.iter().map(|attr| format!(" {}", attr)).collect()
+    }
+
+    /// Returns the visibility like it is written before the function signature
+    fn dump_visibility(&self) -> String {
+        if self.visibility != Visibility::Default {
+            format!("{} ", self.visibility)
+        } else {
+            String::new()
+        }
+    }
+
     /// Adds a new block to the function
     pub fn addBlock(&mut self, name: &str) -> &mut Block {
         self.blocks.push_back(Block::new(name, &self));
@@ -102,7 +257,8 @@ impl Function {
     /// Emits the Ir of the function into a string
     pub fn dump(&self) -> String {
         if self.linkage == Linkage::Extern {
-            let string = format!("declare {} @{}({})\n",
+            let string = format!("declare {}{} @{}({}){}\n",
+                self.ty.dump_callconv(),
                 self.ty.ret,
                 self.name, {
                     let mut fmt = String::new();
@@ -118,13 +274,13 @@ impl Function {
                     }
         
                     fmt
-                });
+                }, self.dump_attrs());
             return string;
         }
 
         let mut string = String::new();
 
-        string += &format!("define {} {} @{}({}) {{\n", self.linkage, self.ty.ret, self.name, {
+        string += &format!("define {} {}{}{} @{}({}){} {{\n", self.linkage, self.dump_visibility(), self.ty.dump_callconv(), self.ty.ret, self.name, {
             let mut fmt = String::new();
 
             for index in 0..self.ty.args.len() {
@@ -138,7 +294,7 @@ impl Function {
             }
 
             fmt
-        });
+        }, self.dump_attrs() + &fmt_placement(&self.section, self.align));
 
         for block in &self.blocks {
             string += &format!("{}\n", block.dump());
@@ -152,9 +308,10 @@ impl Function {
     /// Emits the Ir of the function into an colored string
     pub fn dumpColored(&self, profile: ColorProfile) -> String {
         if self.linkage == Linkage::Extern {
-            let string = format!("{} {} {} @{}( {})\n",
+            let string = format!("{} {} {}{} @{}( {}){}\n",
                 profile.markup("declare", ColorClass::Instr),
                 profile.markup(&format!("{}", self.linkage), ColorClass::Ty),
+                profile.markup(&self.ty.dump_callconv(), ColorClass::Ty),
                 profile.markup(&self.ty.ret.to_string(), ColorClass::Ty),
                 profile.markup(&self.name, ColorClass::Name), {
                     let mut fmt = String::new();
@@ -172,14 +329,16 @@ impl Function {
                     }
         
                     fmt
-                });
+                }, profile.markup(&self.dump_attrs(), ColorClass::Ty));
             return string;
         }
 
         let mut string = String::new();
 
-        string += &format!("{} {} @{}({}) {{\n", 
+        string += &format!("{} {}{}{} @{}({}){} {{\n", 
                         profile.markup("define", ColorClass::Instr),
+                        profile.markup(&fmt_linkage(self.linkage, self.visibility), ColorClass::Ty),
+                        profile.markup(&self.ty.dump_callconv(), ColorClass::Ty),
                         profile.markup(&self.ty.ret.to_string(), ColorClass::Ty), 
                         profile.markup(&self.name, ColorClass::Name), {
             let mut fmt = String::new();
@@ -197,7 +356,7 @@ impl Function {
             }
 
             fmt
-        });
+        }, profile.markup(&(self.dump_attrs() + &fmt_placement(&self.section, self.align)), ColorClass::Ty));
 
         for block in &self.blocks {
             string += &format!(" {}\n", block.dumpColored(profile));
@@ -211,19 +370,39 @@ impl Function {
     /// Verifys if the function and all of its blocks are correct:
     ///  * Checks if the return type is the actual specified return type of the function
     ///  * Checks all ir nodes
+    ///  * Checks that the attributes don't contradict each other and are respected by the nodes
     pub fn verify(&self) -> Result<(), VerifyError> {
         for block in &self.blocks {
             block.verify(self)?
         }
 
+        for inline in [FunctionAttr::Inline, FunctionAttr::AlwaysInline] {
+            if self.has_attr(inline) && self.has_attr(FunctionAttr::NoInline) {
+                Err(VerifyError::ConflictingAttributes(inline, FunctionAttr::NoInline))?
+            }
+        }
+
+        let nodes = || self.blocks.iter().flat_map(|block| block.nodes.iter()).map(|node| node.as_any());
+
+        if self.has_attr(FunctionAttr::NoReturn) && nodes().any(|node| node.is::<Return<Type>>() || node.is::<Return<Var>>()) {
+            Err(VerifyError::ReturnInNoReturnFunc)?
+        }
+
+        // the register save area of variadic functions lives in the stack frame
+        if self.has_attr(FunctionAttr::Naked) && (self.ty.any_args || nodes().any(|node| {
+            node.is::<Alloca<Var, TypeMetadata>>() || node.is::<Alloca<Var, AggregateType>>() || node.is::<Phi>()
+                || node.is::<Call<Function, Vec<Var>, Var>>() || node.is::<CallIndirect>() || node.is::<TailCall<Function, Vec<Var>>>()
+                || intrinsic::calls_runtime(node)
+        })) {
+            Err(VerifyError::StackInNakedFunc)?
+        }
+
         Ok(())
     }
     
-    /// Runs the pass manager over all blocks
+    /// Runs the pass manager over the function
     pub fn runPassMngr(&mut self, mngr: &PassManager) {
-        for block in &mut self.blocks {
-            mngr.run(block);
-        }
+        mngr.run_func(self);
     }
 }
 
@@ -0,0 +1,132 @@
+use crate::Obj::{Linkage, TlsModel, Visibility};
+
+use super::constant::{fmt_data, SymbolRef};
+
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A mutable global variable
+pub struct Global {
+    pub(crate) name: String,
+    /// the initial data (`None` if the global is zero initialized and placed in .bss)
+    pub(crate) data: Option<Vec<u8>>,
+    pub(crate) size: usize,
+    pub(crate) linkage: Linkage,
+    pub(crate) visibility: Visibility,
+    /// the model which is used to access it if it is thread local
+    pub(crate) thread_local: Option<TlsModel>,
+    /// the section in which it is placed (if it isn't placed in the default one)
+    pub(crate) section: Option<String>,
+    /// the alignment of it
+    pub(crate) align: Option<u64>,
+    /// the addresses of symbols which are placed in the initial data
+    pub(crate) refs: Vec<SymbolRef>,
+}
+
+impl Global {
+    /// creates a new global
+    pub fn new(name: String) -> Self {
+        Self {
+            name: name,
+            data: None,
+            size: 0,
+            linkage: Linkage::Internal,
+            visibility: Visibility::Default,
+            thread_local: None,
+            section: None,
+            align: None,
+            refs: vec![],
+        }
+    }
+
+    /// sets the initial data of the global
+    pub fn set(&mut self, data: Vec<u8>) {
+        self.size = data.len();
+        self.data = Some(data);
+    }
+
+    /// makes the global zero initialized with the given size in bytes
+    pub fn zeroed(&mut self, size: usize) {
+        self.size = size;
+        self.data = None;
+    }
+
+    /// gets the initial data of the global
+    pub fn get(&self) -> Vec<u8> {
+        self.data.clone().unwrap_or_else(|| vec![0; self.size])
+    }
+
+    /// appends the address of the symbol (plus the addend) to the initial data
+    /// (the address is filled in by the linker)
+    pub fn add_ref(&mut self, symbol: &str, addend: i64) {
+        let mut data = self.get();
+
+        self.refs.push(SymbolRef { at: data.len(), symbol: symbol.to_owned(), addend: addend });
+        data.extend_from_slice(&[0; 8]);
+
+        self.set(data);
+    }
+
+    /// Returns if the global is zero initialized
+    pub fn is_zeroed(&self) -> bool {
+        self.data.is_none()
+    }
+
+    /// Returns the initializer like it is written in the ir
+    pub(crate) fn fmt_init(&self, is_func: &dyn Fn(&str) -> bool) -> String {
+        match &self.data {
+            Some(data) => fmt_data(data, &self.refs, is_func),
+            None => format!("zeroinit {}", self.size),
+        }
+    }
+
+    /// Sets that the global is externally visible
+    pub fn extrn(&mut self) {
+        self.linkage = Linkage::External;
+    }
+
+    /// Sets that the global is imported from another object file
+    pub fn import(&mut self) {
+        self.linkage = Linkage::Extern;
+    }
+
+    /// Sets that the global is only internally visible
+    pub fn private(&mut self) {
+        self.linkage = Linkage::Internal;
+    }
+
+    /// Sets that the global is externally visible, but can be overridden by a non weak definition of another object file
+    pub fn weak(&mut self) {
+        self.linkage = Linkage::Weak;
+    }
+
+    /// Sets that the global is externally visible and that the linker only keeps one of its definitions (used for e.g: generic instances)
+    pub fn linkonce(&mut self) {
+        self.linkage = Linkage::LinkOnce;
+    }
+
+    /// Sets the visibility of the global (e.g: `hidden` ones aren't exported from shared libraries)
+    pub fn set_visibility(&mut self, visibility: Visibility) {
+        self.visibility = visibility;
+    }
+
+    /// Sets that the global is thread local (every thread has its own copy of it) and how its adress is computed
+    pub fn set_thread_local(&mut self, model: TlsModel) {
+        self.thread_local = Some(model);
+    }
+
+    /// Returns if the global is thread local
+    pub fn is_thread_local(&self) -> bool {
+        self.thread_local.is_some()
+    }
+
+    /// Places the global in the section with the given name (e.g: `.init_array`)
+    pub fn set_section(&mut self, section: &str) {
+        self.section = Some(section.to_owned());
+    }
+
+    /// Sets the alignment of the global (needs to be a power of two)
+    pub fn set_align(&mut self, align: u64) {
+        assert!(align.is_power_of_two(), "the alignment needs to be a power of two");
+        self.align = Some(align);
+    }
+}
@@ -1,10 +1,14 @@
 mod module;
 mod func;
 mod typ;
+mod aggregate;
 mod builder;
 mod block;
 mod var;
 mod constant;
+mod global;
+mod defuse;
+mod cfg;
 mod nodes;
 pub mod parser;
 
@@ -16,17 +20,23 @@ pub mod ir {
 use std::error::Error;
 use std::fmt::Display;
 
+use ir::AtomicOrdering;
+
 pub use module::Module;
-pub use func::{Function, FunctionType, FnTy, Func};
-pub use constant::Const;
+pub use func::{Function, FunctionAttr, FunctionType, FnTy, Func};
+pub use constant::{Const, SymbolRef};
+pub use global::Global;
 pub use typ::Type;
 pub use typ::TypeMetadata;
+pub use aggregate::{AggregateType, ElementType};
 pub use builder::IRBuilder;
 pub use block::Block;
 pub use var::Var;
+pub use defuse::{DefUse, NodeRef};
+pub use cfg::{Cfg, DomTree, DominanceFrontiers};
 
 /// An error which stores if an ir node is invalid
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum VerifyError {
     /// The type of the ret node doesn't match the function return type
     RetTyNotFnTy(TypeMetadata, TypeMetadata),
@@ -36,6 +46,46 @@ pub enum VerifyError {
     InvalidArgumentTypeFound,
     /// To many arguments were supplyed
     ToManyArgumentsWereSupplyed,
+    /// A phi node comes after a non phi node in its block
+    PhiIsntAtBlockStart,
+    /// A phi node recives a value from a block which doesn't exist in the function
+    PhiFromUnknownBlock,
+    /// A switch node has multiple cases with the same value
+    SwitchCaseTwice(Type),
+    /// The field index of a gep node into a struct isn't a constant
+    FieldIndexNotConstant,
+    /// The constant index of a gep node is bigger than the number of fields/elements
+    IndexOutOfBounds(u64, usize),
+    /// The memory ordering can't be used for the atomic node (e.g: a release load)
+    InvalidAtomicOrdering(AtomicOrdering),
+    /// The atomic node can't operate on values of the type
+    InvalidAtomicType(TypeMetadata),
+    /// The overflow checked arithmetic node can't operate on values of the type
+    InvalidOverflowType(TypeMetadata),
+    /// The bitwise operation, shift or remainder can't operate on values of the type
+    IntOnlyOperation(TypeMetadata),
+    /// The output of a compare node isn't a bool
+    CmpOutNotBool(TypeMetadata),
+    /// The cast between the two types isn't supported
+    InvalidCast(TypeMetadata, TypeMetadata),
+    /// The function has two attributes which contradict each other
+    ConflictingAttributes(FunctionAttr, FunctionAttr),
+    /// A function with the noreturn attribute contains a return node
+    ReturnInNoReturnFunc,
+    /// A naked function is variadic or uses a node which needs a stack frame
+    StackInNakedFunc,
+    /// The variadic arguments are accessed in a function which isn't variadic
+    VaStartInNonVariadicFunc,
+    /// The type can't be read from the variadic arguments
+    InvalidVaArgType(TypeMetadata),
+    /// Two inputs of an inline assembly node are bound to the same register
+    AsmRegBoundTwice,
+    /// The constraints of an inline assembly node use an unknown register
+    UnknownAsmReg(String),
+    /// The constraints of an inline assembly node use a register which needs to be preserved for the caller
+    AsmRegCalleeSaved(String),
+    /// A tail call needs to pass arguments over the stack
+    TailCallStackArgs,
 }
 
 impl Display for VerifyError {
@@ -63,7 +113,67 @@ impl Display for VerifyError {
                 format!(
                     "too many arguments were supplyed"
                 )
-            }
+            },
+            VerifyError::PhiIsntAtBlockStart => {
+                "phi nodes need to be at the start of their block".to_owned()
+            },
+            VerifyError::PhiFromUnknownBlock => {
+                "a phi node recives a value from a block which doesn't exist".to_owned()
+            },
+            VerifyError::SwitchCaseTwice(value) => {
+                format!("the switch has multiple cases for the value {}", value.fmt_val())
+            },
+            VerifyError::FieldIndexNotConstant => {
+                "the field index into a struct needs to be a constant".to_owned()
+            },
+            VerifyError::IndexOutOfBounds(index, len) => {
+                format!("the index {} is out of bounds for a type with {} fields/elements", index, len)
+            },
+            VerifyError::InvalidAtomicOrdering(order) => {
+                format!("the memory ordering {} isn't allowed for this atomic node", order)
+            },
+            VerifyError::InvalidAtomicType(ty) => {
+                format!("atomic nodes can't operate on values of the type {}", ty)
+            },
+            VerifyError::InvalidOverflowType(ty) => {
+                format!("overflow checked arithmetic needs an integer type, but got {}", ty)
+            },
+            VerifyError::IntOnlyOperation(ty) => {
+                format!("bitwise operations, shifts and remainders need an integer type, but got {}", ty)
+            },
+            VerifyError::CmpOutNotBool(ty) => {
+                format!("the result of a compare node needs to be a bool, but got {}", ty)
+            },
+            VerifyError::InvalidCast(from, to) => {
+                format!("a value of the type {} can't be cast to {}", from, to)
+            },
+            VerifyError::ConflictingAttributes(attr, other) => {
+                format!("the function attributes {} and {} can't be used together", attr, other)
+            },
+            VerifyError::ReturnInNoReturnFunc => {
+                "a function with the #noreturn attribute can't return".to_owned()
+            },
+            VerifyError::StackInNakedFunc => {
+                "naked functions have no stack frame, so they can't be variadic or use allocas, phis, calls or runtime memory intrinsics".to_owned()
+            },
+            VerifyError::VaStartInNonVariadicFunc => {
+                "va_start can only be used in functions with variadic arguments".to_owned()
+            },
+            VerifyError::InvalidVaArgType(ty) => {
+                format!("va_arg needs a scalar type which fits into a register, but got {}", ty)
+            },
+            VerifyError::AsmRegBoundTwice => {
+                "a register is bound to more than one input of the inline assembly".to_owned()
+            },
+            VerifyError::UnknownAsmReg(reg) => {
+                format!("unknown register {} in the constraints of the inline assembly", reg)
+            },
+            VerifyError::AsmRegCalleeSaved(reg) => {
+                format!("the inline assembly can't bind or clobber the callee saved register {}", reg)
+            },
+            VerifyError::TailCallStackArgs => {
+                "tail calls can only pass their arguments in registers".to_owned()
+            },
         })
     }
 }
@@ -1,7 +1,7 @@
-use crate::{prelude::Triple, CodeGen::MachineInstr, Obj::{Decl, Link, Linkage, ObjectBuilder}, Optimizations::PassManager, Support::{ColorClass, ColorProfile}, Target::TargetRegistry};
+use crate::{prelude::Triple, CodeGen::MachineInstr, Obj::{Decl, Link, Linkage, ObjectBuilder, Visibility}, Optimizations::PassManager, Support::{ColorClass, ColorProfile}, Target::TargetRegistry};
 
-use super::{func::FunctionType, Const, Function, VerifyError};
-use std::{collections::{BTreeMap, HashMap}, error::Error, fs::OpenOptions, io::Write, path::Path};
+use super::{constant::{fmt_data, fmt_linkage, fmt_placement, fmt_thread_local}, func::FunctionType, AggregateType, Const, Function, FunctionAttr, Global, VerifyError};
+use std::{collections::{HashMap, HashSet}, error::Error, fs::OpenOptions, io::Write, path::Path};
 
 /// ## The Module
 /// The main class for handeling functions
@@ -9,6 +9,9 @@ use std::{collections::{BTreeMap, HashMap}, error::Error, fs::OpenOptions, io::W
 pub struct Module {
     pub(crate) funcs: HashMap<String, Function>,
     pub(crate) consts: HashMap<String, Const>,
+    pub(crate) globals: HashMap<String, Global>,
+    /// the struct and array types (in the order of their definition, so types can refer to earlier ones)
+    pub(crate) types: Vec<(String, AggregateType)>,
 }
 
 impl Module {
@@ -17,6 +20,8 @@ impl Module {
         Self {
             funcs: HashMap::new(),
             consts: HashMap::new(),
+            globals: HashMap::new(),
+            types: Vec::new(),
         }
     }
 
@@ -44,6 +49,28 @@ impl Module {
         self.consts.insert(constant.name.to_string(), constant);
     }
 
+    /// Adds a new mutable global variable to the module
+    pub fn addGlobal(&mut self, name: &str) -> &mut Global {
+        self.globals
+            .insert(name.to_string(), Global::new(name.to_string()));
+        self.globals.get_mut(name).unwrap()
+    }
+
+    /// Adds an already defined global to the module
+    pub fn add_raw_global(&mut self, global: Global) {
+        self.globals.insert(global.name.to_string(), global);
+    }
+
+    /// Adds a named struct or array type to the module
+    pub fn add_type(&mut self, name: &str, ty: AggregateType) {
+        self.types.push((name.to_string(), ty));
+    }
+
+    /// Returns the struct or array type with the given name
+    pub fn getType(&self, name: &str) -> Option<&AggregateType> {
+        self.types.iter().find(|(ty_name, _)| ty_name == name).map(|(_, ty)| ty)
+    }
+
     #[allow(dead_code)]
     /// Returns a read only reference to the given function name
     /// ### Used for passes
@@ -63,21 +90,18 @@ impl Module {
     pub fn dump(&self) -> String {
         let mut string = String::new();
 
-        for (_, consta) in &self.consts {
-            let mut bytes = String::from("[ ");
-
-            for byte in &consta.data { 
-                bytes.push_str(&format!("{}, ", *byte));
-            }
+        for (name, ty) in &self.types {
+            string += &format!("type {} = {}\n", name, ty.body());
+        }
 
-            if consta.data.len() > 0 {
-                bytes.remove(bytes.chars().count() - 1);
-                bytes.remove(bytes.chars().count() - 2);
-            }
+        let is_func = |name: &str| self.funcs.contains_key(name);
 
-            bytes.push(']');
+        for (_, consta) in &self.consts {
+            string += &format!("const {}{}{} = {}{}\n", fmt_linkage(consta.linkage, consta.visibility), fmt_thread_local(consta.thread_local), consta.name, fmt_data(&consta.data, &consta.refs, &is_func), fmt_placement(&consta.section, consta.align));
+        }
 
-            string += &format!("const {} = {}\n", consta.name,  bytes);
+        for global in self.globals.values() {
+            string += &format!("global {}{}{} = {}{}\n", fmt_linkage(global.linkage, global.visibility), fmt_thread_local(global.thread_local), global.name, global.fmt_init(&is_func), fmt_placement(&global.section, global.align));
         }
 
         for (_, func) in &self.funcs {
@@ -92,24 +116,35 @@ impl Module {
     pub fn dumpColored(&self, profile: ColorProfile) -> String {
         let mut string = String::new();
 
-        for (_, consta) in &self.consts {
-            let mut bytes = String::from("[ ");
-
-            for byte in &consta.data { 
-                bytes.push_str(&format!("{}, ", *byte));
-            }
-
-            if consta.data.len() > 0 {
-                bytes.remove(bytes.chars().count() - 1);
-                bytes.remove(bytes.chars().count() - 2);
-            }
+        for (name, ty) in &self.types {
+            string += &format!("{} {} = {}\n", 
+                profile.markup("type", ColorClass::Instr), 
+                profile.markup(name, ColorClass::Name), 
+                profile.markup(&ty.body(), ColorClass::Ty)
+            );
+        }
 
-            bytes.push(']');
+        let is_func = |name: &str| self.funcs.contains_key(name);
 
-            string += &format!("{} {} = {}\n", 
+        for (_, consta) in &self.consts {
+            string += &format!("{} {}{}{} = {}{}\n", 
                 profile.markup("const", ColorClass::Instr), 
+                profile.markup(&fmt_linkage(consta.linkage, consta.visibility), ColorClass::Ty), 
+                profile.markup(&fmt_thread_local(consta.thread_local), ColorClass::Ty), 
                 profile.markup(&consta.name, ColorClass::Name), 
-                profile.markup(&bytes, ColorClass::Value)
+                profile.markup(&fmt_data(&consta.data, &consta.refs, &is_func), ColorClass::Value),
+                profile.markup(&fmt_placement(&consta.section, consta.align), ColorClass::Ty)
+            );
+        }
+
+        for global in self.globals.values() {
+            string += &format!("{} {}{}{} = {}{}\n", 
+                profile.markup("global", ColorClass::Instr), 
+                profile.markup(&fmt_linkage(global.linkage, global.visibility), ColorClass::Ty), 
+                profile.markup(&fmt_thread_local(global.thread_local), ColorClass::Ty), 
+                profile.markup(&global.name, ColorClass::Name), 
+                profile.markup(&global.fmt_init(&is_func), ColorClass::Value),
+                profile.markup(&fmt_placement(&global.section, global.align), ColorClass::Ty)
             );
         }
 
@@ -144,18 +179,78 @@ impl Module {
 
         for (_, consta) in &self.consts {
             obj.decl((consta.name.as_str(), Decl::Constant, consta.linkage));
+            obj.visibility(&consta.name, consta.visibility);
+
+            if let Some(model) = consta.thread_local {
+                obj.thread_local(&consta.name, model);
+            }
+
+            if let Some(section) = &consta.section {
+                obj.section(&consta.name, section);
+            }
+
+            if let Some(align) = consta.align {
+                obj.align(&consta.name, align);
+            }
+
             obj.define(&consta.name, consta.data.clone());
         }
 
+        for global in self.globals.values() {
+            obj.decl((global.name.as_str(), if global.is_zeroed() { Decl::ZeroedData } else { Decl::Data }, global.linkage));
+            obj.visibility(&global.name, global.visibility);
+
+            if let Some(model) = global.thread_local {
+                obj.thread_local(&global.name, model);
+            }
+
+            if let Some(section) = &global.section {
+                obj.section(&global.name, section);
+            }
+
+            if let Some(align) = global.align {
+                obj.align(&global.name, align);
+            }
+
+            obj.define(&global.name, global.get());
+        }
+
+        // the addresses of symbols inside of constants and globals
+        for (name, refs) in self.consts.values().map(|consta| (&consta.name, &consta.refs))
+            .chain(self.globals.values().map(|global| (&global.name, &global.refs))) {
+            for sym in refs {
+                obj.link_adr(Link {
+                    from: name.to_owned(),
+                    to: sym.symbol.to_owned(),
+                    at: sym.at,
+                    addend: sym.addend,
+                    special: false,
+                });
+            }
+        }
+
+        // runtime functions which the backend calls (e.g: memcpy) without them being declared in the module
+        let mut runtime_funcs = HashSet::new();
+
         for (name, func) in &self.funcs {
-            obj.decl( (&name, Decl::Function, func.linkage));
+            obj.decl( (&name, if func.has_attr(FunctionAttr::Cold) { Decl::ColdFunction } else { Decl::Function }, func.linkage));
+            obj.visibility(name, func.visibility);
+
+            if let Some(section) = &func.section {
+                obj.section(name, section);
+            }
 
-            let mut blocks = BTreeMap::new();
+            if let Some(align) = func.align {
+                obj.align(name, align);
+            }
+
+            // the blocks keep their order, so the entry block (which holds the prolog) stays first
+            let mut blocks = vec![];
 
             for block in &func.blocks {
                 let (compiled, links) = registry.buildMachineCodeForTarget(triple.arch, block, &func)?;
 
-                blocks.insert(block.name.to_owned(), (compiled, links));
+                blocks.push((block.name.to_owned(), (compiled, links)));
             }
 
             let mut comp = vec![];
@@ -164,21 +259,35 @@ impl Module {
 
             let mut block_adrs = HashMap::new();
 
+            let mut jump_tables: Vec<(String, Vec<(i64, String)>)> = vec![];
+
             for (name, (data, links)) in blocks {
                 let prev_len = comp.len();
 
-                block_adrs.insert(name, prev_len);
+                block_adrs.insert(name.to_owned(), prev_len);
 
                 comp.extend_from_slice(&data);
 
                 for link in links {
-                    if link.special { // block to block link
+                    if link.special && link.from != name { // jump table entry (the source is the table)
+                        if let Some((_, entries)) = jump_tables.iter_mut().find(|(table, _)| *table == link.from) {
+                            entries.push((link.addend, link.to));
+                        } else {
+                            jump_tables.push((link.from, vec![(link.addend, link.to)]));
+                        }
+                    } else if link.special { // block to block link
                         let adr = |idx| {
                             link.at as i64 + (prev_len as i64) + link.addend + idx
                         };
 
                         block_links.push(((adr(0), adr(1), adr(2), adr(3)), link.to, link.from, link.at as i64));
                     } else {
+                        let known = self.funcs.contains_key(&link.to) || self.consts.contains_key(&link.to) || self.globals.contains_key(&link.to);
+
+                        if !known && runtime_funcs.insert(link.to.to_owned()) {
+                            obj.decl((&link.to, Decl::Function, Linkage::Extern));
+                        }
+
                         obj.link(Link { 
                             from: link.from, 
                             to: link.to, 
@@ -192,9 +301,10 @@ impl Module {
 
             for (idx, target, source, off) in block_links {
                 let mut target_adr = *block_adrs.get(&target).expect("hmm i made a programming error") as i64;
-                let source_adr = *block_adrs.get(&source).expect("hmm i made a programming error") as i64 + 5;
+                let source_adr = *block_adrs.get(&source).expect("hmm i made a programming error") as i64;
 
-                target_adr = target_adr - source_adr - (off - source_adr);
+                // the displacement is relative to the end of the jump (`off` is relative to the start of the source block)
+                target_adr -= source_adr + off;
 
                 let bytes = target_adr.to_be_bytes();
 
@@ -209,6 +319,24 @@ impl Module {
             }
 
             obj.define(&name, comp);
+
+            // the entries of jump tables are the offsets of their block relative to the table
+            for (table, entries) in jump_tables {
+                obj.decl((&table, Decl::Constant, Linkage::Internal));
+                obj.define(&table, vec![0; entries.len() * 4]);
+
+                for (offset, block) in entries {
+                    let block_adr = *block_adrs.get(&block).expect("hmm i made a programming error") as i64;
+
+                    obj.link(Link {
+                        from: table.to_owned(),
+                        to: name.to_owned(),
+                        at: offset as usize,
+                        addend: block_adr + offset,
+                        special: false,
+                    });
+                }
+            }
         }
 
         Ok(obj)
@@ -253,7 +381,15 @@ impl Module {
         lines.push_str(&format!("// by {}\n\n", env!("CARGO_PKG_AUTHORS").replace(";", " ")));
         lines.push_str("section .rodata\n\n");
 
-        for (_, consta) in &self.consts {
+        // the symbols which are placed in custom sections are emitted after the default sections
+        let custom_consts = self.consts.values().filter(|consta| consta.section.is_some());
+        let custom_globals = self.globals.values().filter(|global| global.section.is_some());
+
+        for consta in self.consts.values().filter(|consta| consta.section.is_none()) {
+            if let Some(align) = consta.align {
+                lines += &format!("align {}\n", align);
+            }
+
             lines.push_str(&format!("{}: {:?} # {}\n", consta.name, consta.data, consta.data.iter()                                      
                                                                                                 .filter_map(|&byte| {
                                                                                                     if byte >= 32 && byte <= 126 {
@@ -263,17 +399,86 @@ impl Module {
                                                                                                     }
                                                                                                 }).collect::<String>()));
         }
+        lines.push_str("section .data\n\n");
+
+        for global in self.globals.values().filter(|global| !global.is_zeroed() && !global.is_thread_local() && global.section.is_none()) {
+            if let Some(align) = global.align {
+                lines += &format!("align {}\n", align);
+            }
+
+            lines.push_str(&format!("{}: {:?}\n", global.name, global.get()));
+        }
+
+        lines.push_str("section .bss\n\n");
+
+        for global in self.globals.values().filter(|global| global.is_zeroed() && !global.is_thread_local() && global.section.is_none()) {
+            if let Some(align) = global.align {
+                lines += &format!("align {}\n", align);
+            }
+
+            lines.push_str(&format!("{}: resb {}\n", global.name, global.size));
+        }
+
+        if self.globals.values().any(|global| global.is_thread_local()) {
+            lines.push_str("section .tdata\n\n");
+
+            for global in self.globals.values().filter(|global| !global.is_zeroed() && global.is_thread_local() && global.section.is_none()) {
+                lines.push_str(&format!("{}: {:?}\n", global.name, global.get()));
+            }
+
+            lines.push_str("section .tbss\n\n");
+
+            for global in self.globals.values().filter(|global| global.is_zeroed() && global.is_thread_local() && global.section.is_none()) {
+                lines.push_str(&format!("{}: resb {}\n", global.name, global.size));
+            }
+        }
+
+        for (name, section, align, data) in custom_consts.map(|consta| (&consta.name, &consta.section, consta.align, consta.data.clone()))
+            .chain(custom_globals.map(|global| (&global.name, &global.section, global.align, global.get()))) {
+            lines += &format!("section {}\n\n", section.as_deref().unwrap_or_default());
+
+            if let Some(align) = align {
+                lines += &format!("align {}\n", align);
+            }
+
+            lines += &format!("{}: {:?}\n\n", name, data);
+        }
+
         lines.push_str("section .text\n\n");
 
+        let mut section = ".text";
+
         for (name, func) in &self.funcs {
             if func.linkage == Linkage::Extern {
                 lines += &format!("global {}\n", name);
                 continue;
             }
 
-            if func.linkage == Linkage::External {
-                lines += &format!("global {}\n", name);
-            } 
+            // cold functions are placed in their own section
+            let func_section = match &func.section {
+                Some(custom) => custom.as_str(),
+                None if func.has_attr(FunctionAttr::Cold) => ".text.unlikely",
+                None => ".text",
+            };
+
+            if func_section != section {
+                section = func_section;
+                lines += &format!("section {}\n\n", section);
+            }
+
+            match func.linkage {
+                Linkage::External => lines += &format!("global {}\n", name),
+                Linkage::Weak | Linkage::LinkOnce => lines += &format!("weak {}\n", name),
+                _ => {},
+            }
+
+            if func.visibility != Visibility::Default {
+                lines += &format!("{} {}\n", func.visibility, name);
+            }
+
+            if let Some(align) = func.align {
+                lines += &format!("align {}\n", align);
+            }
 
             lines += &format!("{}:\n", name);
 
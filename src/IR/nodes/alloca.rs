@@ -0,0 +1,138 @@
+use super::*;
+
+impl Ir for Alloca<Var, TypeMetadata> {
+    fn dump(&self) -> String {
+        format!("{} = alloca {}", self.inner1.name, self.inner2)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} = {} {}", 
+            profile.markup(&self.inner1.name, ColorClass::Var), 
+            profile.markup("alloca", ColorClass::Instr),
+            profile.markup(&self.inner2.to_string(), ColorClass::Ty),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        if self.inner1.ty != TypeMetadata::ptr {
+            Err(VerifyError::Op0Op1TyNoMatch(self.inner1.ty, TypeMetadata::ptr))?
+        }
+        Ok(())
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        *var == self.inner1
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::from( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr) {
+        registry.compile_alloca(self)
+    }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::Alloca
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![]
+    }
+
+    fn result(&self) -> Option<&Var> {
+        Some(&self.inner1)
+    }
+}
+
+/// Trait for the alloca instruction
+/// Used for overloading the BuildAlloca function
+pub trait BuildAlloca<T> {
+    /// reserves stack memory for the given type and returns a pointer to it
+    fn BuildAlloca(&mut self, ty: T) -> Var;
+}
+
+impl BuildAlloca<TypeMetadata> for IRBuilder<'_> {
+    fn BuildAlloca(&mut self, ty: TypeMetadata) -> Var {
+        let block = self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one");
+        
+        let out = Var::new(block, TypeMetadata::ptr);
+
+        block.push_ir(Alloca::new(out.clone(), ty));
+
+        out
+    }
+}
+
+impl Ir for Alloca<Var, AggregateType> {
+    fn dump(&self) -> String {
+        format!("{} = alloca {}", self.inner1.name, self.inner2)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} = {} {}", 
+            profile.markup(&self.inner1.name, ColorClass::Var), 
+            profile.markup("alloca", ColorClass::Instr),
+            profile.markup(&self.inner2.to_string(), ColorClass::Ty),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        if self.inner1.ty != TypeMetadata::ptr {
+            Err(VerifyError::Op0Op1TyNoMatch(self.inner1.ty, TypeMetadata::ptr))?
+        }
+        Ok(())
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        *var == self.inner1
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::from( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr) {
+        registry.compile_alloca_aggregate(self)
+    }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::Alloca
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![]
+    }
+
+    fn result(&self) -> Option<&Var> {
+        Some(&self.inner1)
+    }
+}
+
+impl BuildAlloca<AggregateType> for IRBuilder<'_> {
+    fn BuildAlloca(&mut self, ty: AggregateType) -> Var {
+        let block = self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one");
+        
+        let out = Var::new(block, TypeMetadata::ptr);
+
+        block.push_ir(Alloca::new(out.clone(), ty));
+
+        out
+    }
+}
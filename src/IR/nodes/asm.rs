@@ -0,0 +1,156 @@
+use super::*;
+use crate::CodeGen::reg::Reg;
+use crate::Target::Arch;
+
+impl InlineAsm {
+    /// Checks that the constraints only use known registers of the architecture
+    /// which the register allocator doesn't need to preserve
+    pub(crate) fn verify_regs(&self, arch: Arch) -> Result<(), VerifyError> {
+        let names = self.ins.iter().map(|(reg, _)| reg)
+            .chain(self.out.iter().map(|(reg, _)| reg))
+            .chain(self.clobbers.iter());
+
+        for name in names {
+            let Some(reg) = Reg::parse(arch, name) else {
+                Err(VerifyError::UnknownAsmReg(name.to_owned()))?
+            };
+
+            // the register allocator doesn't save them in the prolog
+            if reg.is_callee_saved() {
+                Err(VerifyError::AsmRegCalleeSaved(name.to_owned()))?
+            }
+        }
+
+        // e.g: eax and rax are the same register
+        let ins = self.ins.iter().filter_map(|(reg, _)| Reg::parse(arch, reg)).collect::<Vec<_>>();
+
+        for (index, reg) in ins.iter().enumerate() {
+            if ins[..index].iter().any(|other| other.same_as(reg)) {
+                Err(VerifyError::AsmRegBoundTwice)?
+            }
+        }
+
+        Ok(())
+    }
+
+    fn dump_constraints(&self, ins: String, out: String, clobbers: String) -> String {
+        let mut constraints = String::new();
+
+        if let Some((reg, _)) = &self.out {
+            constraints.push_str(&format!(" {}({})", out, reg));
+        }
+
+        if !self.ins.is_empty() {
+            constraints.push_str(&format!(" {}({})", ins, self.ins.iter()
+                .map(|(reg, var)| format!("{} {} {}", reg, var.ty, var.name))
+                .collect::<Vec<_>>()
+                .join(", ")
+            ));
+        }
+
+        if !self.clobbers.is_empty() {
+            constraints.push_str(&format!(" {}({})", clobbers, self.clobbers.join(", ")));
+        }
+
+        constraints
+    }
+}
+
+impl Ir for InlineAsm {
+    fn dump(&self) -> String {
+        let constraints = self.dump_constraints("in".into(), "out".into(), "clobber".into());
+
+        match &self.out {
+            Some((_, out)) => format!("{} = asm {} {:?}{}", out.name, out.ty, self.asm, constraints),
+            None => format!("asm {:?}{}", self.asm, constraints),
+        }
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        let constraints = self.dump_constraints(
+            profile.markup("in", ColorClass::Instr), 
+            profile.markup("out", ColorClass::Instr), 
+            profile.markup("clobber", ColorClass::Instr)
+        );
+
+        let asm = profile.markup(&format!("{:?}", self.asm), ColorClass::Value);
+
+        match &self.out {
+            Some((_, out)) => format!("{} = {} {} {}{}", 
+                profile.markup(&out.name, ColorClass::Var),
+                profile.markup("asm", ColorClass::Instr),
+                profile.markup(&out.ty.to_string(), ColorClass::Ty),
+                asm, constraints,
+            ),
+            None => format!("{} {}{}", profile.markup("asm", ColorClass::Instr), asm, constraints),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        // the registers themself are checked by the backend (see `verify_regs`)
+        // because their names depend on the target architecture
+        for (index, (reg, _)) in self.ins.iter().enumerate() {
+            if self.ins[..index].iter().any(|(other, _)| other == reg) {
+                Err(VerifyError::AsmRegBoundTwice)?
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new(self.clone())
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr) {
+        registry.compile_inline_asm(self)
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        self.ins.iter().any(|(_, input)| input == var) || self.out.as_ref().map(|(_, out)| out == var).unwrap_or(false)
+    }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::InlineAsm
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        self.ins.iter().map(|(_, var)| var).map(Operand::Var).collect()
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        self.ins.iter_mut().map(|(_, var)| var).map(OperandMut::Var).collect()
+    }
+
+    fn result(&self) -> Option<&Var> {
+        self.out.as_ref().map(|(_, var)| var)
+    }
+}
+
+/// Trait for the inline assembly instruction
+/// Used for overloading the BuildInlineAsm function
+pub trait BuildInlineAsm {
+    /// assembles the assembly string with the assembler of the target.
+    /// The inputs are bound to the named registers and the output (if it has a type) is read out of its register
+    /// (the assembly may override the registers of the inputs, the output and the clobbers)
+    fn BuildInlineAsm(&mut self, asm: &str, ins: &[(&str, Var)], out: Option<(&str, TypeMetadata)>, clobbers: &[&str]) -> Option<Var>;
+}
+
+impl BuildInlineAsm for IRBuilder<'_> {
+    fn BuildInlineAsm(&mut self, asm: &str, ins: &[(&str, Var)], out: Option<(&str, TypeMetadata)>, clobbers: &[&str]) -> Option<Var> {
+        let block = self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+        let out = out.map(|(reg, ty)| (reg.to_owned(), Var::new(block, ty)));
+
+        let ins = ins.iter().map(|(reg, var)| (reg.to_string(), var.to_owned())).collect();
+        let clobbers = clobbers.iter().map(|reg| reg.to_string()).collect();
+
+        block.push_ir(InlineAsm::new(asm.to_owned(), ins, out.clone(), clobbers));
+
+        out.map(|(_, out)| out)
+    }
+}
@@ -3,7 +3,7 @@ use super::*;
 impl Ir for Assign<Var, Type> {
     fn dump(&self) -> String {
         let meta: TypeMetadata = self.inner2.into();
-        format!("{} = {} {}", self.inner1.name, meta, self.inner2.val())
+        format!("{} = {} {}", self.inner1.name, meta, self.inner2.fmt_val())
     }
 
     fn dumpColored(&self, profile: ColorProfile) -> String {
@@ -11,7 +11,7 @@ impl Ir for Assign<Var, Type> {
         format!("{} = {} {}", 
             profile.markup(&self.inner1.name, ColorClass::Var), 
             profile.markup(&meta.to_string(), ColorClass::Instr), 
-            profile.markup(&self.inner2.val().to_string(), ColorClass::Value),
+            profile.markup(&self.inner2.fmt_val(), ColorClass::Value),
         )
     }
 
@@ -41,6 +41,22 @@ impl Ir for Assign<Var, Type> {
         if *var == self.inner1 { true }
         else { false }
     }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::Assign
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![Operand::Const(&self.inner2)]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![OperandMut::Const(&mut self.inner2)]
+    }
+
+    fn result(&self) -> Option<&Var> {
+        Some(&self.inner1)
+    }
 }
 
 impl Ir for Assign<Var, Var> {
@@ -85,6 +101,22 @@ impl Ir for Assign<Var, Var> {
         else if *var == self.inner2 { true }
         else { false }
     }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::Assign
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![Operand::Var(&self.inner2)]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![OperandMut::Var(&mut self.inner2)]
+    }
+
+    fn result(&self) -> Option<&Var> {
+        Some(&self.inner1)
+    }
 }
 
 impl Ir for Assign<Var, Const> {
@@ -120,6 +152,75 @@ impl Ir for Assign<Var, Const> {
         if *var == self.inner1 { true }
         else { false }
     }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::Assign
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![]
+    }
+
+    fn result(&self) -> Option<&Var> {
+        Some(&self.inner1)
+    }
+}
+
+impl Ir for Assign<Var, Function> {
+    fn dump(&self) -> String {
+        format!("{} = ptr @{}", self.inner1.name, self.inner2.name)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} = {} {}", 
+            profile.markup(&self.inner1.name, ColorClass::Var), 
+            profile.markup("ptr", ColorClass::Ty), 
+            profile.markup(&format!("@{}", self.inner2.name), ColorClass::Name),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        if self.inner1.ty != TypeMetadata::ptr {
+            Err(VerifyError::Op0Op1TyNoMatch(self.inner1.ty, TypeMetadata::ptr))?
+        }
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new(self.clone())
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr) {
+        registry.compile_assign_var_func(self)
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        *var == self.inner1
+    }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::Assign
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![]
+    }
+
+    fn result(&self) -> Option<&Var> {
+        Some(&self.inner1)
+    }
 }
 
 /// Trait used for overloading the BuildAssign function
@@ -162,3 +263,31 @@ impl BuildAssign<&Const> for IRBuilder<'_> {
         out
     }
 }
+
+impl BuildAssign<&Global> for IRBuilder<'_> {
+    fn BuildAssign(&mut self, value: &Global) -> Var {
+        let block = self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one");
+        
+        let out = Var::new(block, TypeMetadata::ptr);
+
+        // the adress of a global is loaded the same way as the adress of a constant
+        let mut adr = Const::new(value.name.to_owned());
+        adr.thread_local = value.thread_local;
+
+        block.push_ir(Assign::new(out.clone(), adr));
+
+        out
+    }
+}
+
+impl BuildAssign<&Function> for IRBuilder<'_> {
+    fn BuildAssign(&mut self, value: &Function) -> Var {
+        let block = self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one");
+        
+        let out = Var::new(block, TypeMetadata::ptr);
+
+        block.push_ir(Assign::new(out.clone(), value.clone()));
+
+        out
+    }
+}
@@ -0,0 +1,436 @@
+use std::fmt::Display;
+
+use super::*;
+
+/// The memory ordering of an atomic node (like the orderings of the c11 memory model)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AtomicOrdering {
+    /// only the access itself is atomic
+    Relaxed,
+    /// later accesses can't be moved before the access
+    Acquire,
+    /// earlier accesses can't be moved after the access
+    Release,
+    /// acquire and release
+    AcqRel,
+    /// acquire and release with a single total order of all seq_cst accesses
+    SeqCst,
+}
+
+impl Display for AtomicOrdering {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            AtomicOrdering::Relaxed => "relaxed",
+            AtomicOrdering::Acquire => "acquire",
+            AtomicOrdering::Release => "release",
+            AtomicOrdering::AcqRel => "acq_rel",
+            AtomicOrdering::SeqCst => "seq_cst",
+        })
+    }
+}
+
+/// The operation of an atomic read-modify-write node
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AtomicRmwOp {
+    /// old + val
+    Add,
+    /// val
+    Xchg,
+    /// old & val
+    And,
+    /// old | val
+    Or,
+}
+
+impl Display for AtomicRmwOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            AtomicRmwOp::Add => "add",
+            AtomicRmwOp::Xchg => "xchg",
+            AtomicRmwOp::And => "and",
+            AtomicRmwOp::Or => "or",
+        })
+    }
+}
+
+/// checks that the pointer is a pointer and that atomic accesses of the type are possible
+pub(crate) fn verify_atomic(ptr: &Var, ty: TypeMetadata) -> Result<(), VerifyError> {
+    if ptr.ty != TypeMetadata::ptr {
+        Err(VerifyError::Op0Op1TyNoMatch(ptr.ty, TypeMetadata::ptr))?
+    }
+
+    if ty.float() || ty == TypeMetadata::Void {
+        Err(VerifyError::InvalidAtomicType(ty))?
+    }
+
+    Ok(())
+}
+
+impl Ir for AtomicLoad {
+    fn dump(&self) -> String {
+        format!("{} = atomic load {} {} {}", self.out.name, self.out.ty, self.ptr.name, self.order)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} = {} {} {} {}",
+            profile.markup(&self.out.name, ColorClass::Var),
+            profile.markup("atomic load", ColorClass::Instr),
+            profile.markup(&self.out.ty.to_string(), ColorClass::Ty),
+            profile.markup(&self.ptr.name, ColorClass::Var),
+            profile.markup(&self.order.to_string(), ColorClass::Ty),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        verify_atomic(&self.ptr, self.out.ty)?;
+
+        if matches!(self.order, AtomicOrdering::Release | AtomicOrdering::AcqRel) {
+            Err(VerifyError::InvalidAtomicOrdering(self.order))?
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new(self.clone())
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr) {
+        registry.compile_atomic_load(self)
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        *var == self.ptr || *var == self.out
+    }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::AtomicLoad
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![Operand::Var(&self.ptr)]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![OperandMut::Var(&mut self.ptr)]
+    }
+
+    fn result(&self) -> Option<&Var> {
+        Some(&self.out)
+    }
+}
+
+impl Ir for AtomicStore {
+    fn dump(&self) -> String {
+        format!("atomic store {} {}, {} {}", self.val.ty, self.val.name, self.ptr.name, self.order)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} {} {}, {} {}",
+            profile.markup("atomic store", ColorClass::Instr),
+            profile.markup(&self.val.ty.to_string(), ColorClass::Ty),
+            profile.markup(&self.val.name, ColorClass::Var),
+            profile.markup(&self.ptr.name, ColorClass::Var),
+            profile.markup(&self.order.to_string(), ColorClass::Ty),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        verify_atomic(&self.ptr, self.val.ty)?;
+
+        if matches!(self.order, AtomicOrdering::Acquire | AtomicOrdering::AcqRel) {
+            Err(VerifyError::InvalidAtomicOrdering(self.order))?
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new(self.clone())
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr) {
+        registry.compile_atomic_store(self)
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        *var == self.ptr || *var == self.val
+    }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::AtomicStore
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![Operand::Var(&self.ptr), Operand::Var(&self.val)]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![OperandMut::Var(&mut self.ptr), OperandMut::Var(&mut self.val)]
+    }
+}
+
+impl Ir for AtomicRmw {
+    fn dump(&self) -> String {
+        format!("{} = atomic {} {} {}, {} {}", self.out.name, self.op, self.out.ty, self.ptr.name, self.val.name, self.order)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} = {} {} {} {}, {} {}",
+            profile.markup(&self.out.name, ColorClass::Var),
+            profile.markup("atomic", ColorClass::Instr),
+            profile.markup(&self.op.to_string(), ColorClass::Instr),
+            profile.markup(&self.out.ty.to_string(), ColorClass::Ty),
+            profile.markup(&self.ptr.name, ColorClass::Var),
+            profile.markup(&self.val.name, ColorClass::Var),
+            profile.markup(&self.order.to_string(), ColorClass::Ty),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        verify_atomic(&self.ptr, self.out.ty)?;
+
+        if self.val.ty != self.out.ty {
+            Err(VerifyError::Op0Op1TyNoMatch(self.val.ty, self.out.ty))?
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new(self.clone())
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr) {
+        registry.compile_atomic_rmw(self)
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        *var == self.ptr || *var == self.val || *var == self.out
+    }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::AtomicRmw
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![Operand::Var(&self.ptr), Operand::Var(&self.val)]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![OperandMut::Var(&mut self.ptr), OperandMut::Var(&mut self.val)]
+    }
+
+    fn result(&self) -> Option<&Var> {
+        Some(&self.out)
+    }
+}
+
+impl Ir for AtomicCmpXchg {
+    fn dump(&self) -> String {
+        format!("{} = atomic cmpxchg {} {}, {}, {} {}", self.out.name, self.out.ty, self.ptr.name, self.expected.name, self.new.name, self.order)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} = {} {} {}, {}, {} {}",
+            profile.markup(&self.out.name, ColorClass::Var),
+            profile.markup("atomic cmpxchg", ColorClass::Instr),
+            profile.markup(&self.out.ty.to_string(), ColorClass::Ty),
+            profile.markup(&self.ptr.name, ColorClass::Var),
+            profile.markup(&self.expected.name, ColorClass::Var),
+            profile.markup(&self.new.name, ColorClass::Var),
+            profile.markup(&self.order.to_string(), ColorClass::Ty),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        verify_atomic(&self.ptr, self.out.ty)?;
+
+        if self.expected.ty != self.out.ty {
+            Err(VerifyError::Op0Op1TyNoMatch(self.expected.ty, self.out.ty))?
+        }
+        if self.new.ty != self.out.ty {
+            Err(VerifyError::Op0Op1TyNoMatch(self.new.ty, self.out.ty))?
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new(self.clone())
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr) {
+        registry.compile_atomic_cmpxchg(self)
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        *var == self.ptr || *var == self.expected || *var == self.new || *var == self.out
+    }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::AtomicCmpXchg
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![Operand::Var(&self.ptr), Operand::Var(&self.expected), Operand::Var(&self.new)]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![OperandMut::Var(&mut self.ptr), OperandMut::Var(&mut self.expected), OperandMut::Var(&mut self.new)]
+    }
+
+    fn result(&self) -> Option<&Var> {
+        Some(&self.out)
+    }
+}
+
+impl Ir for Fence {
+    fn dump(&self) -> String {
+        format!("fence {}", self.order)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} {}",
+            profile.markup("fence", ColorClass::Instr),
+            profile.markup(&self.order.to_string(), ColorClass::Ty),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        if self.order == AtomicOrdering::Relaxed {
+            Err(VerifyError::InvalidAtomicOrdering(self.order))?
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new(self.clone())
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr) {
+        registry.compile_fence(self)
+    }
+
+    fn uses(&self, _: &Var) -> bool {
+        false
+    }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::Fence
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![]
+    }
+}
+
+/// Trait for the atomic load instruction
+/// Used for overloading the BuildAtomicLoad function
+pub trait BuildAtomicLoad {
+    /// atomically loads a value of the given type from the pointer
+    fn BuildAtomicLoad(&mut self, ptr: Var, ty: TypeMetadata, order: AtomicOrdering) -> Var;
+}
+
+impl BuildAtomicLoad for IRBuilder<'_> {
+    fn BuildAtomicLoad(&mut self, ptr: Var, ty: TypeMetadata, order: AtomicOrdering) -> Var {
+        let block = self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+        let out = Var::new(block, ty);
+
+        block.push_ir(AtomicLoad::new(ptr, out.clone(), order));
+
+        out
+    }
+}
+
+/// Trait for the atomic store instruction
+/// Used for overloading the BuildAtomicStore function
+pub trait BuildAtomicStore {
+    /// atomically stores the value to the pointer
+    fn BuildAtomicStore(&mut self, ptr: Var, val: Var, order: AtomicOrdering);
+}
+
+impl BuildAtomicStore for IRBuilder<'_> {
+    fn BuildAtomicStore(&mut self, ptr: Var, val: Var, order: AtomicOrdering) {
+        self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one")
+            .push_ir(AtomicStore::new(ptr, val, order))
+    }
+}
+
+/// Trait for the atomic read-modify-write instruction
+/// Used for overloading the BuildAtomicRmw function
+pub trait BuildAtomicRmw {
+    /// atomically combines the value at the pointer with the operand and returns the old value
+    fn BuildAtomicRmw(&mut self, op: AtomicRmwOp, ptr: Var, val: Var, order: AtomicOrdering) -> Var;
+}
+
+impl BuildAtomicRmw for IRBuilder<'_> {
+    fn BuildAtomicRmw(&mut self, op: AtomicRmwOp, ptr: Var, val: Var, order: AtomicOrdering) -> Var {
+        let block = self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+        let out = Var::new(block, val.ty);
+
+        block.push_ir(AtomicRmw::new(op, ptr, val, out.clone(), order));
+
+        out
+    }
+}
+
+/// Trait for the atomic compare exchange instruction
+/// Used for overloading the BuildAtomicCmpXchg function
+pub trait BuildAtomicCmpXchg {
+    /// atomically replaces the value at the pointer if it is the expected one and returns the old value
+    fn BuildAtomicCmpXchg(&mut self, ptr: Var, expected: Var, new: Var, order: AtomicOrdering) -> Var;
+}
+
+impl BuildAtomicCmpXchg for IRBuilder<'_> {
+    fn BuildAtomicCmpXchg(&mut self, ptr: Var, expected: Var, new: Var, order: AtomicOrdering) -> Var {
+        let block = self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+        let out = Var::new(block, expected.ty);
+
+        block.push_ir(AtomicCmpXchg::new(ptr, expected, new, out.clone(), order));
+
+        out
+    }
+}
+
+/// Trait for the fence instruction
+/// Used for overloading the BuildFence function
+pub trait BuildFence {
+    /// builds a memory fence
+    fn BuildFence(&mut self, order: AtomicOrdering);
+}
+
+impl BuildFence for IRBuilder<'_> {
+    fn BuildFence(&mut self, order: AtomicOrdering) {
+        self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one")
+            .push_ir(Fence::new(order))
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{Support::ColorClass, IR::{Block, IRBuilder, Var}};
 
-use super::{Br, BrCond, Ir};
+use super::{Br, BrCond, Ir, Opcode, Operand, OperandMut};
 
 impl Ir for Br<Box<Block>> {
     fn dump(&self) -> String {
@@ -36,8 +36,24 @@ impl Ir for Br<Box<Block>> {
         false
     }
     
-    fn is(&self, other: &Box<dyn Ir>) -> bool {
-        other.dump() == self.dump()
+    fn opcode(&self) -> Opcode {
+        Opcode::Br
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![]
+    }
+
+    fn successors(&self) -> Vec<&Block> {
+        vec![&self.inner1]
+    }
+
+    fn successors_mut(&mut self) -> Vec<&mut Block> {
+        vec![&mut self.inner1]
     }
 }
 
@@ -81,6 +97,26 @@ impl Ir for BrCond<Var, Block, Block> {
             false
         }
     }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::BrCond
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![Operand::Var(&self.inner1)]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![OperandMut::Var(&mut self.inner1)]
+    }
+
+    fn successors(&self) -> Vec<&Block> {
+        vec![&self.inner2, &self.inner3]
+    }
+
+    fn successors_mut(&mut self) -> Vec<&mut Block> {
+        vec![&mut self.inner2, &mut self.inner3]
+    }
 }
 
 /// This trait is used for building br nodes
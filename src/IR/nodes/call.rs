@@ -8,7 +8,11 @@ impl Ir for Call<Function, Vec<Var>, Var> {
             fmt.push_str(&format!("{} ", arg))
         }
 
-        format!("{} = call {} {} {}", self.inner3.name, self.inner1.ty.ret, self.inner1.name, fmt)
+        for attr in self.inner1.attrs() {
+            fmt.push_str(&format!("{} ", attr))
+        }
+
+        format!("{} = call {}{} {} {}", self.inner3.name, self.inner1.ty.dump_callconv(), self.inner1.ty.ret, self.inner1.name, fmt)
     }
 
     fn dumpColored(&self, profile: ColorProfile) -> String {
@@ -19,9 +23,15 @@ impl Ir for Call<Function, Vec<Var>, Var> {
             fmt.push(' ');
         }
 
-        format!("{} = {} {} {} {}", 
+        for attr in self.inner1.attrs() {
+            fmt.push_str(&profile.markup(&attr.to_string(), ColorClass::Ty));
+            fmt.push(' ');
+        }
+
+        format!("{} = {} {}{} {} {}", 
             profile.markup(&self.inner3.name, ColorClass::Var),
             profile.markup("call", ColorClass::Instr),
+            profile.markup(&self.inner1.ty.dump_callconv(), ColorClass::Ty),
             profile.markup(&self.inner1.ty.ret.to_string(), ColorClass::Ty),
             profile.markup(&self.inner1.name, ColorClass::Name),
             fmt
@@ -75,9 +85,216 @@ impl Ir for Call<Function, Vec<Var>, Var> {
 
         uses
     }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::Call
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        self.inner2.iter().map(Operand::Var).collect()
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        self.inner2.iter_mut().map(OperandMut::Var).collect()
+    }
+
+    fn result(&self) -> Option<&Var> {
+        Some(&self.inner3)
+    }
+
+    fn callee(&self) -> Option<&Function> {
+        Some(&self.inner1)
+    }
 }
 
 
+impl Ir for CallIndirect {
+    fn dump(&self) -> String {
+        let mut fmt = String::new();
+        
+        for arg in &self.args {
+            fmt.push_str(&format!("{} ", arg))
+        }
+
+        format!("{} = call {} {} {}", self.out.name, self.ty, self.ptr.name, fmt)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        let mut fmt = String::new();
+        
+        for arg in &self.args {
+            fmt.push_str(&arg.to_colored_string(profile));
+            fmt.push(' ');
+        }
+
+        format!("{} = {} {} {} {}", 
+            profile.markup(&self.out.name, ColorClass::Var),
+            profile.markup("call", ColorClass::Instr),
+            profile.markup(&self.ty.to_string(), ColorClass::Ty),
+            profile.markup(&self.ptr.name, ColorClass::Var),
+            fmt
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        if self.ptr.ty != TypeMetadata::ptr {
+            Err(VerifyError::Op0Op1TyNoMatch(self.ptr.ty, TypeMetadata::ptr))?
+        }
+
+        if self.out.ty != self.ty.ret {
+            Err(VerifyError::Op0Op1TyNoMatch(self.out.ty, self.ty.ret))?
+        }
+
+        if self.args.len() < self.ty.args.len() {
+            Err(VerifyError::InvalidArgumentTypeFound)?
+        }
+
+        if self.args.len() > self.ty.args.len() && !self.ty.any_args {
+            Err(VerifyError::ToManyArgumentsWereSupplyed)?
+        }
+
+        for (arg, ty) in self.args.iter().zip(&self.ty.args) {
+            if arg.ty != *ty {
+                Err(VerifyError::InvalidArgumentTypeFound)?
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::from( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr) {
+        registry.compile_call_indirect(self)
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        *var == self.out || *var == self.ptr || self.args.iter().any(|arg| arg.name == var.name)
+    }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::CallIndirect
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        let mut operands = vec![Operand::Var(&self.ptr)];
+        operands.extend(self.args.iter().map(Operand::Var));
+
+        operands
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        let mut operands = vec![OperandMut::Var(&mut self.ptr)];
+        operands.extend(self.args.iter_mut().map(OperandMut::Var));
+
+        operands
+    }
+
+    fn result(&self) -> Option<&Var> {
+        Some(&self.out)
+    }
+}
+
+impl Ir for TailCall<Function, Vec<Var>> {
+    fn dump(&self) -> String {
+        let mut fmt = String::new();
+        
+        for arg in &self.inner2 {
+            fmt.push_str(&format!("{} ", arg))
+        }
+
+        for attr in self.inner1.attrs() {
+            fmt.push_str(&format!("{} ", attr))
+        }
+
+        format!("tail call {}{} {} {}", self.inner1.ty.dump_callconv(), self.inner1.ty.ret, self.inner1.name, fmt)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        let mut fmt = String::new();
+        
+        for arg in &self.inner2 {
+            fmt.push_str(&arg.to_colored_string(profile));
+            fmt.push(' ');
+        }
+
+        for attr in self.inner1.attrs() {
+            fmt.push_str(&profile.markup(&attr.to_string(), ColorClass::Ty));
+            fmt.push(' ');
+        }
+
+        format!("{} {}{} {} {}", 
+            profile.markup("tail call", ColorClass::Instr),
+            profile.markup(&self.inner1.ty.dump_callconv(), ColorClass::Ty),
+            profile.markup(&self.inner1.ty.ret.to_string(), ColorClass::Ty),
+            profile.markup(&self.inner1.name, ColorClass::Name),
+            fmt
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, FuncTy: FunctionType) -> Result<(), VerifyError> {
+        // the result of the callee is directly returned to our caller
+        if self.inner1.ty.ret != FuncTy.ret {
+            Err(VerifyError::RetTyNotFnTy(self.inner1.ty.ret, FuncTy.ret))?
+        }
+
+        let args = &self.inner1.ty.args;
+
+        if self.inner2.len() < args.len() {
+            Err(VerifyError::InvalidArgumentTypeFound)?
+        }
+
+        if self.inner2.len() > args.len() && !self.inner1.ty.any_args {
+            Err(VerifyError::ToManyArgumentsWereSupplyed)?
+        }
+
+        for (arg, ty) in self.inner2.iter().zip(args) {
+            if arg.ty != *ty {
+                Err(VerifyError::InvalidArgumentTypeFound)?
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::from( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr) {
+        registry.compile_tail_call(self)
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        self.inner2.iter().any(|arg| arg.name == var.name)
+    }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::TailCall
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        self.inner2.iter().map(Operand::Var).collect()
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        self.inner2.iter_mut().map(OperandMut::Var).collect()
+    }
+
+    fn callee(&self) -> Option<&Function> {
+        Some(&self.inner1)
+    }
+}
 
 /// Trait for the call instruction
 /// Used for overloading the BuildCall function
@@ -96,3 +313,32 @@ impl BuildCall<&Function, Vec<Var>> for IRBuilder<'_> {
         out 
     }
 }
+
+impl BuildCall<(Var, FunctionType), Vec<Var>> for IRBuilder<'_> {
+    fn BuildCall(&mut self, func: (Var, FunctionType), args: Vec<Var>) -> Var {
+        let block = self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one");
+        
+        let (ptr, ty) = func;
+
+        let out = Var::new(block, ty.ret);
+
+        block.push_ir(CallIndirect::new(ptr, ty, args, out.clone()));
+
+        out 
+    }
+}
+
+/// Trait for the tail call instruction
+/// Used for overloading the BuildTailCall function
+pub trait BuildTailCall<T, U> {
+    /// builds a call which reuses the stack frame of the current function
+    /// and returns the result of the callee (it terminates the block)
+    fn BuildTailCall(&mut self, func: T, args: U);
+}
+
+impl BuildTailCall<&Function, Vec<Var>> for IRBuilder<'_> {
+    fn BuildTailCall(&mut self, func: &Function, args: Vec<Var>) {
+        self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one")
+            .push_ir(TailCall::new(func.clone(), args))
+    }
+}
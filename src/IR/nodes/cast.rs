@@ -23,6 +23,18 @@ impl Ir for Cast<Var, TypeMetadata, Var> {
         if self.inner3.ty != self.inner2 {
             Err(VerifyError::Op0Op1TyNoMatch(self.inner3.ty, self.inner2))?
         }
+
+        let (from, to) = (self.inner1.ty, self.inner2);
+
+        // floats are only converted from and to numbers (a float to bool cast would need a compare)
+        let invalid = from == TypeMetadata::Void || to == TypeMetadata::Void
+            || (from.float() && matches!(to, TypeMetadata::ptr | TypeMetadata::bool))
+            || (to.float() && from == TypeMetadata::ptr);
+
+        if invalid {
+            Err(VerifyError::InvalidCast(from, to))?
+        }
+
         Ok(())
     }
 
@@ -41,6 +53,22 @@ impl Ir for Cast<Var, TypeMetadata, Var> {
     fn compile(&self, registry: &mut TargetBackendDescr) {
         registry.compile_cast_var(&self)
     }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::Cast
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![Operand::Var(&self.inner1)]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![OperandMut::Var(&mut self.inner1)]
+    }
+
+    fn result(&self) -> Option<&Var> {
+        Some(&self.inner3)
+    }
 }
 
 /// Trait for the cast instruction
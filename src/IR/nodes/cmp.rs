@@ -1,8 +1,8 @@
 use std::fmt::Display;
 
-use crate::{Support::ColorClass, IR::{IRBuilder, Var, VerifyError}};
+use crate::{Support::ColorClass, IR::{IRBuilder, TypeMetadata, Var, VerifyError}};
 
-use super::{Cmp, Ir};
+use super::{Cmp, Ir, Opcode, Operand, OperandMut};
 
 /// The "compare mode" (e.g: ls is equal to rs)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -58,6 +58,10 @@ impl Ir for Cmp {
             Err(VerifyError::Op0Op1TyNoMatch(self.ls.ty, self.rs.ty))?
         }
 
+        if self.out.ty != TypeMetadata::bool {
+            Err(VerifyError::CmpOutNotBool(self.out.ty))?
+        }
+
         Ok(())
     }
 
@@ -74,6 +78,22 @@ impl Ir for Cmp {
         else if other.name == self.rs.name { true }
         else { false }
     }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::Cmp
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![Operand::Var(&self.ls), Operand::Var(&self.rs)]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![OperandMut::Var(&mut self.ls), OperandMut::Var(&mut self.rs)]
+    }
+
+    fn result(&self) -> Option<&Var> {
+        Some(&self.out)
+    }
 }
 
 /// The trait `BuildCmp` is used to build the cmp node
@@ -86,7 +106,7 @@ impl BuildCmp for IRBuilder<'_> {
     fn BuildCmp(&mut self, mode: CmpMode, ls: Var, rs: Var) -> Var {
         let block = self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one");
         
-        let out = Var::new(block, ls.ty);
+        let out = Var::new(block, TypeMetadata::bool);
 
         block.push_ir( Cmp::new(mode, ls, rs, out.to_owned()) );
 
@@ -0,0 +1,161 @@
+use super::*;
+
+impl Ir for Gep<Var> {
+    fn dump(&self) -> String {
+        format!("{} = gep {} {}, {}", self.out.name, self.ty, self.ptr.name, self.index.name)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} = {} {} {}, {}", 
+            profile.markup(&self.out.name, ColorClass::Var), 
+            profile.markup("gep", ColorClass::Instr),
+            profile.markup(&self.ty.to_string(), ColorClass::Ty),
+            profile.markup(&self.ptr.name, ColorClass::Var), 
+            profile.markup(&self.index.name, ColorClass::Var), 
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        self.verify_ptrs()?;
+
+        if let AggregateType::Struct { .. } = self.ty {
+            Err(VerifyError::FieldIndexNotConstant)?
+        }
+
+        if self.index.ty.float() {
+            Err(VerifyError::Op0Op1TyNoMatch(self.index.ty, TypeMetadata::i64))?
+        }
+
+        Ok(())
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        *var == self.out || *var == self.ptr || *var == self.index
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::from( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr) {
+        registry.compile_gep_var(self)
+    }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::Gep
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![Operand::Var(&self.ptr), Operand::Var(&self.index)]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![OperandMut::Var(&mut self.ptr), OperandMut::Var(&mut self.index)]
+    }
+
+    fn result(&self) -> Option<&Var> {
+        Some(&self.out)
+    }
+}
+
+impl Ir for Gep<Type> {
+    fn dump(&self) -> String {
+        format!("{} = gep {} {}, {}", self.out.name, self.ty, self.ptr.name, self.index.fmt_val())
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} = {} {} {}, {}", 
+            profile.markup(&self.out.name, ColorClass::Var), 
+            profile.markup("gep", ColorClass::Instr),
+            profile.markup(&self.ty.to_string(), ColorClass::Ty),
+            profile.markup(&self.ptr.name, ColorClass::Var), 
+            profile.markup(&self.index.fmt_val(), ColorClass::Value), 
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        self.verify_ptrs()?;
+
+        let index = self.index.val();
+
+        if index >= self.ty.len() as u64 {
+            Err(VerifyError::IndexOutOfBounds(index, self.ty.len()))?
+        }
+
+        Ok(())
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        *var == self.out || *var == self.ptr
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::from( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr) {
+        registry.compile_gep_type(self)
+    }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::Gep
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![Operand::Var(&self.ptr), Operand::Const(&self.index)]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![OperandMut::Var(&mut self.ptr), OperandMut::Const(&mut self.index)]
+    }
+
+    fn result(&self) -> Option<&Var> {
+        Some(&self.out)
+    }
+}
+
+impl<T> Gep<T> {
+    fn verify_ptrs(&self) -> Result<(), VerifyError> {
+        if self.ptr.ty != TypeMetadata::ptr {
+            Err(VerifyError::Op0Op1TyNoMatch(self.ptr.ty, TypeMetadata::ptr))?
+        }
+        if self.out.ty != TypeMetadata::ptr {
+            Err(VerifyError::Op0Op1TyNoMatch(self.out.ty, TypeMetadata::ptr))?
+        }
+        Ok(())
+    }
+}
+
+/// Trait for the gep instruction
+/// Used for overloading the BuildGep function
+pub trait BuildGep<T> {
+    /// returns a pointer to the field/element at the index of the aggregate the pointer points to
+    fn BuildGep(&mut self, ty: AggregateType, ptr: Var, index: T) -> Var;
+}
+
+macro_rules! BuildGepImpl {
+    ($index:ty) => {
+        impl BuildGep<$index> for IRBuilder<'_> {
+            fn BuildGep(&mut self, ty: AggregateType, ptr: Var, index: $index) -> Var {
+                let block = self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one");
+                
+                let out = Var::new(block, TypeMetadata::ptr);
+
+                block.push_ir( Gep::new(ty, ptr, index, out.to_owned()) );
+
+                out
+            }
+        }
+    };
+}
+
+BuildGepImpl!(Var);
+BuildGepImpl!(Type);
@@ -0,0 +1,121 @@
+use super::*;
+
+/// checks that the size of a memory intrinsic is an integer
+/// (variable sizes are passed to the runtime function, so they need to be 64 bit)
+fn verify_size<T: SelectValue>(size: &T) -> Result<(), VerifyError> {
+    let ty = size.ty();
+
+    if ty.float() || ty == TypeMetadata::Void || ty == TypeMetadata::ptr || (size.var().is_some() && ty.bitSize() != 64) {
+        Err(VerifyError::Op0Op1TyNoMatch(ty, TypeMetadata::u64))?
+    }
+
+    Ok(())
+}
+
+macro_rules! MemIntrinsicNode {
+    ($name:ident, $op:ident, $dump:expr, $size:ty, $compileFunc:ident, $verifyOp:expr) => {
+        impl Ir for $name<$size> {
+            fn dump(&self) -> String {
+                format!("{} {}, {} {}, {} {}", $dump, self.dst.name, self.$op.ty, self.$op.name, self.size.ty(), self.size.fmt_value())
+            }
+
+            fn dumpColored(&self, profile: ColorProfile) -> String {
+                format!("{} {}, {} {}, {} {}",
+                    profile.markup($dump, ColorClass::Instr),
+                    profile.markup(&self.dst.name, ColorClass::Var),
+                    profile.markup(&self.$op.ty.to_string(), ColorClass::Ty),
+                    profile.markup(&self.$op.name, ColorClass::Var),
+                    profile.markup(&self.size.ty().to_string(), ColorClass::Ty),
+                    profile.markup(&self.size.fmt_value(), ColorClass::Value),
+                )
+            }
+
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+
+            fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+                if self.dst.ty != TypeMetadata::ptr {
+                    Err(VerifyError::Op0Op1TyNoMatch(self.dst.ty, TypeMetadata::ptr))?
+                }
+
+                $verifyOp(&self.$op)?;
+
+                verify_size(&self.size)
+            }
+
+            fn clone_box(&self) -> Box<dyn Ir> {
+                Box::new(self.clone())
+            }
+
+            fn compile(&self, registry: &mut TargetBackendDescr) {
+                registry.$compileFunc(self)
+            }
+
+            fn uses(&self, var: &Var) -> bool {
+                *var == self.dst || *var == self.$op || self.size.var().is_some_and(|size| size.name == var.name)
+            }
+
+            fn opcode(&self) -> Opcode {
+                Opcode::$name
+            }
+
+            fn operands(&self) -> Vec<Operand<'_>> {
+                vec![Operand::Var(&self.dst), Operand::Var(&self.$op), self.size.operand()]
+            }
+
+            fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+                vec![OperandMut::Var(&mut self.dst), OperandMut::Var(&mut self.$op), self.size.operand_mut()]
+            }
+        }
+    };
+}
+
+fn verify_memcpy_src(src: &Var) -> Result<(), VerifyError> {
+    if src.ty != TypeMetadata::ptr {
+        Err(VerifyError::Op0Op1TyNoMatch(src.ty, TypeMetadata::ptr))?
+    }
+
+    Ok(())
+}
+
+fn verify_memset_val(val: &Var) -> Result<(), VerifyError> {
+    if val.ty != TypeMetadata::u8 && val.ty != TypeMetadata::i8 {
+        Err(VerifyError::Op0Op1TyNoMatch(val.ty, TypeMetadata::u8))?
+    }
+
+    Ok(())
+}
+
+MemIntrinsicNode!(MemCpy, src, "memcpy", Var, compile_memcpy_var, verify_memcpy_src);
+MemIntrinsicNode!(MemCpy, src, "memcpy", Type, compile_memcpy_type, verify_memcpy_src);
+MemIntrinsicNode!(MemSet, val, "memset", Var, compile_memset_var, verify_memset_val);
+MemIntrinsicNode!(MemSet, val, "memset", Type, compile_memset_type, verify_memset_val);
+
+/// Trait for the memcpy instruction
+/// Used for overloading the BuildMemCpy function
+pub trait BuildMemCpy<T> {
+    /// copies `size` bytes from the source to the destination pointer
+    fn BuildMemCpy(&mut self, dst: Var, src: Var, size: T);
+}
+
+impl<T: SelectValue> BuildMemCpy<T> for IRBuilder<'_> where MemCpy<T>: Ir {
+    fn BuildMemCpy(&mut self, dst: Var, src: Var, size: T) {
+        self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one")
+            .push_ir(MemCpy::new(dst, src, size))
+    }
+}
+
+/// Trait for the memset instruction
+/// Used for overloading the BuildMemSet function
+pub trait BuildMemSet<T> {
+    /// fills `size` bytes at the destination pointer with the byte value
+    fn BuildMemSet(&mut self, dst: Var, val: Var, size: T);
+}
+
+impl<T: SelectValue> BuildMemSet<T> for IRBuilder<'_> where MemSet<T>: Ir {
+    fn BuildMemSet(&mut self, dst: Var, val: Var, size: T) {
+        self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one")
+            .push_ir(MemSet::new(dst, val, size))
+    }
+}
@@ -0,0 +1,77 @@
+use super::*;
+
+impl Ir for Load<Var, Var, TypeMetadata> {
+    fn dump(&self) -> String {
+        format!("{} = load {} {}", self.inner1.name, self.inner3, self.inner2.name)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} = {} {} {}", 
+            profile.markup(&self.inner1.name, ColorClass::Var), 
+            profile.markup("load", ColorClass::Instr),
+            profile.markup(&self.inner3.to_string(), ColorClass::Ty),
+            profile.markup(&self.inner2.name, ColorClass::Var), 
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        if self.inner2.ty != TypeMetadata::ptr {
+            Err(VerifyError::Op0Op1TyNoMatch(self.inner2.ty, TypeMetadata::ptr))?
+        }
+        if self.inner1.ty != self.inner3 {
+            Err(VerifyError::Op0Op1TyNoMatch(self.inner1.ty, self.inner3))?
+        }
+        Ok(())
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        *var == self.inner1 || *var == self.inner2
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::from( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr) {
+        registry.compile_load(self)
+    }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::Load
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![Operand::Var(&self.inner2)]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![OperandMut::Var(&mut self.inner2)]
+    }
+
+    fn result(&self) -> Option<&Var> {
+        Some(&self.inner1)
+    }
+}
+
+/// Trait for the load instruction
+/// Used for overloading the BuildLoad function
+pub trait BuildLoad<T, U> {
+    /// loads a value of the given type from the pointer
+    fn BuildLoad(&mut self, ptr: T, ty: U) -> Var;
+}
+
+impl BuildLoad<Var, TypeMetadata> for IRBuilder<'_> {
+    fn BuildLoad(&mut self, ptr: Var, ty: TypeMetadata) -> Var {
+        let block = self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one");
+        
+        let out = Var::new(block, ty);
+
+        block.push_ir(Load::new(out.clone(), ptr, ty));
+
+        out
+    }
+}
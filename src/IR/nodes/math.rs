@@ -59,7 +59,7 @@ macro_rules! MathIrNode {
             }
         
             fn dump(&self) -> String {
-                format!("{} = {} {} {}, {}", self.inner3.name, $dump, self.inner3.ty, self.inner1.val(), self.inner2.val())
+                format!("{} = {} {} {}, {}", self.inner3.name, $dump, self.inner3.ty, self.inner1.val(), self.inner2.fmt_val())
             }
         
             fn dumpColored(&self, profile: ColorProfile) -> String {
@@ -67,8 +67,8 @@ macro_rules! MathIrNode {
                     profile.markup(&self.inner3.name, ColorClass::Var), 
                     profile.markup($dump, ColorClass::Instr), 
                     profile.markup(&self.inner3.ty.to_string(), ColorClass::Ty), 
-                    profile.markup(&self.inner1.val().to_string(), ColorClass::Value), 
-                    profile.markup(&self.inner2.val().to_string(), ColorClass::Value)
+                    profile.markup(&self.inner1.fmt_val(), ColorClass::Value), 
+                    profile.markup(&self.inner2.fmt_val(), ColorClass::Value)
                 )
             }
         
@@ -86,6 +86,10 @@ macro_rules! MathIrNode {
                         Err(VerifyError::Op0Op1TyNoMatch(op0Ty, op2Ty))?
                     } else { todo!("unknown error variant (debug: ty0 {} ty1 {} ty2 {})", op0Ty, op1Ty, op2Ty) }
                 }
+
+                if self.opcode().int_only() && op0Ty.float() {
+                    Err(VerifyError::IntOnlyOperation(op0Ty))?
+                }
         
                 Ok(())
             }
@@ -102,6 +106,22 @@ macro_rules! MathIrNode {
                 if *var == self.inner3 { true }
                 else { false }
             }
+
+            fn opcode(&self) -> Opcode {
+                Opcode::$name
+            }
+
+            fn operands(&self) -> Vec<Operand<'_>> {
+                vec![Operand::Const(&self.inner1), Operand::Const(&self.inner2)]
+            }
+
+            fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+                vec![OperandMut::Const(&mut self.inner1), OperandMut::Const(&mut self.inner2)]
+            }
+
+            fn result(&self) -> Option<&Var> {
+                Some(&self.inner3)
+            }
         }
         
         impl Ir for $name<Var, Var, Var> {
@@ -137,6 +157,10 @@ macro_rules! MathIrNode {
                         Err(VerifyError::Op0Op1TyNoMatch(op0Ty, op2Ty))?
                     } else { todo!("unknown error variant (debug: ty0 {} ty1 {} ty2 {})", op0Ty, op1Ty, op2Ty) }
                 }
+
+                if self.opcode().int_only() && op0Ty.float() {
+                    Err(VerifyError::IntOnlyOperation(op0Ty))?
+                }
         
                 Ok(())
             }
@@ -153,6 +177,22 @@ macro_rules! MathIrNode {
                 if *var == self.inner1 || *var == self.inner2 || *var == self.inner3 { true }
                 else { false }
             }
+
+            fn opcode(&self) -> Opcode {
+                Opcode::$name
+            }
+
+            fn operands(&self) -> Vec<Operand<'_>> {
+                vec![Operand::Var(&self.inner1), Operand::Var(&self.inner2)]
+            }
+
+            fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+                vec![OperandMut::Var(&mut self.inner1), OperandMut::Var(&mut self.inner2)]
+            }
+
+            fn result(&self) -> Option<&Var> {
+                Some(&self.inner3)
+            }
         }
         
         impl Ir for $name<Var, Type, Var> {
@@ -161,7 +201,7 @@ macro_rules! MathIrNode {
             }
         
             fn dump(&self) -> String {
-                format!("{} = {} {} {}, {}", self.inner3.name, $dump, self.inner1.ty, self.inner1.name, self.inner2.val())
+                format!("{} = {} {} {}, {}", self.inner3.name, $dump, self.inner1.ty, self.inner1.name, self.inner2.fmt_val())
             }
         
             fn dumpColored(&self, profile: ColorProfile) -> String {
@@ -170,7 +210,7 @@ macro_rules! MathIrNode {
                     profile.markup($dump, ColorClass::Instr), 
                     profile.markup(&self.inner1.ty.to_string(), ColorClass::Ty), 
                     profile.markup(&self.inner1.name.to_string(), ColorClass::Var), 
-                    profile.markup(&self.inner2.val().to_string(), ColorClass::Var)
+                    profile.markup(&self.inner2.fmt_val(), ColorClass::Var)
                 )
             }
         
@@ -188,6 +228,10 @@ macro_rules! MathIrNode {
                         Err(VerifyError::Op0Op1TyNoMatch(op0Ty, op2Ty))?
                     } else { todo!("unknown error variant (debug: ty0 {} ty1 {} ty2 {})", op0Ty, op1Ty, op2Ty) }
                 }
+
+                if self.opcode().int_only() && op0Ty.float() {
+                    Err(VerifyError::IntOnlyOperation(op0Ty))?
+                }
         
                 Ok(())
             }
@@ -204,6 +248,22 @@ macro_rules! MathIrNode {
                 if *var == self.inner1 || *var == self.inner3 { true }
                 else { false }
             }
+
+            fn opcode(&self) -> Opcode {
+                Opcode::$name
+            }
+
+            fn operands(&self) -> Vec<Operand<'_>> {
+                vec![Operand::Var(&self.inner1), Operand::Const(&self.inner2)]
+            }
+
+            fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+                vec![OperandMut::Var(&mut self.inner1), OperandMut::Const(&mut self.inner2)]
+            }
+
+            fn result(&self) -> Option<&Var> {
+                Some(&self.inner3)
+            }
         }
         
     };
@@ -217,3 +277,175 @@ MathIrNode!(And,    compile_and_var_var,   compile_and_var_type, compile_and_typ
 MathIrNode!(Mul,    compile_mul_var_var,   compile_mul_var_type, compile_mul_type_type, BuildMul, BuildMul, "mul");
 MathIrNode!(Div,    compile_div_var_var,   compile_div_var_type, compile_div_type_type, BuildDiv, BuildDiv, "div");
 
+MathIrNode!(Rem,    compile_rem_var_var,   compile_rem_var_type, compile_rem_type_type, BuildRem, BuildRem, "rem");
+MathIrNode!(Shl,    compile_shl_var_var,   compile_shl_var_type, compile_shl_type_type, BuildShl, BuildShl, "shl");
+MathIrNode!(Lshr,   compile_lshr_var_var,  compile_lshr_var_type, compile_lshr_type_type, BuildLshr, BuildLshr, "lshr");
+MathIrNode!(Ashr,   compile_ashr_var_var,  compile_ashr_var_type, compile_ashr_type_type, BuildAshr, BuildAshr, "ashr");
+
+macro_rules! UnaryIrNode {
+    ($name:ident, $compileFuncVar:ident, $compileFuncTy:ident, $buildTraitName:ident, $buildFuncName:ident, $dump:expr) => {
+        /// Used for overloading the build function
+        pub trait $buildTraitName<T> {
+            /// Applies the operation to the value
+            fn $buildFuncName(&mut self, op: T) -> Var;
+        }
+
+        impl $buildTraitName<Type> for IRBuilder<'_> {
+            fn $buildFuncName(&mut self, op: Type) -> Var {
+                let block = self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+                let var = Var::new(block, op.into());
+
+                block.push_ir($name::new(op, var.clone()));
+
+                var
+            }
+        }
+
+        impl $buildTraitName<Var> for IRBuilder<'_> {
+            fn $buildFuncName(&mut self, op: Var) -> Var {
+                let block = self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+                let var = Var::new(block, op.ty);
+
+                block.push_ir($name::new(op, var.clone()));
+
+                var
+            }
+        }
+
+        impl Ir for $name<Type, Var> {
+            fn clone_box(&self) -> Box<dyn Ir> {
+                Box::new(self.clone())
+            }
+
+            fn dump(&self) -> String {
+                format!("{} = {} {} {}", self.inner2.name, $dump, self.inner2.ty, self.inner1.fmt_val())
+            }
+
+            fn dumpColored(&self, profile: ColorProfile) -> String {
+                format!("{} = {} {} {}", 
+                    profile.markup(&self.inner2.name, ColorClass::Var), 
+                    profile.markup($dump, ColorClass::Instr), 
+                    profile.markup(&self.inner2.ty.to_string(), ColorClass::Ty), 
+                    profile.markup(&self.inner1.fmt_val(), ColorClass::Value)
+                )
+            }
+
+            fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+                let op0Ty: TypeMetadata = self.inner1.into();
+                let op1Ty: TypeMetadata = self.inner2.ty;
+
+                if op0Ty != op1Ty {
+                    Err(VerifyError::Op0Op1TyNoMatch(op0Ty, op1Ty))?
+                }
+
+                if self.opcode().int_only() && op0Ty.float() {
+                    Err(VerifyError::IntOnlyOperation(op0Ty))?
+                }
+
+                Ok(())
+            }
+
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+
+            fn compile(&self, registry: &mut TargetBackendDescr) {
+                registry.$compileFuncTy(&self)
+            }
+
+            fn uses(&self, var: &Var) -> bool {
+                if *var == self.inner2 { true }
+                else { false }
+            }
+
+            fn opcode(&self) -> Opcode {
+                Opcode::$name
+            }
+
+            fn operands(&self) -> Vec<Operand<'_>> {
+                vec![Operand::Const(&self.inner1)]
+            }
+
+            fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+                vec![OperandMut::Const(&mut self.inner1)]
+            }
+
+            fn result(&self) -> Option<&Var> {
+                Some(&self.inner2)
+            }
+        }
+
+        impl Ir for $name<Var, Var> {
+            fn clone_box(&self) -> Box<dyn Ir> {
+                Box::new(self.clone())
+            }
+
+            fn dump(&self) -> String {
+                format!("{} = {} {} {}", self.inner2.name, $dump, self.inner2.ty, self.inner1.name)
+            }
+
+            fn dumpColored(&self, profile: ColorProfile) -> String {
+                format!("{} = {} {} {}", 
+                    profile.markup(&self.inner2.name, ColorClass::Var), 
+                    profile.markup($dump, ColorClass::Instr), 
+                    profile.markup(&self.inner2.ty.to_string(), ColorClass::Ty), 
+                    profile.markup(&self.inner1.name, ColorClass::Var)
+                )
+            }
+
+            fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+                let op0Ty: TypeMetadata = self.inner1.ty;
+                let op1Ty: TypeMetadata = self.inner2.ty;
+
+                if op0Ty != op1Ty {
+                    Err(VerifyError::Op0Op1TyNoMatch(op0Ty, op1Ty))?
+                }
+
+                if self.opcode().int_only() && op0Ty.float() {
+                    Err(VerifyError::IntOnlyOperation(op0Ty))?
+                }
+
+                Ok(())
+            }
+
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+
+            fn compile(&self, registry: &mut TargetBackendDescr) {
+                registry.$compileFuncVar(&self)
+            }
+
+            fn uses(&self, var: &Var) -> bool {
+                if *var == self.inner1 || *var == self.inner2 { true }
+                else { false }
+            }
+
+            fn opcode(&self) -> Opcode {
+                Opcode::$name
+            }
+
+            fn operands(&self) -> Vec<Operand<'_>> {
+                vec![Operand::Var(&self.inner1)]
+            }
+
+            fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+                vec![OperandMut::Var(&mut self.inner1)]
+            }
+
+            fn result(&self) -> Option<&Var> {
+                Some(&self.inner2)
+            }
+        }
+    };
+}
+
+UnaryIrNode!(Neg, compile_neg_var, compile_neg_type, BuildNeg, BuildNeg, "neg");
+UnaryIrNode!(Not, compile_not_var, compile_not_type, BuildNot, BuildNot, "not");
+
+UnaryIrNode!(Bswap, compile_bswap_var, compile_bswap_type, BuildBswap, BuildBswap, "bswap");
+UnaryIrNode!(Ctpop, compile_ctpop_var, compile_ctpop_type, BuildCtpop, BuildCtpop, "ctpop");
+UnaryIrNode!(Ctlz, compile_ctlz_var, compile_ctlz_type, BuildCtlz, BuildCtlz, "ctlz");
+UnaryIrNode!(Cttz, compile_cttz_var, compile_cttz_type, BuildCttz, BuildCttz, "cttz");
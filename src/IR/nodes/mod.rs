@@ -1,5 +1,5 @@
 use std::{any::Any, fmt::Debug, hash::Hash};
-use super::{Const, Function, FunctionType, IRBuilder, Type, TypeMetadata, Var, VerifyError};
+use super::{AggregateType, Block, Const, Function, FunctionType, Global, IRBuilder, Type, TypeMetadata, Var, VerifyError};
 use crate::Target::TargetBackendDescr;
 
 mod assign;
@@ -9,6 +9,18 @@ mod math;
 mod ret;
 mod br;
 mod cmp;
+mod alloca;
+mod load;
+mod store;
+mod phi;
+mod switch;
+mod select;
+mod gep;
+mod intrinsic;
+mod atomic;
+mod overflow;
+mod va;
+mod asm;
 
 pub use assign::*;
 pub use call::*;
@@ -17,6 +29,18 @@ pub use math::*;
 pub use ret::*;
 pub use br::*;
 pub use cmp::*;
+pub use alloca::*;
+pub use load::*;
+pub use store::*;
+pub use phi::*;
+pub use switch::*;
+pub use select::*;
+pub use gep::*;
+pub use intrinsic::*;
+pub use atomic::*;
+pub use overflow::*;
+pub use va::*;
+pub use asm::*;
 
 macro_rules! IrTypeWith3 {
     ($name:tt, $param1:tt, $param2:tt, $param3:tt) => {
@@ -99,6 +123,7 @@ macro_rules! IrTypeWith1 {
 
 IrTypeWith1!(Return, T);
 IrTypeWith3!(Call, T, U, Z);
+IrTypeWith2!(TailCall, T, U);
 IrTypeWith2!(Assign, T, U);
 IrTypeWith3!(Cast, T, U, Z);
 IrTypeWith3!(Add, T, U, Z);
@@ -108,10 +133,26 @@ IrTypeWith3!(Or, T, U, Z);
 IrTypeWith3!(And, T, U, Z);
 IrTypeWith3!(Mul, T, U, Z);
 IrTypeWith3!(Div, T, U, Z);
+IrTypeWith3!(Rem, T, U, Z);
+IrTypeWith3!(Shl, T, U, Z);
+IrTypeWith3!(Lshr, T, U, Z);
+IrTypeWith3!(Ashr, T, U, Z);
+
+IrTypeWith2!(Neg, T, U);
+IrTypeWith2!(Not, T, U);
+
+IrTypeWith2!(Bswap, T, U);
+IrTypeWith2!(Ctpop, T, U);
+IrTypeWith2!(Ctlz, T, U);
+IrTypeWith2!(Cttz, T, U);
 
 IrTypeWith1!(Br, T);
 IrTypeWith3!(BrCond, T, U, Z);
 
+IrTypeWith2!(Alloca, T, U);
+IrTypeWith3!(Load, T, U, Z);
+IrTypeWith2!(Store, T, U);
+
 /// The cmp node is used to compare values
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Cmp {
@@ -136,11 +177,528 @@ impl Cmp {
     }
 }
 
+/// The phi node is used to select the value of the block from which the current block was entered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Phi {
+    pub(crate) out: Var,
+    pub(crate) recive_from_blocks: Vec<(Block, Var)>,
+}
+
+impl Phi {
+    /// Creates a new instance
+    #[allow(dead_code)]
+    pub(crate) fn new(out: Var, recive_from_blocks: Vec<(Block, Var)>) -> Box<Self> {
+        Box::from(
+            Self {
+                out: out,
+                recive_from_blocks: recive_from_blocks,
+            }
+        )
+    }
+}
+
+/// The switch node is used to jump to the block of the case which matches the value
+/// (or to the default block if no case matches)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Switch {
+    pub(crate) var: Var,
+    pub(crate) default: Block,
+    pub(crate) cases: Vec<(Type, Block)>,
+}
+
+impl Switch {
+    /// Creates a new instance
+    #[allow(dead_code)]
+    pub(crate) fn new(var: Var, default: Block, cases: Vec<(Type, Block)>) -> Box<Self> {
+        Box::from(
+            Self {
+                var: var,
+                default: default,
+                cases: cases,
+            }
+        )
+    }
+}
+
+/// The select node is used to choose between two values based on a condition
+/// (without branching)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Select<T, U> {
+    pub(crate) cond: Var,
+    pub(crate) yes: T,
+    pub(crate) no: U,
+    pub(crate) out: Var,
+}
+
+impl<T, U> Select<T, U> {
+    /// Creates a new instance
+    #[allow(dead_code)]
+    pub(crate) fn new(cond: Var, yes: T, no: U, out: Var) -> Box<Self> {
+        Box::from(
+            Self {
+                cond: cond,
+                yes: yes,
+                no: no,
+                out: out,
+            }
+        )
+    }
+}
+
+/// The indirect call node is used to call the function the pointer points to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallIndirect {
+    pub(crate) ptr: Var,
+    pub(crate) ty: FunctionType,
+    pub(crate) args: Vec<Var>,
+    pub(crate) out: Var,
+}
+
+impl CallIndirect {
+    /// Creates a new instance
+    #[allow(dead_code)]
+    pub(crate) fn new(ptr: Var, ty: FunctionType, args: Vec<Var>, out: Var) -> Box<Self> {
+        Box::from(
+            Self {
+                ptr: ptr,
+                ty: ty,
+                args: args,
+                out: out,
+            }
+        )
+    }
+}
+
+/// The memcpy node copies `size` bytes from the memory the source pointer points to
+/// into the memory the destination pointer points to (the memory areas mustn't overlap)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemCpy<T> {
+    pub(crate) dst: Var,
+    pub(crate) src: Var,
+    pub(crate) size: T,
+}
+
+impl<T> MemCpy<T> {
+    /// Creates a new instance
+    #[allow(dead_code)]
+    pub(crate) fn new(dst: Var, src: Var, size: T) -> Box<Self> {
+        Box::from(
+            Self {
+                dst: dst,
+                src: src,
+                size: size,
+            }
+        )
+    }
+}
+
+/// The memset node fills `size` bytes of the memory the destination pointer points to with the byte value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemSet<T> {
+    pub(crate) dst: Var,
+    pub(crate) val: Var,
+    pub(crate) size: T,
+}
+
+impl<T> MemSet<T> {
+    /// Creates a new instance
+    #[allow(dead_code)]
+    pub(crate) fn new(dst: Var, val: Var, size: T) -> Box<Self> {
+        Box::from(
+            Self {
+                dst: dst,
+                val: val,
+                size: size,
+            }
+        )
+    }
+}
+
+/// The unreachable node marks the end of a code path which can never be executed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unreachable;
+
+impl Unreachable {
+    /// Creates a new instance
+    #[allow(dead_code)]
+    pub(crate) fn new() -> Box<Self> {
+        Box::from(Self)
+    }
+}
+
+/// The gep node is used to compute the adress of a field of a struct or an element of an array
+/// (the pointer points to the aggregate)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gep<T> {
+    pub(crate) ty: AggregateType,
+    pub(crate) ptr: Var,
+    pub(crate) index: T,
+    pub(crate) out: Var,
+}
+
+impl<T> Gep<T> {
+    /// Creates a new instance
+    #[allow(dead_code)]
+    pub(crate) fn new(ty: AggregateType, ptr: Var, index: T, out: Var) -> Box<Self> {
+        Box::from(
+            Self {
+                ty: ty,
+                ptr: ptr,
+                index: index,
+                out: out,
+            }
+        )
+    }
+}
+
+/// The atomic load node loads a value from the pointer in one indivisible access
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtomicLoad {
+    pub(crate) ptr: Var,
+    pub(crate) out: Var,
+    pub(crate) order: atomic::AtomicOrdering,
+}
+
+impl AtomicLoad {
+    /// Creates a new instance
+    #[allow(dead_code)]
+    pub(crate) fn new(ptr: Var, out: Var, order: atomic::AtomicOrdering) -> Box<Self> {
+        Box::from(
+            Self {
+                ptr: ptr,
+                out: out,
+                order: order,
+            }
+        )
+    }
+}
+
+/// The atomic store node stores a value to the pointer in one indivisible access
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtomicStore {
+    pub(crate) ptr: Var,
+    pub(crate) val: Var,
+    pub(crate) order: atomic::AtomicOrdering,
+}
+
+impl AtomicStore {
+    /// Creates a new instance
+    #[allow(dead_code)]
+    pub(crate) fn new(ptr: Var, val: Var, order: atomic::AtomicOrdering) -> Box<Self> {
+        Box::from(
+            Self {
+                ptr: ptr,
+                val: val,
+                order: order,
+            }
+        )
+    }
+}
+
+/// The atomic read-modify-write node combines the value at the pointer with the operand
+/// and writes the result back (the output is the old value)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtomicRmw {
+    pub(crate) op: atomic::AtomicRmwOp,
+    pub(crate) ptr: Var,
+    pub(crate) val: Var,
+    pub(crate) out: Var,
+    pub(crate) order: atomic::AtomicOrdering,
+}
+
+impl AtomicRmw {
+    /// Creates a new instance
+    #[allow(dead_code)]
+    pub(crate) fn new(op: atomic::AtomicRmwOp, ptr: Var, val: Var, out: Var, order: atomic::AtomicOrdering) -> Box<Self> {
+        Box::from(
+            Self {
+                op: op,
+                ptr: ptr,
+                val: val,
+                out: out,
+                order: order,
+            }
+        )
+    }
+}
+
+/// The atomic compare exchange node stores the new value to the pointer if the value at the pointer
+/// is the expected one (the output is the old value)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtomicCmpXchg {
+    pub(crate) ptr: Var,
+    pub(crate) expected: Var,
+    pub(crate) new: Var,
+    pub(crate) out: Var,
+    pub(crate) order: atomic::AtomicOrdering,
+}
+
+impl AtomicCmpXchg {
+    /// Creates a new instance
+    #[allow(dead_code)]
+    pub(crate) fn new(ptr: Var, expected: Var, new: Var, out: Var, order: atomic::AtomicOrdering) -> Box<Self> {
+        Box::from(
+            Self {
+                ptr: ptr,
+                expected: expected,
+                new: new,
+                out: out,
+                order: order,
+            }
+        )
+    }
+}
+
+/// The fence node orders the memory accesses before it against the ones after it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fence {
+    pub(crate) order: atomic::AtomicOrdering,
+}
+
+impl Fence {
+    /// Creates a new instance
+    #[allow(dead_code)]
+    pub(crate) fn new(order: atomic::AtomicOrdering) -> Box<Self> {
+        Box::from(Self { order: order })
+    }
+}
+
+/// The overflow checked arithmetic node computes the wrapping result and a bool which is true
+/// if the result overflowed (signed types check for signed overflow, unsigned types for carry)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WithOverflow {
+    pub(crate) op: overflow::OverflowOp,
+    pub(crate) ls: Var,
+    pub(crate) rs: Var,
+    pub(crate) out: Var,
+    pub(crate) overflow: Var,
+}
+
+impl WithOverflow {
+    /// Creates a new instance
+    #[allow(dead_code)]
+    pub(crate) fn new(op: overflow::OverflowOp, ls: Var, rs: Var, out: Var, overflow: Var) -> Box<Self> {
+        Box::from(
+            Self {
+                op: op,
+                ls: ls,
+                rs: rs,
+                out: out,
+                overflow: overflow,
+            }
+        )
+    }
+}
+
+/// The va_start node initializes the va_list the pointer points to,
+/// so it refers to the first variadic argument of the function
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VaStart {
+    pub(crate) list: Var,
+}
+
+impl VaStart {
+    /// Creates a new instance
+    #[allow(dead_code)]
+    pub(crate) fn new(list: Var) -> Box<Self> {
+        Box::from(Self { list: list })
+    }
+}
+
+/// The va_arg node reads the next variadic argument as the type of the output
+/// and advances the va_list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VaArg {
+    pub(crate) list: Var,
+    pub(crate) out: Var,
+}
+
+impl VaArg {
+    /// Creates a new instance
+    #[allow(dead_code)]
+    pub(crate) fn new(list: Var, out: Var) -> Box<Self> {
+        Box::from(Self { list: list, out: out })
+    }
+}
+
+/// The va_end node ends the use of the va_list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VaEnd {
+    pub(crate) list: Var,
+}
+
+impl VaEnd {
+    /// Creates a new instance
+    #[allow(dead_code)]
+    pub(crate) fn new(list: Var) -> Box<Self> {
+        Box::from(Self { list: list })
+    }
+}
+
+/// The inline assembly node: the assembly string is assembled by the assembler of the target
+/// (the inputs are moved into the registers they are bound to, the output is read out of its register
+/// and the variables in the clobbered registers are saved around it)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InlineAsm {
+    pub(crate) asm: String,
+    pub(crate) ins: Vec<(String, Var)>,
+    pub(crate) out: Option<(String, Var)>,
+    pub(crate) clobbers: Vec<String>,
+}
+
+impl InlineAsm {
+    /// Creates a new instance
+    #[allow(dead_code)]
+    pub(crate) fn new(asm: String, ins: Vec<(String, Var)>, out: Option<(String, Var)>, clobbers: Vec<String>) -> Box<Self> {
+        Box::from(Self { asm: asm, ins: ins, out: out, clobbers: clobbers })
+    }
+}
+
 use crate::Support::{ColorClass, ColorProfile};
 
+/// The operation of an ir node (independent of the kinds of its operands)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Opcode {
+    /// the ret node
+    Ret,
+    /// the unreachable node
+    Unreachable,
+    /// the br node
+    Br,
+    /// the br cond node
+    BrCond,
+    /// the switch node
+    Switch,
+    /// the assign node
+    Assign,
+    /// the call node
+    Call,
+    /// the call (indirect) node
+    CallIndirect,
+    /// the tail call node
+    TailCall,
+    /// the cast node
+    Cast,
+    /// the add node
+    Add,
+    /// the sub node
+    Sub,
+    /// the xor node
+    Xor,
+    /// the or node
+    Or,
+    /// the and node
+    And,
+    /// the mul node
+    Mul,
+    /// the div node
+    Div,
+    /// the rem node
+    Rem,
+    /// the shl node
+    Shl,
+    /// the lshr node
+    Lshr,
+    /// the ashr node
+    Ashr,
+    /// the neg node
+    Neg,
+    /// the not node
+    Not,
+    /// the bswap node
+    Bswap,
+    /// the ctpop node
+    Ctpop,
+    /// the ctlz node
+    Ctlz,
+    /// the cttz node
+    Cttz,
+    /// the alloca node
+    Alloca,
+    /// the load node
+    Load,
+    /// the store node
+    Store,
+    /// the cmp node
+    Cmp,
+    /// the phi node
+    Phi,
+    /// the select node
+    Select,
+    /// the gep node
+    Gep,
+    /// the memcpy node
+    MemCpy,
+    /// the memset node
+    MemSet,
+    /// the atomic load node
+    AtomicLoad,
+    /// the atomic store node
+    AtomicStore,
+    /// the atomic read-modify-write node
+    AtomicRmw,
+    /// the atomic compare exchange node
+    AtomicCmpXchg,
+    /// the fence node
+    Fence,
+    /// the overflow checked arithmetic node (with its operation)
+    WithOverflow(OverflowOp),
+    /// the va_start node
+    VaStart,
+    /// the va_arg node
+    VaArg,
+    /// the va_end node
+    VaEnd,
+    /// the inline assembly node
+    InlineAsm,
+}
+
+impl Opcode {
+    /// Returns if the operation is only supported for intengers (and bools)
+    pub fn int_only(&self) -> bool {
+        use Opcode::*;
+        matches!(self, Xor | Or | And | Rem | Shl | Lshr | Ashr | Not | Bswap | Ctpop | Ctlz | Cttz)
+    }
+}
+
+/// A value which is read by an ir node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand<'a> {
+    /// a variable
+    Var(&'a Var),
+    /// a constant
+    Const(&'a Type),
+}
+
+impl<'a> Operand<'a> {
+    /// Returns the variable if the operand is one
+    pub fn var(&self) -> Option<&'a Var> {
+        match self {
+            Operand::Var(var) => Some(var),
+            Operand::Const(_) => None,
+        }
+    }
+
+    /// Returns the constant if the operand is one
+    pub fn constant(&self) -> Option<Type> {
+        match self {
+            Operand::Var(_) => None,
+            Operand::Const(constant) => Some(**constant),
+        }
+    }
+}
+
+/// A value which is read by an ir node and which can be rewritten in place
+#[derive(Debug, PartialEq, Eq)]
+pub enum OperandMut<'a> {
+    /// a variable
+    Var(&'a mut Var),
+    /// a constant
+    Const(&'a mut Type),
+}
 
 /// The ir trait
-pub(crate) trait Ir: Debug + Any {
+pub(crate) trait Ir: Debug + Any + IrEq {
     /// Returns the ir node as his textual representation
     fn dump(&self) -> String;
     /// Returns the ir node as his textual representation with colors
@@ -157,13 +715,62 @@ pub(crate) trait Ir: Debug + Any {
     /// Compiles the node based on the given target
     fn compile(&self, registry: &mut TargetBackendDescr);
 
-    /// Returns if the node uses the variable
-    fn uses(&self, _: &Var) -> bool {
-        false
+    /// Returns if the node uses the variable (as an operand or as its result)
+    fn uses(&self, var: &Var) -> bool {
+        let used = self.operands().iter().any(|operand| operand.var().is_some_and(|operand| operand.name == var.name));
+
+        used || self.results().iter().any(|result| result.name == var.name)
     }
 
+    /// Returns the operation of the node
+    fn opcode(&self) -> Opcode;
+
+    /// Returns the values which are read by the node (in the order they are written in the ir)
+    fn operands(&self) -> Vec<Operand<'_>>;
+
+    /// Returns the values which are read by the node, so they can be rewritten
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>>;
+
+    /// Returns the variable which the node writes its result to
+    fn result(&self) -> Option<&Var> {
+        None
+    }
+
+    /// Returns all variables which are written by the node (the result and e.g. the overflow flag)
+    fn results(&self) -> Vec<&Var> {
+        self.result().into_iter().collect()
+    }
+
+    /// Returns the blocks which the node can branch to
+    fn successors(&self) -> Vec<&Block> {
+        vec![]
+    }
+
+    /// Returns the blocks which the node can branch to, so they can be rewritten (in the same order as `successors`)
+    fn successors_mut(&mut self) -> Vec<&mut Block> {
+        vec![]
+    }
+
+    /// Returns the function which the node calls directly
+    fn callee(&self) -> Option<&Function> {
+        None
+    }
+
+    /// Returns if the node is the same as the other node
     fn is(&self, other: &Box<dyn Ir>) -> bool {
-        other.dump() == self.dump()
+        self.eq_node(other.as_any())
+    }
+}
+
+/// Compares an ir node with another one (implemented for every node which implements `PartialEq`)
+pub(crate) trait IrEq {
+    /// Returns if the other node has the same type and is equal to the node
+    fn eq_node(&self, other: &dyn Any) -> bool;
+}
+
+impl<T: PartialEq + Any> IrEq for T {
+    fn eq_node(&self, other: &dyn Any) -> bool {
+        other.downcast_ref::<T>().is_some_and(|other| other == self)
     }
 }
 
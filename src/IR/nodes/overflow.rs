@@ -0,0 +1,116 @@
+use std::fmt::Display;
+
+use super::*;
+
+/// The arithmetic operation of an overflow checked arithmetic node
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OverflowOp {
+    /// ls + rs
+    Add,
+    /// ls - rs
+    Sub,
+    /// ls * rs
+    Mul,
+}
+
+impl Display for OverflowOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            OverflowOp::Add => "add",
+            OverflowOp::Sub => "sub",
+            OverflowOp::Mul => "mul",
+        })
+    }
+}
+
+impl Ir for WithOverflow {
+    fn dump(&self) -> String {
+        format!("{}, {} = {} overflow {} {}, {}", self.out.name, self.overflow.name, self.op, self.out.ty, self.ls.name, self.rs.name)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{}, {} = {} {} {} {}, {}",
+            profile.markup(&self.out.name, ColorClass::Var),
+            profile.markup(&self.overflow.name, ColorClass::Var),
+            profile.markup(&self.op.to_string(), ColorClass::Instr),
+            profile.markup("overflow", ColorClass::Instr),
+            profile.markup(&self.out.ty.to_string(), ColorClass::Ty),
+            profile.markup(&self.ls.name, ColorClass::Var),
+            profile.markup(&self.rs.name, ColorClass::Var),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        if self.ls.ty != self.rs.ty {
+            Err(VerifyError::Op0Op1TyNoMatch(self.ls.ty, self.rs.ty))?
+        }
+        if self.out.ty != self.ls.ty {
+            Err(VerifyError::Op0Op1TyNoMatch(self.out.ty, self.ls.ty))?
+        }
+        if self.overflow.ty != TypeMetadata::bool {
+            Err(VerifyError::Op0Op1TyNoMatch(self.overflow.ty, TypeMetadata::bool))?
+        }
+
+        if self.out.ty.float() || matches!(self.out.ty, TypeMetadata::bool | TypeMetadata::Void) {
+            Err(VerifyError::InvalidOverflowType(self.out.ty))?
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new(self.clone())
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr) {
+        registry.compile_with_overflow(self)
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        *var == self.ls || *var == self.rs || *var == self.out || *var == self.overflow
+    }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::WithOverflow(self.op)
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![Operand::Var(&self.ls), Operand::Var(&self.rs)]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![OperandMut::Var(&mut self.ls), OperandMut::Var(&mut self.rs)]
+    }
+
+    fn result(&self) -> Option<&Var> {
+        Some(&self.out)
+    }
+
+    fn results(&self) -> Vec<&Var> {
+        vec![&self.out, &self.overflow]
+    }
+}
+
+/// Trait for the overflow checked arithmetic instruction
+/// Used for overloading the BuildWithOverflow function
+pub trait BuildWithOverflow {
+    /// computes the operation and returns the result and a bool which is true if it overflowed
+    fn BuildWithOverflow(&mut self, op: OverflowOp, ls: Var, rs: Var) -> (Var, Var);
+}
+
+impl BuildWithOverflow for IRBuilder<'_> {
+    fn BuildWithOverflow(&mut self, op: OverflowOp, ls: Var, rs: Var) -> (Var, Var) {
+        let block = self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+        let out = Var::new(block, ls.ty);
+        let overflow = Var::new(block, TypeMetadata::bool);
+
+        block.push_ir(WithOverflow::new(op, ls, rs, out.clone(), overflow.clone()));
+
+        (out, overflow)
+    }
+}
@@ -0,0 +1,108 @@
+use super::*;
+
+impl Ir for Phi {
+    fn dump(&self) -> String {
+        let mut fmt = String::new();
+
+        for (block, var) in &self.recive_from_blocks {
+            if !fmt.is_empty() {
+                fmt.push_str(", ");
+            }
+
+            fmt.push_str(&format!("[{}, {}]", var.name, block.name));
+        }
+
+        format!("{} = phi {} {}", self.out.name, self.out.ty, fmt)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        let mut fmt = String::new();
+
+        for (block, var) in &self.recive_from_blocks {
+            if !fmt.is_empty() {
+                fmt.push_str(", ");
+            }
+
+            fmt.push_str(&format!("[{}, {}]", 
+                profile.markup(&var.name, ColorClass::Var), 
+                profile.markup(&block.name, ColorClass::Name),
+            ));
+        }
+
+        format!("{} = {} {} {}", 
+            profile.markup(&self.out.name, ColorClass::Var), 
+            profile.markup("phi", ColorClass::Instr),
+            profile.markup(&self.out.ty.to_string(), ColorClass::Ty),
+            fmt,
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        for (_, var) in &self.recive_from_blocks {
+            if var.ty != self.out.ty {
+                Err(VerifyError::Op0Op1TyNoMatch(self.out.ty, var.ty))?
+            }
+        }
+
+        Ok(())
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        *var == self.out || self.recive_from_blocks.iter().any(|(_, recive)| recive == var)
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::from( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr) {
+        registry.compile_phi(self)
+    }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::Phi
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        self.recive_from_blocks.iter().map(|(_, var)| var).map(Operand::Var).collect()
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        self.recive_from_blocks.iter_mut().map(|(_, var)| var).map(OperandMut::Var).collect()
+    }
+
+    fn result(&self) -> Option<&Var> {
+        Some(&self.out)
+    }
+}
+
+/// Trait for the phi instruction
+/// Used for overloading the BuildPhi function
+pub trait BuildPhi<T> {
+    /// builds a phi node which selects the variable of the block from which the current block was entered
+    fn BuildPhi(&mut self, typ: T, recive_from_blocks: Vec<(&Block, Var)>) -> Var;
+}
+
+impl BuildPhi<TypeMetadata> for IRBuilder<'_> {
+    fn BuildPhi(&mut self, typ: TypeMetadata, recive_from_blocks: Vec<(&Block, Var)>) -> Var {
+        let block = self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one");
+        
+        let out = Var::new(block, typ);
+
+        let recive_from_blocks = recive_from_blocks.into_iter().map(|(from, var)| {
+            (Block { // creating a new one in order to safe some memory space
+                name: from.name.to_owned(),
+                nodes: vec![],
+                varCount: 0,
+            }, var)
+        }).collect();
+
+        block.push_ir(Phi::new(out.to_owned(), recive_from_blocks));
+
+        out
+    }
+}
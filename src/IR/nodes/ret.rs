@@ -7,7 +7,7 @@ impl Ir for Return<Type> {
 
     fn dump(&self) -> String {
         let metadata: TypeMetadata = self.inner1.into();
-        format!("ret {} {}", metadata, self.inner1.val())
+        format!("ret {} {}", metadata, self.inner1.fmt_val())
     }
 
     fn dumpColored(&self, profile: ColorProfile) -> String {
@@ -15,7 +15,7 @@ impl Ir for Return<Type> {
         format!("{} {} {}", 
             profile.markup("ret", ColorClass::Instr),
             profile.markup(&metadata.to_string(), ColorClass::Ty), 
-            profile.markup(&self.inner1.val().to_string(), ColorClass::Var),
+            profile.markup(&self.inner1.fmt_val(), ColorClass::Var),
         )
     }
 
@@ -36,6 +36,18 @@ impl Ir for Return<Type> {
     fn compile(&self, registry: &mut TargetBackendDescr) {
         registry.compile_ret_ty(&self)
     }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::Ret
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![Operand::Const(&self.inner1)]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![OperandMut::Const(&mut self.inner1)]
+    }
 }
 
 impl Ir for Return<Var> {
@@ -77,6 +89,56 @@ impl Ir for Return<Var> {
         if *var == self.inner1 { true }
         else { false }
     }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::Ret
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![Operand::Var(&self.inner1)]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![OperandMut::Var(&mut self.inner1)]
+    }
+}
+
+impl Ir for Unreachable {
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new(self.clone())
+    }
+
+    fn dump(&self) -> String {
+        "unreachable".to_string()
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        profile.markup("unreachable", ColorClass::Instr)
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr) {
+        registry.compile_unreachable(self)
+    }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::Unreachable
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![]
+    }
 }
 
 /// Trait for the return instruction
@@ -98,4 +160,17 @@ impl BuildReturn<Var> for IRBuilder<'_> {
         self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one")
             .push_ir(Return::new(var))
     }
-}
\ No newline at end of file
+}
+
+/// Trait for the unreachable instruction
+pub trait BuildUnreachable {
+    /// Marks the end of the current block as never reached
+    fn BuildUnreachable(&mut self);
+}
+
+impl BuildUnreachable for IRBuilder<'_> {
+    fn BuildUnreachable(&mut self) {
+        self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one")
+            .push_ir(Unreachable::new())
+    }
+}
@@ -0,0 +1,178 @@
+use super::*;
+
+/// A value which can be selected by the select node (either a variable or a constant)
+pub trait SelectValue: Debug + Clone + 'static {
+    /// Returns the type of the value
+    fn ty(&self) -> TypeMetadata;
+
+    /// Returns the value like it is written in the ir
+    fn fmt_value(&self) -> String;
+
+    /// Returns the variable if the value is one
+    fn var(&self) -> Option<&Var>;
+
+    /// Returns the constant if the value is one
+    fn constant(&self) -> Option<Type>;
+
+    /// Returns the value as an operand of a node
+    fn operand(&self) -> Operand<'_>;
+
+    /// Returns the value as a mutable operand of a node
+    fn operand_mut(&mut self) -> OperandMut<'_>;
+}
+
+impl SelectValue for Var {
+    fn ty(&self) -> TypeMetadata {
+        self.ty
+    }
+
+    fn fmt_value(&self) -> String {
+        self.name.to_owned()
+    }
+
+    fn var(&self) -> Option<&Var> {
+        Some(self)
+    }
+
+    fn constant(&self) -> Option<Type> {
+        None
+    }
+
+    fn operand(&self) -> Operand<'_> {
+        Operand::Var(self)
+    }
+
+    fn operand_mut(&mut self) -> OperandMut<'_> {
+        OperandMut::Var(self)
+    }
+}
+
+impl SelectValue for Type {
+    fn ty(&self) -> TypeMetadata {
+        (*self).into()
+    }
+
+    fn fmt_value(&self) -> String {
+        self.fmt_val()
+    }
+
+    fn var(&self) -> Option<&Var> {
+        None
+    }
+
+    fn constant(&self) -> Option<Type> {
+        Some(*self)
+    }
+
+    fn operand(&self) -> Operand<'_> {
+        Operand::Const(self)
+    }
+
+    fn operand_mut(&mut self) -> OperandMut<'_> {
+        OperandMut::Const(self)
+    }
+}
+
+macro_rules! SelectIrNode {
+    ($yes:ty, $no:ty, $compileFunc:ident) => {
+        impl Ir for Select<$yes, $no> {
+            fn dump(&self) -> String {
+                format!("{} = select {} {}, {}, {}", self.out.name, self.out.ty, self.cond.name, self.yes.fmt_value(), self.no.fmt_value())
+            }
+
+            fn dumpColored(&self, profile: ColorProfile) -> String {
+                format!("{} = {} {} {}, {}, {}", 
+                    profile.markup(&self.out.name, ColorClass::Var), 
+                    profile.markup("select", ColorClass::Instr), 
+                    profile.markup(&self.out.ty.to_string(), ColorClass::Ty), 
+                    profile.markup(&self.cond.name, ColorClass::Var), 
+                    profile.markup(&self.yes.fmt_value(), if self.yes.var().is_some() { ColorClass::Var } else { ColorClass::Value }), 
+                    profile.markup(&self.no.fmt_value(), if self.no.var().is_some() { ColorClass::Var } else { ColorClass::Value }), 
+                )
+            }
+
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+
+            fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+                if self.cond.ty != TypeMetadata::bool {
+                    Err(VerifyError::Op0Op1TyNoMatch(self.cond.ty, TypeMetadata::bool))?
+                }
+
+                if self.yes.ty() != self.no.ty() {
+                    Err(VerifyError::Op0Op1TyNoMatch(self.yes.ty(), self.no.ty()))?
+                }
+
+                if self.yes.ty() != self.out.ty {
+                    Err(VerifyError::Op0Op1TyNoMatch(self.yes.ty(), self.out.ty))?
+                }
+
+                Ok(())
+            }
+
+            fn clone_box(&self) -> Box<dyn Ir> {
+                Box::new(self.clone())
+            }
+
+            fn compile(&self, registry: &mut TargetBackendDescr) {
+                registry.$compileFunc(&self)
+            }
+
+            fn uses(&self, var: &Var) -> bool {
+                if *var == self.cond || *var == self.out { true }
+                else if self.yes.var() == Some(var) { true }
+                else if self.no.var() == Some(var) { true }
+                else { false }
+            }
+
+            fn opcode(&self) -> Opcode {
+                Opcode::Select
+            }
+
+            fn operands(&self) -> Vec<Operand<'_>> {
+                vec![Operand::Var(&self.cond), self.yes.operand(), self.no.operand()]
+            }
+
+            fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+                vec![OperandMut::Var(&mut self.cond), self.yes.operand_mut(), self.no.operand_mut()]
+            }
+
+            fn result(&self) -> Option<&Var> {
+                Some(&self.out)
+            }
+        }
+    };
+}
+
+SelectIrNode!(Var, Var, compile_select_var_var);
+SelectIrNode!(Var, Type, compile_select_var_type);
+SelectIrNode!(Type, Var, compile_select_type_var);
+SelectIrNode!(Type, Type, compile_select_type_type);
+
+/// The trait `BuildSelect` is used to build the select node
+pub trait BuildSelect<T, U> {
+    /// builds a select node which returns `yes` if the condition is true, else `no`
+    fn BuildSelect(&mut self, cond: Var, yes: T, no: U) -> Var;
+}
+
+macro_rules! BuildSelectImpl {
+    ($yes:ty, $no:ty) => {
+        impl BuildSelect<$yes, $no> for IRBuilder<'_> {
+            fn BuildSelect(&mut self, cond: Var, yes: $yes, no: $no) -> Var {
+                let block = self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one");
+                
+                let out = Var::new(block, yes.ty());
+
+                block.push_ir( Select::new(cond, yes, no, out.to_owned()) );
+
+                out
+            }
+        }
+    };
+}
+
+BuildSelectImpl!(Var, Var);
+BuildSelectImpl!(Var, Type);
+BuildSelectImpl!(Type, Var);
+BuildSelectImpl!(Type, Type);
@@ -0,0 +1,126 @@
+use super::*;
+
+impl Ir for Store<Var, Var> {
+    fn dump(&self) -> String {
+        format!("store {} {}, {}", self.inner2.ty, self.inner2.name, self.inner1.name)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} {} {}, {}", 
+            profile.markup("store", ColorClass::Instr),
+            profile.markup(&self.inner2.ty.to_string(), ColorClass::Ty),
+            profile.markup(&self.inner2.name, ColorClass::Var), 
+            profile.markup(&self.inner1.name, ColorClass::Var), 
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        if self.inner1.ty != TypeMetadata::ptr {
+            Err(VerifyError::Op0Op1TyNoMatch(self.inner1.ty, TypeMetadata::ptr))?
+        }
+        Ok(())
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        *var == self.inner1 || *var == self.inner2
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::from( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr) {
+        registry.compile_store_var(self)
+    }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::Store
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![Operand::Var(&self.inner2), Operand::Var(&self.inner1)]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![OperandMut::Var(&mut self.inner2), OperandMut::Var(&mut self.inner1)]
+    }
+}
+
+impl Ir for Store<Var, Type> {
+    fn dump(&self) -> String {
+        let metadata: TypeMetadata = self.inner2.into();
+        format!("store {} {}, {}", metadata, self.inner2.fmt_val(), self.inner1.name)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        let metadata: TypeMetadata = self.inner2.into();
+        format!("{} {} {}, {}", 
+            profile.markup("store", ColorClass::Instr),
+            profile.markup(&metadata.to_string(), ColorClass::Ty),
+            profile.markup(&self.inner2.fmt_val(), ColorClass::Value), 
+            profile.markup(&self.inner1.name, ColorClass::Var), 
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        if self.inner1.ty != TypeMetadata::ptr {
+            Err(VerifyError::Op0Op1TyNoMatch(self.inner1.ty, TypeMetadata::ptr))?
+        }
+        Ok(())
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        *var == self.inner1
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::from( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr) {
+        registry.compile_store_ty(self)
+    }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::Store
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![Operand::Const(&self.inner2), Operand::Var(&self.inner1)]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![OperandMut::Const(&mut self.inner2), OperandMut::Var(&mut self.inner1)]
+    }
+}
+
+/// Trait for the store instruction
+/// Used for overloading the BuildStore function
+pub trait BuildStore<T, U> {
+    /// stores the value into the memory the pointer points to
+    fn BuildStore(&mut self, ptr: T, value: U);
+}
+
+impl BuildStore<Var, Var> for IRBuilder<'_> {
+    fn BuildStore(&mut self, ptr: Var, value: Var) {
+        let block = self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+        block.push_ir(Store::new(ptr, value));
+    }
+}
+
+impl BuildStore<Var, Type> for IRBuilder<'_> {
+    fn BuildStore(&mut self, ptr: Var, value: Type) {
+        let block = self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+        block.push_ir(Store::new(ptr, value));
+    }
+}
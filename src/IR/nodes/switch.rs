@@ -0,0 +1,119 @@
+use super::*;
+
+impl Ir for Switch {
+    fn dump(&self) -> String {
+        let mut fmt = String::new();
+
+        for (value, block) in &self.cases {
+            fmt.push_str(&format!(", [{}, {}]", value.fmt_val(), block.name));
+        }
+
+        format!("switch {} {}, {}{}", self.var.ty, self.var.name, self.default.name, fmt)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        let mut fmt = String::new();
+
+        for (value, block) in &self.cases {
+            fmt.push_str(&format!(", [{}, {}]", 
+                profile.markup(&value.fmt_val(), ColorClass::Value), 
+                profile.markup(&block.name, ColorClass::Name),
+            ));
+        }
+
+        format!("{} {} {}, {}{}", 
+            profile.markup("switch", ColorClass::Instr),
+            profile.markup(&self.var.ty.to_string(), ColorClass::Ty),
+            profile.markup(&self.var.name, ColorClass::Var), 
+            profile.markup(&self.default.name, ColorClass::Name),
+            fmt,
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        for (value, _) in &self.cases {
+            let ty: TypeMetadata = (*value).into();
+
+            if ty != self.var.ty {
+                Err(VerifyError::Op0Op1TyNoMatch(self.var.ty, ty))?
+            }
+        }
+
+        for (index, (value, _)) in self.cases.iter().enumerate() {
+            if self.cases[..index].iter().any(|(other, _)| other.val() == value.val()) {
+                Err(VerifyError::SwitchCaseTwice(*value))?
+            }
+        }
+
+        Ok(())
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        *var == self.var
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::from( self.clone() )
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr) {
+        registry.compile_switch(self)
+    }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::Switch
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![Operand::Var(&self.var)]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![OperandMut::Var(&mut self.var)]
+    }
+
+    fn successors(&self) -> Vec<&Block> {
+        let mut successors = vec![&self.default];
+        successors.extend(self.cases.iter().map(|(_, block)| block));
+
+        successors
+    }
+
+    fn successors_mut(&mut self) -> Vec<&mut Block> {
+        let mut successors = vec![&mut self.default];
+        successors.extend(self.cases.iter_mut().map(|(_, block)| block));
+
+        successors
+    }
+}
+
+/// Trait for the switch instruction
+/// Used for overloading the BuildSwitch function
+pub trait BuildSwitch<T, U> {
+    /// builds a switch node which jumps to the block of the matching case or to the default block
+    fn BuildSwitch(&mut self, var: T, default: U, cases: Vec<(Type, U)>);
+}
+
+impl BuildSwitch<Var, &Block> for IRBuilder<'_> {
+    fn BuildSwitch(&mut self, var: Var, default: &Block, cases: Vec<(Type, &Block)>) {
+        let block = self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+        let cases = cases.into_iter().map(|(value, to)| {
+            (value, Block { // creating a new one in order to safe some memory space
+                name: to.name.to_owned(),
+                nodes: vec![],
+                varCount: 0,
+            })
+        }).collect();
+
+        block.push_ir(Switch::new(var, Block {
+            name: default.name.to_owned(),
+            nodes: vec![],
+            varCount: 0,
+        }, cases));
+    }
+}
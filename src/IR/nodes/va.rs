@@ -0,0 +1,210 @@
+use super::*;
+
+impl Ir for VaStart {
+    fn dump(&self) -> String {
+        format!("va_start {}", self.list.name)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} {}",
+            profile.markup("va_start", ColorClass::Instr),
+            profile.markup(&self.list.name, ColorClass::Var),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, FuncTy: FunctionType) -> Result<(), VerifyError> {
+        if self.list.ty != TypeMetadata::ptr {
+            Err(VerifyError::Op0Op1TyNoMatch(self.list.ty, TypeMetadata::ptr))?
+        }
+
+        if !FuncTy.any_args {
+            Err(VerifyError::VaStartInNonVariadicFunc)?
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new(self.clone())
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr) {
+        registry.compile_va_start(self)
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        *var == self.list
+    }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::VaStart
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![Operand::Var(&self.list)]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![OperandMut::Var(&mut self.list)]
+    }
+}
+
+impl Ir for VaArg {
+    fn dump(&self) -> String {
+        format!("{} = va_arg {} {}", self.out.name, self.out.ty, self.list.name)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} = {} {} {}",
+            profile.markup(&self.out.name, ColorClass::Var),
+            profile.markup("va_arg", ColorClass::Instr),
+            profile.markup(&self.out.ty.to_string(), ColorClass::Ty),
+            profile.markup(&self.list.name, ColorClass::Var),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        if self.list.ty != TypeMetadata::ptr {
+            Err(VerifyError::Op0Op1TyNoMatch(self.list.ty, TypeMetadata::ptr))?
+        }
+
+        if self.out.ty == TypeMetadata::Void {
+            Err(VerifyError::InvalidVaArgType(self.out.ty))?
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new(self.clone())
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr) {
+        registry.compile_va_arg(self)
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        *var == self.list || *var == self.out
+    }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::VaArg
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![Operand::Var(&self.list)]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![OperandMut::Var(&mut self.list)]
+    }
+
+    fn result(&self) -> Option<&Var> {
+        Some(&self.out)
+    }
+}
+
+impl Ir for VaEnd {
+    fn dump(&self) -> String {
+        format!("va_end {}", self.list.name)
+    }
+
+    fn dumpColored(&self, profile: ColorProfile) -> String {
+        format!("{} {}",
+            profile.markup("va_end", ColorClass::Instr),
+            profile.markup(&self.list.name, ColorClass::Var),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn verify(&self, _: FunctionType) -> Result<(), VerifyError> {
+        if self.list.ty != TypeMetadata::ptr {
+            Err(VerifyError::Op0Op1TyNoMatch(self.list.ty, TypeMetadata::ptr))?
+        }
+
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Ir> {
+        Box::new(self.clone())
+    }
+
+    fn compile(&self, registry: &mut TargetBackendDescr) {
+        registry.compile_va_end(self)
+    }
+
+    fn uses(&self, var: &Var) -> bool {
+        *var == self.list
+    }
+
+    fn opcode(&self) -> Opcode {
+        Opcode::VaEnd
+    }
+
+    fn operands(&self) -> Vec<Operand<'_>> {
+        vec![Operand::Var(&self.list)]
+    }
+
+    fn operands_mut(&mut self) -> Vec<OperandMut<'_>> {
+        vec![OperandMut::Var(&mut self.list)]
+    }
+}
+
+/// Trait for the va_start instruction
+/// Used for overloading the BuildVaStart function
+pub trait BuildVaStart {
+    /// initializes the va_list the pointer points to
+    /// (it needs 24 bytes on x64 System V and 8 bytes on Windows x64)
+    fn BuildVaStart(&mut self, list: Var);
+}
+
+impl BuildVaStart for IRBuilder<'_> {
+    fn BuildVaStart(&mut self, list: Var) {
+        self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one")
+            .push_ir(VaStart::new(list))
+    }
+}
+
+/// Trait for the va_arg instruction
+/// Used for overloading the BuildVaArg function
+pub trait BuildVaArg {
+    /// reads the next variadic argument of the va_list as the given type
+    fn BuildVaArg(&mut self, list: Var, ty: TypeMetadata) -> Var;
+}
+
+impl BuildVaArg for IRBuilder<'_> {
+    fn BuildVaArg(&mut self, list: Var, ty: TypeMetadata) -> Var {
+        let block = self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one");
+
+        let out = Var::new(block, ty);
+
+        block.push_ir(VaArg::new(list, out.clone()));
+
+        out
+    }
+}
+
+/// Trait for the va_end instruction
+/// Used for overloading the BuildVaEnd function
+pub trait BuildVaEnd {
+    /// ends the use of the va_list
+    fn BuildVaEnd(&mut self, list: Var);
+}
+
+impl BuildVaEnd for IRBuilder<'_> {
+    fn BuildVaEnd(&mut self, list: Var) {
+        self.blocks.get_mut(self.curr).expect("the IRBuilder needs to have an current block\nConsider creating one")
+            .push_ir(VaEnd::new(list))
+    }
+}
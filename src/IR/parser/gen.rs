@@ -1,6 +1,6 @@
-use std::collections::{BTreeMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 
-use crate::{Obj::Linkage, IR::{Block, Const, Function, FunctionType, Module, TypeMetadata}};
+use crate::{prelude::{Assign, Call, TailCall}, Obj::{Linkage, TlsModel, Visibility}, Target::CallConv, IR::{AggregateType, Block, Const, Function, FunctionAttr, FunctionType, Global, Module, SymbolRef, TypeMetadata, Var}};
 
 use super::parser::{IrBlock, IrInstr, IrStmt};
 
@@ -12,6 +12,8 @@ pub struct IrGen {
 
     funcs: Vec<Function>,
     consts: Vec<Const>,
+    globals: Vec<Global>,
+    types: Vec<(String, AggregateType)>,
 }
 
 impl IrGen {
@@ -22,21 +24,33 @@ impl IrGen {
 
             funcs: vec![],
             consts: vec![],
+            globals: vec![],
+            types: vec![],
         }
     }
 
-    fn gen_func(&mut self, name: String, ret: TypeMetadata, args:  (BTreeMap<String, TypeMetadata>, bool), body: BTreeMap<String, IrBlock>, scope: Linkage) {
+    #[allow(clippy::too_many_arguments)]
+    fn gen_func(&mut self, name: String, ret: TypeMetadata, args:  (BTreeMap<String, TypeMetadata>, bool), body: Vec<(String, IrBlock)>, scope: Linkage, visibility: Visibility, attrs: Vec<FunctionAttr>, callconv: Option<CallConv>, section: Option<String>, align: Option<u64>) {
         let mut ty = FunctionType::new(vec![], ret);
+        ty.callconv = callconv;
 
         for (_, arg) in &args.0 {
             ty.args.push( *arg );
         }
 
+        if args.1 {
+            ty.activate_dynamic_arguments();
+        }
+
         let mut raw = Function {
             ty: ty,
             name: name,
             linkage: scope,
+            visibility: visibility,
             blocks: VecDeque::new(),
+            attrs: attrs,
+            section: section,
+            align: align,
         };
 
         for (name, block) in body {
@@ -66,22 +80,104 @@ impl IrGen {
     pub fn gen(&mut self) {
         for stmt in self.input.clone() {
             match stmt {
-                IrStmt::Func { name, ret, args, body, scope, location: _ } => self.gen_func(name, ret, args, body, scope),
-                IrStmt::Const { name, data, location: _, scope } => self.gen_const(name, data, scope),
+                IrStmt::Func { name, ret, args, body, scope, visibility, attrs, callconv, section, align, location: _ } => self.gen_func(name, ret, args, body, scope, visibility, attrs, callconv, section, align),
+                IrStmt::Const { name, data, refs, location: _, scope, visibility, thread_local, section, align } => self.gen_const(name, data, refs, scope, visibility, thread_local, section, align),
+                IrStmt::Global { name, data, refs, size, location: _, scope, visibility, thread_local, section, align } => self.gen_global(name, data, refs, size, scope, visibility, thread_local, section, align),
+                IrStmt::Type { name, ty, location: _ } => self.types.push((name, ty)),
+            }
+        }
+
+        self.gen_variadic_calls();
+        self.gen_thread_local_refs();
+    }
+
+    /// the parser only knows the name of the symbol of which the adress is loaded,
+    /// so the loads of thread local symbols are marked here (they get accessed over the thread pointer)
+    fn gen_thread_local_refs(&mut self) {
+        let thread_locals = self.consts.iter().map(|consta| (consta.name.to_owned(), consta.thread_local))
+            .chain(self.globals.iter().map(|global| (global.name.to_owned(), global.thread_local)))
+            .filter_map(|(name, model)| Some((name, model?)))
+            .collect::<HashMap<_, _>>();
+
+        for func in &mut self.funcs {
+            for block in &mut func.blocks {
+                for node in &mut block.nodes {
+                    if let Some(assign) = node.as_any().downcast_ref::<Assign<Var, Const>>() {
+                        if let Some(model) = thread_locals.get(&assign.inner2.name) {
+                            let mut assign = assign.to_owned();
+                            assign.inner2.thread_local = Some(*model);
+                            *node = Box::new(assign);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// the parser only knows the arguments of a call, so calls to variadic functions
+    /// are marked as such here (some calling conventions pass the variadic arguments differently)
+    fn gen_variadic_calls(&mut self) {
+        let variadic = self.funcs.iter()
+            .filter(|func| func.ty.any_args)
+            .map(|func| func.name.to_owned())
+            .collect::<Vec<_>>();
+
+        for func in &mut self.funcs {
+            for block in &mut func.blocks {
+                for node in &mut block.nodes {
+                    if let Some(call) = node.as_any().downcast_ref::<Call<Function, Vec<Var>, Var>>() {
+                        if variadic.contains(&call.inner1.name) {
+                            let mut call = call.to_owned();
+                            call.inner1.ty.activate_dynamic_arguments();
+                            *node = Box::new(call);
+                        }
+                    } else if let Some(call) = node.as_any().downcast_ref::<TailCall<Function, Vec<Var>>>() {
+                        if variadic.contains(&call.inner1.name) {
+                            let mut call = call.to_owned();
+                            call.inner1.ty.activate_dynamic_arguments();
+                            *node = Box::new(call);
+                        }
+                    }
+                }
             }
         }
     }
 
-    fn gen_const(&mut self, name: String, data: Vec<u8>, scope: Linkage) {
+    #[allow(clippy::too_many_arguments)]
+    fn gen_const(&mut self, name: String, data: Vec<u8>, refs: Vec<SymbolRef>, scope: Linkage, visibility: Visibility, thread_local: Option<TlsModel>, section: Option<String>, align: Option<u64>) {
         let raw = Const {
             name: name,
             data: data,
             linkage: scope,
+            visibility: visibility,
+            thread_local: thread_local,
+            section: section,
+            align: align,
+            refs: refs,
         };
 
         self.consts.push( raw );
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn gen_global(&mut self, name: String, data: Option<Vec<u8>>, refs: Vec<SymbolRef>, size: usize, scope: Linkage, visibility: Visibility, thread_local: Option<TlsModel>, section: Option<String>, align: Option<u64>) {
+        let mut raw = Global::new(name);
+
+        match data {
+            Some(data) => raw.set(data),
+            None => raw.zeroed(size),
+        }
+
+        raw.linkage = scope;
+        raw.visibility = visibility;
+        raw.thread_local = thread_local;
+        raw.section = section;
+        raw.align = align;
+        raw.refs = refs;
+
+        self.globals.push( raw );
+    }
+
     /// emits the generated functions, constants, .. into a usable module
     pub fn module(&self) -> Module {
         let mut module = Module();
@@ -94,6 +190,14 @@ impl IrGen {
             module.add_raw_const( constant.to_owned() );
         }
 
+        for global in &self.globals {
+            module.add_raw_global( global.to_owned() );
+        }
+
+        for (name, ty) in &self.types {
+            module.add_type( name, ty.to_owned() );
+        }
+
         module
     }
 }
\ No newline at end of file
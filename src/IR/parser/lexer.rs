@@ -17,7 +17,7 @@ pub struct Loc {
 }
 
 /// The token type for parsing ir
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum TokenType {
     /// :
     Dot,
@@ -64,6 +64,9 @@ pub enum TokenType {
     /// 1234
     Int(i64),
 
+    /// 12.34
+    Float(f64),
+
     /// declare
     Declare,
 
@@ -78,6 +81,15 @@ pub enum TokenType {
 
     /// cond
     Cond,
+
+    /// type
+    Type,
+
+    /// global
+    Global,
+
+    /// #attr
+    Attr(String),
 }
 
 impl TokenType {
@@ -97,18 +109,22 @@ impl TokenType {
             TokenType::Ident(_) => "ident",
             TokenType::String(_) => "string",
             TokenType::Int(_) => "int",
+            TokenType::Float(_) => "float",
             TokenType::Declare => "declare",
             TokenType::Define => "define",
             TokenType::Func(_) => "func",
             TokenType::TripleDot => "...",
             TokenType::Block(_) => "block",
             TokenType::Cond => "cond",
+            TokenType::Type => "type",
+            TokenType::Global => "global",
+            TokenType::Attr(_) => "attribute",
         }.to_string()
     }
 }
 
 /// An ir token
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Token {
     /// the type
     pub typ: TokenType,
@@ -118,7 +134,7 @@ pub struct Token {
 
 /// A lexer for lexing ygen ir strings
 #[allow(unused)]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct IrLexer {
     input_stream: String,
 
@@ -149,6 +165,8 @@ impl IrLexer {
         keys.insert("declare".into(), TokenType::Declare);
         keys.insert("define".into(), TokenType::Define);
         keys.insert("const".into(), TokenType::Const);
+        keys.insert("type".into(), TokenType::Type);
+        keys.insert("global".into(), TokenType::Global);
         keys.insert("cond".into(), TokenType::Cond);
 
         
@@ -303,10 +321,12 @@ impl IrLexer {
 
             'a'..='z' | 'A'..='Z' | '_' => ty = Some(self.scan_ident()?),
 
-            '0'..='9' => ty = Some(self.scan_num()?),
+            '0'..='9' | '-' | '+' => ty = Some(self.scan_num()?),
 
             '@' => ty = Some(self.scan_func()?),
 
+            '#' => ty = Some(self.scan_attr()?),
+
             any => Err(IrError::UnexpectedCharacter { 
                 chr: any, 
                 loc: self.loc.clone() 
@@ -362,6 +382,42 @@ impl IrLexer {
         Ok(TokenType::Var(out))
     }
 
+    fn scan_attr(&mut self) -> Result<TokenType, IrError> {
+        let mut out = String::new();
+
+        self.advance()?; // #
+
+        let mut looping = true;
+
+        while looping {
+            if self.is_at_end() {
+                Err(IrError::UndeterminedTokenSequence { 
+                    loc: self.loc.clone(), 
+                    expected: String::from(r#"'a'..'z', 'A'..'Z', '0'..'9' or '_' for attributes"#) 
+                })?
+            }
+
+            let chr = self.peek().unwrap();
+
+            match chr {
+                '0'..='9' => out.push(chr),
+                'a'..='z' => out.push(chr),
+                'A'..='Z' => out.push(chr),
+                '_' => out.push(chr),
+
+                _ => looping = false,
+            }
+
+            if looping {
+                self.advance()?;
+            }
+        }
+
+        self.no_pop = true;
+
+        Ok(TokenType::Attr(out))
+    }
+
     fn scan_string(&mut self) -> Result<TokenType, IrError> {
         let mut out = String::new();
 
@@ -453,6 +509,7 @@ impl IrLexer {
         let mut string = String::new();
 
         let mut looping = true;
+        let mut float = false;
 
         while looping {
             if self.is_at_end() {
@@ -468,6 +525,13 @@ impl IrLexer {
                 '0'..='9' => string.push(chr),
                 'x' => string.push('x'),
                 'b' => string.push('b'),
+                '-' if string.is_empty() || string.ends_with('e') => string.push('-'),
+                '+' if string.is_empty() => {}, // e.g: the addend of a symbol address
+                '.' if !float => {
+                    float = true;
+                    string.push('.');
+                },
+                'e' | 'E' if float => string.push('e'),
 
                 _ => looping = false,
             }
@@ -477,6 +541,15 @@ impl IrLexer {
             }
         }
 
+        self.no_pop = true;
+
+        if float {
+            return match string.parse::<f64>() {
+                Ok(f) => Ok(TokenType::Float(f)),
+                Err(err) => Err(IrError::Boxed{ err: Box::from(err), loc: self.loc.clone() }),
+            };
+        }
+
         let mut negate = false;
 
 		let mut out = match if string.starts_with("0x") {
@@ -497,8 +570,6 @@ impl IrLexer {
             out = -out;
         }
 
-        self.no_pop = true;
-
         Ok(TokenType::Int(out))
     }
 
@@ -7,6 +7,8 @@ use lexer::Loc;
 
 use crate::Support::{self, Colorize};
 
+use crate::Target::CallConv;
+
 use super::{Module, TypeMetadata};
 
 /// Ygen-Ir lexing
@@ -94,6 +96,16 @@ pub enum IrError {
         loc: Loc,
     },
 
+    /// the calling convention of the call is different than the one of the called function
+    FuncWrongCallConv {
+        /// the calling convention of the function (`None` is the one of the target)
+        expected: Option<CallConv>,
+        /// the calling convention of the call
+        found: Option<CallConv>,
+        /// location
+        loc: Loc,
+    },
+
     /// unkown thing
     Unkown {
         /// whats unkown (e.g: block)
@@ -131,6 +143,14 @@ pub enum IrError {
         /// expected amount
         expected: usize,
     },
+
+    /// the alignment isn't a power of two
+    InvalidAlign {
+        /// the alignment
+        align: i64,
+        /// the location
+        loc: Loc,
+    },
 }
 
 impl Display for IrError {
@@ -267,6 +287,20 @@ impl Display for IrError {
                 fab.to_string()
             }
         
+            IrError::FuncWrongCallConv { expected, found, loc } => {
+                let mut fab = Support::Error::new("wrong calling convention", "", "", "");
+
+                fab.deactivateLocationDisplay();
+
+                fab.setCodeLine(loc.line_string.to_owned());
+
+                let fmt = |conv: &Option<CallConv>| conv.map(|conv| conv.to_string()).unwrap_or("the default one".to_owned());
+
+                fab.addWhere(format!("encountered the calling convention: {} but the function uses: {}", fmt(found), fmt(expected)), loc.coloumn, loc.length);
+                
+                fab.to_string()
+            }
+        
             IrError::Unkown { what, name, loc } => {
                 let mut fab = Support::Error::new(format!("unkown {what}"), "", "", "col");
 
@@ -317,6 +351,17 @@ impl Display for IrError {
 
                 fab.to_string()
             }
+        
+            IrError::InvalidAlign { align, loc } => {
+                let mut fab = Support::Error::new("invalid alignment", "", "", "");
+
+                fab.deactivateLocationDisplay();
+
+                fab.setCodeLine(loc.line_string.to_owned());
+                fab.addWhere(format!("the alignment {align} isn't a power of two"), loc.coloumn, loc.length);
+
+                fab.to_string()
+            }
         })
     }
 }
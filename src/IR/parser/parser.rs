@@ -1,8 +1,9 @@
-use std::collections::{BTreeMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 
-use crate::prelude::{Cmp, CmpMode, Ir};
-use crate::Obj::Linkage;
-use crate::IR::{ir, Block, Const, FnTy, Function, Type, TypeMetadata, Var};
+use crate::prelude::{AtomicOrdering, AtomicRmwOp, Cmp, CmpMode, Ir, OverflowOp};
+use crate::Obj::{Linkage, TlsModel, Visibility};
+use crate::Target::CallConv;
+use crate::IR::{ir, AggregateType, Block, Const, ElementType, FnTy, Function, FunctionAttr, SymbolRef, Type, TypeMetadata, Var};
 
 use super::lexer::{Loc, Token, TokenType};
 use super::IrError;
@@ -37,8 +38,13 @@ pub enum IrStmt {
         name: String,
         ret: TypeMetadata, 
         args: (BTreeMap<String, TypeMetadata>, /*unlim args*/bool), 
-        body: BTreeMap<String, IrBlock>,
+        body: Vec<(String, IrBlock)>,
         scope: Linkage,
+        visibility: Visibility,
+        attrs: Vec<FunctionAttr>,
+        callconv: Option<CallConv>,
+        section: Option<String>,
+        align: Option<u64>,
 
         location: Loc,
     },
@@ -46,18 +52,57 @@ pub enum IrStmt {
     Const{
         name: String,
         data: Vec<u8>, 
+        /// the addresses of symbols in the data
+        refs: Vec<SymbolRef>,
         location: Loc,
         scope: Linkage,
+        visibility: Visibility,
+        thread_local: Option<TlsModel>,
+        section: Option<String>,
+        align: Option<u64>,
     },
+    /// a mutable global variable
+    Global{
+        name: String,
+        /// the initial data (`None` if it is zero initialized)
+        data: Option<Vec<u8>>,
+        /// the addresses of symbols in the initial data
+        refs: Vec<SymbolRef>,
+        size: usize,
+        location: Loc,
+        scope: Linkage,
+        visibility: Visibility,
+        thread_local: Option<TlsModel>,
+        section: Option<String>,
+        align: Option<u64>,
+    },
+    /// a struct or array type
+    Type{
+        name: String,
+        ty: AggregateType,
+        location: Loc,
+    },
+}
+
+/// A value of a select node (either a variable or a constant)
+enum SelectValue {
+    Var(Var),
+    Const(Type),
 }
 
 /// Parses ir tokens into ir statements with location data
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct IrParser {
     /// The output
     pub out: Vec<IrStmt>,
 
     input: VecDeque<Token>,
+
+    /// the types of the variables of the current function
+    var_types: HashMap<String, TypeMetadata>,
+
+    /// the struct and array types which were defined till now
+    types: HashMap<String, AggregateType>,
 }
 
 impl IrParser {
@@ -66,6 +111,8 @@ impl IrParser {
         Self {
             out: vec![],
             input: VecDeque::from(tokens),
+            var_types: HashMap::new(),
+            types: HashMap::new(),
         }
     }
 
@@ -90,6 +137,8 @@ impl IrParser {
             TokenType::Declare => self.parse_declare(),
             TokenType::Define => self.parse_define(),
             TokenType::Const => self.parse_const(),
+            TokenType::Type => self.parse_type_def(),
+            TokenType::Global => self.parse_global(),
 
             _ => Err(IrError::UnexpectedToken(tok.clone())),
         }
@@ -102,6 +151,8 @@ impl IrParser {
         self.expect( TokenType::Declare )?;
         self.input.pop_front(); // advance over declare
 
+        let callconv = self.parse_callconv();
+
         let ret = self.parse_type()?;
         self.input.pop_front();
 
@@ -161,10 +212,17 @@ impl IrParser {
         self.expect(TokenType::RParam)?;
         self.input.pop_front(); // the closing param )
 
+        let attrs = self.parse_attrs()?;
+
         Ok(IrStmt::Func { 
             name: name, 
-            body: BTreeMap::new(),
+            body: vec![],
             scope: Linkage::Extern,
+            visibility: Visibility::Default,
+            attrs: attrs,
+            callconv: callconv,
+            section: None,
+            align: None,
             args: (args, unlim),
             ret: ret,
 
@@ -174,30 +232,20 @@ impl IrParser {
 
     fn parse_define(&mut self) -> Result<IrStmt, IrError> {
         let name;
-        let mut body = BTreeMap::new();
+        let mut body = vec![];
         let mut args = BTreeMap::new();
-        
-        let mut link = Linkage::External;
 
+        self.var_types.clear();
+        
         self.expect( TokenType::Define )?;
         self.input.pop_front(); // advance over define
 
-        let ret = self.parse_type()?;
-        self.input.pop_front();
+        let (link, visibility) = self.parse_linkage();
 
-        let curr = self.current_token()?;
+        let callconv = self.parse_callconv();
 
-        if let TokenType::Ident(ident) = &curr.typ {
-            link = match ident.as_str() {
-                "local" | "internal" | "private" => Linkage::Internal,
-                "public" | "external" => Linkage::External,
-                _ => Err(IrError::Unkown { 
-                    what: "linkage".to_owned(), 
-                    name: ident.to_owned(), 
-                    loc: curr.loc.clone()
-                })?
-            };
-        }
+        let ret = self.parse_type()?;
+        self.input.pop_front();
 
         self.expect( TokenType::Func(String::new()) )?;
 
@@ -212,6 +260,8 @@ impl IrParser {
 
         self.input.pop_front();
 
+        let mut unlim = false;
+
         loop {
             let current = self.current_token()?;
 
@@ -223,6 +273,12 @@ impl IrParser {
                 self.input.pop_front();
             }
 
+            if TokenType::TripleDot == self.current_token()?.typ {
+                self.input.pop_front();
+                unlim = true;
+                break;
+            }
+
             let var_type = self.parse_type()?;
             self.input.pop_front();
 
@@ -238,11 +294,15 @@ impl IrParser {
             
             self.input.pop_front();
 
+            self.var_types.insert(var_name.to_owned(), var_type);
             args.insert(var_name, var_type );
         }
 
         self.input.pop_front(); // the closing param )
 
+        let attrs = self.parse_attrs()?;
+
+        let (section, align) = self.parse_placement()?;
         
         self.expect(TokenType::LBracket)?;
         self.input.pop_front();
@@ -256,7 +316,7 @@ impl IrParser {
 
             let (name, block) = self.parse_block()?;
 
-            body.insert( name, block );
+            body.push( (name, block) );
         }
 
         self.input.pop_front(); // }
@@ -264,8 +324,13 @@ impl IrParser {
         Ok(IrStmt::Func { 
             name: name, 
             body: body,
-            args: (args, false),
+            args: (args, unlim),
             scope: link,
+            visibility: visibility,
+            attrs: attrs,
+            callconv: callconv,
+            section: section,
+            align: align,
             ret: ret,
 
             location: loc,
@@ -277,50 +342,7 @@ impl IrParser {
 
         self.input.pop_front();
 
-        /* 
-        PARSE NAME
-        */
-
-        let mut scope = Linkage::External;
-        let mut parsed_scope = false;
-
-        let mut name = "unreachable".into();
-
-        let mut location = Loc::default();
-
-        self.expect( TokenType::Ident(String::new()) )?;
-
-        let tok = self.current_token()?;
-        if let TokenType::Ident(ident) = &tok.typ {
-            match ident.as_str() {
-                "local" | "internal" | "private" => {
-                    parsed_scope = true;
-                    scope = Linkage::Internal
-                },
-                "public" | "external" => {
-                    parsed_scope = true;
-                    scope = Linkage::External
-                },
-                _ => {
-                    name = ident.to_string();
-                    location = tok.loc.clone();
-                }
-            }
-        } else { unreachable!() }
-
-        self.input.pop_front();
-
-        if parsed_scope {
-            self.expect(TokenType::Ident(String::new()))?;
-            let tok = self.current_token()?;
-
-            if let TokenType::Ident(ident) = &tok.typ {
-                name = ident.to_owned();
-                location = tok.loc.clone();
-            } else { unreachable!() }
-
-            self.input.pop_front();
-        }
+        let (name, scope, visibility, thread_local, mut location) = self.parse_symbol_name()?;
 
         self.expect(TokenType::Equal)?;
         self.input.pop_front();
@@ -328,10 +350,11 @@ impl IrParser {
         let current = self.current_token()?;
 
         let mut data = vec![];
+        let mut refs = vec![];
 
         match &current.typ {
             TokenType::String(x) => data = x.as_bytes().to_vec(),
-            TokenType::LSquare => data = self.parse_data_array()?, 
+            TokenType::LSquare => (data, refs) = self.parse_data_array()?, 
 
             _=> Err(IrError::ExpectedTokenButFoundAnUnexpectedOne { 
                 found: current.clone(), 
@@ -350,14 +373,332 @@ impl IrParser {
 
         self.input.pop_front();
 
+        let (section, align) = self.parse_placement()?;
+
         Ok(IrStmt::Const { 
             name: name, 
             data: data,
+            refs: refs,
+            location: location,
+            scope: scope,
+            visibility: visibility,
+            thread_local: thread_local,
+            section: section,
+            align: align,
+        })
+    }
+
+    fn parse_global(&mut self) -> Result<IrStmt, IrError> {
+        self.expect(TokenType::Global)?;
+
+        self.input.pop_front();
+
+        let (name, scope, visibility, thread_local, mut location) = self.parse_symbol_name()?;
+
+        self.expect(TokenType::Equal)?;
+        self.input.pop_front();
+
+        let current = self.current_token()?;
+
+        let mut refs = vec![];
+
+        let (data, size) = match &current.typ {
+            TokenType::String(x) => (Some(x.as_bytes().to_vec()), x.len()),
+            TokenType::LSquare => {
+                let data;
+                (data, refs) = self.parse_data_array()?;
+                let size = data.len();
+                (Some(data), size)
+            },
+            TokenType::Ident(ident) if ident == "zeroinit" => {
+                self.input.pop_front();
+
+                self.expect(TokenType::Int(0))?;
+
+                if let TokenType::Int(size) = &self.current_token()?.typ {
+                    (None, *size as usize)
+                } else { unreachable!() }
+            },
+
+            _=> Err(IrError::UndeterminedTokenSequence { 
+                loc: current.loc.clone(), 
+                expected: "strings, data arrays, zeroinit <size> - for valid globals".to_owned(), 
+            })?
+        };
+
+        let current = &self.current_token()?.loc;
+
+        if location.line == current.line {
+            location.length = current.coloumn - location.coloumn;
+        }
+
+        self.input.pop_front();
+
+        let (section, align) = self.parse_placement()?;
+
+        Ok(IrStmt::Global { 
+            name: name, 
+            data: data,
+            refs: refs,
+            size: size,
             location: location,
             scope: scope,
+            visibility: visibility,
+            thread_local: thread_local,
+            section: section,
+            align: align,
+        })
+    }
+
+    /// parses the optional linkage and visibility before a symbol
+    /// (symbols without a linkage are external and have the default visibility)
+    fn parse_linkage(&mut self) -> (Linkage, Visibility) {
+        let mut linkage = Linkage::External;
+        let mut visibility = Visibility::Default;
+
+        if let Some(Token { typ: TokenType::Ident(ident), .. }) = self.input.front() {
+            let parsed = match ident.as_str() {
+                "local" | "internal" | "private" => Some(Linkage::Internal),
+                "public" | "external" => Some(Linkage::External),
+                "weak" => Some(Linkage::Weak),
+                "linkonce" | "comdat" => Some(Linkage::LinkOnce),
+                _ => None,
+            };
+
+            if let Some(parsed) = parsed {
+                linkage = parsed;
+                self.input.pop_front();
+            }
+        }
+
+        if let Some(Token { typ: TokenType::Ident(ident), .. }) = self.input.front() {
+            let parsed = match ident.as_str() {
+                "default" => Some(Visibility::Default),
+                "hidden" => Some(Visibility::Hidden),
+                "protected" => Some(Visibility::Protected),
+                _ => None,
+            };
+
+            if let Some(parsed) = parsed {
+                visibility = parsed;
+                self.input.pop_front();
+            }
+        }
+
+        (linkage, visibility)
+    }
+
+    /// parses the optional `thread_local` or `thread_local(<model>)` before a constant/global
+    /// (the model defaults to local exec)
+    fn parse_thread_local(&mut self) -> Result<Option<TlsModel>, IrError> {
+        if !matches!(self.input.front(), Some(Token { typ: TokenType::Ident(ident), .. }) if ident == "thread_local") {
+            return Ok(None);
+        }
+
+        self.input.pop_front();
+
+        if TokenType::LParam != self.current_token()?.typ {
+            return Ok(Some(TlsModel::LocalExec));
+        }
+
+        self.input.pop_front();
+
+        self.expect(TokenType::Ident(String::new()))?;
+
+        let tok = self.current_token()?;
+
+        let model = if let TokenType::Ident(ident) = &tok.typ {
+            match ident.as_str() {
+                "localexec" => TlsModel::LocalExec,
+                "initialexec" => TlsModel::InitialExec,
+                _ => Err(IrError::Unkown { 
+                    what: "tls model".to_owned(), 
+                    name: ident.to_owned(), 
+                    loc: tok.loc.clone()
+                })?
+            }
+        } else { unreachable!() };
+
+        self.input.pop_front();
+
+        self.expect(TokenType::RParam)?;
+        self.input.pop_front();
+
+        Ok(Some(model))
+    }
+
+    /// parses the optional `section "<name>"` and `align <n>` after a function signature or the data of a constant/global
+    fn parse_placement(&mut self) -> Result<(Option<String>, Option<u64>), IrError> {
+        let mut section = None;
+        let mut align = None;
+
+        if matches!(self.input.front(), Some(Token { typ: TokenType::Ident(ident), .. }) if ident == "section") {
+            self.input.pop_front();
+
+            self.expect(TokenType::String(String::new()))?;
+
+            if let TokenType::String(name) = &self.current_token()?.typ {
+                section = Some(name.to_owned());
+            }
+
+            self.input.pop_front();
+        }
+
+        if matches!(self.input.front(), Some(Token { typ: TokenType::Ident(ident), .. }) if ident == "align") {
+            self.input.pop_front();
+
+            self.expect(TokenType::Int(0))?;
+
+            let tok = self.current_token()?;
+
+            if let TokenType::Int(value) = &tok.typ {
+                let value = *value;
+
+                if value <= 0 || !(value as u64).is_power_of_two() {
+                    Err(IrError::InvalidAlign { 
+                        align: value, 
+                        loc: tok.loc.clone() 
+                    })?
+                }
+
+                align = Some(value as u64);
+            }
+
+            self.input.pop_front();
+        }
+
+        Ok((section, align))
+    }
+
+    /// parses the name of a constant/global with the optional linkage, visibility and thread local marker before it
+    fn parse_symbol_name(&mut self) -> Result<(String, Linkage, Visibility, Option<TlsModel>, Loc), IrError> {
+        let (scope, visibility) = self.parse_linkage();
+
+        let thread_local = self.parse_thread_local()?;
+
+        self.expect( TokenType::Ident(String::new()) )?;
+
+        let tok = self.current_token()?;
+
+        let (name, location) = if let TokenType::Ident(ident) = &tok.typ {
+            (ident.to_owned(), tok.loc.clone())
+        } else { unreachable!() };
+
+        self.input.pop_front();
+
+        Ok((name, scope, visibility, thread_local, location))
+    }
+
+    fn parse_type_def(&mut self) -> Result<IrStmt, IrError> {
+        self.expect(TokenType::Type)?;
+        self.input.pop_front();
+
+        self.expect(TokenType::Ident(String::new()))?;
+
+        let tok = self.current_token()?;
+        let location = tok.loc.clone();
+
+        let name = if let TokenType::Ident(ident) = &tok.typ {
+            ident.to_owned()
+        } else { unreachable!() };
+
+        self.input.pop_front();
+
+        self.expect(TokenType::Equal)?;
+        self.input.pop_front();
+
+        let ty = if TokenType::LBracket == self.current_token()?.typ {
+            self.input.pop_front(); // {
+
+            let mut fields = vec![];
+
+            loop {
+                if TokenType::RBracket == self.current_token()?.typ {
+                    break;
+                }
+
+                if !fields.is_empty() {
+                    self.expect(TokenType::Comma)?;
+                    self.input.pop_front();
+                }
+
+                fields.push( self.parse_element_type()? );
+            }
+
+            self.input.pop_front(); // }
+
+            AggregateType::Struct { 
+                name: name.to_owned(), 
+                fields: fields,
+            }
+        } else {
+            self.parse_aggregate_type()?
+        };
+
+        self.types.insert(name.to_owned(), ty.to_owned());
+
+        Ok(IrStmt::Type { 
+            name: name, 
+            ty: ty, 
+            location: location,
         })
     }
 
+    /// parses an already defined struct/array type or an array type (`[ty x len]`) and advances over it
+    fn parse_aggregate_type(&mut self) -> Result<AggregateType, IrError> {
+        let tok = self.current_token()?.clone();
+
+        match &tok.typ {
+            TokenType::Ident(name) if self.types.contains_key(name) => {
+                self.input.pop_front();
+                Ok(self.types.get(name).expect("unreachable").to_owned())
+            },
+            TokenType::LSquare => {
+                self.input.pop_front(); // [
+
+                let elem = self.parse_element_type()?;
+
+                let tok = self.current_token()?;
+                if TokenType::Ident("x".into()) != tok.typ {
+                    Err(IrError::UndeterminedTokenSequence { 
+                        loc: tok.loc.clone(), 
+                        expected: "x for a valid array type ([ty x len])".into(), 
+                    })?
+                }
+                self.input.pop_front();
+
+                self.expect(TokenType::Int(0))?;
+                let len = if let TokenType::Int(len) = &self.current_token()?.typ {
+                    *len as usize
+                } else { unreachable!() };
+                self.input.pop_front();
+
+                self.expect(TokenType::RSquare)?;
+                self.input.pop_front();
+
+                Ok(AggregateType::Array { 
+                    elem: Box::new(elem), 
+                    len: len,
+                })
+            },
+            _ => Err(IrError::UnkownType(tok)),
+        }
+    }
+
+    /// parses the type of a struct field or array element and advances over it
+    fn parse_element_type(&mut self) -> Result<ElementType, IrError> {
+        let tok = self.current_token()?.clone();
+
+        if let TokenType::Ident(ident) = &tok.typ {
+            if let Some(ty) = TypeMetadata::parse(ident.to_owned()) {
+                self.input.pop_front();
+                return Ok(ElementType::Scalar(ty));
+            }
+        }
+
+        Ok(ElementType::Aggregate(self.parse_aggregate_type()?))
+    }
+
     fn parse_block(&mut self) -> Result<(String, IrBlock), IrError> {
         self.expect(TokenType::Block(String::new()))?;
 
@@ -410,11 +751,20 @@ impl IrParser {
                     name
                 } else { unreachable!() };
 
+                // nodes with two outputs (e.g: `%res, %overflow = add overflow ...`)
+                let overflow = if let TokenType::Comma = self.current_token()?.typ {
+                    self.input.pop_front(); // ,
+
+                    Some(self.parse_var_operand(TypeMetadata::bool)?)
+                } else { None };
+
                 self.expect(TokenType::Equal)?;
                 self.input.pop_front(); // =
 
                 self.expect(TokenType::Ident(String::new()))?; // node
-                if let TokenType::Ident(instrinc) = &self.current_token()?.typ {
+                if let Some(overflow) = overflow {
+                    self.parse_with_overflow(name, overflow)?
+                } else if let TokenType::Ident(instrinc) = &self.current_token()?.typ {
                     match instrinc.as_str() {
                         "sub" => self.parse_sub(name)?,
                         "add" => self.parse_add(name)?,
@@ -424,8 +774,26 @@ impl IrParser {
                         "or" => self.parse_or(name)?,
                         "and" => self.parse_and(name)?,
                         "div" => self.parse_div(name)?,
+                        "rem" => self.parse_rem(name)?,
+                        "shl" => self.parse_shl(name)?,
+                        "lshr" => self.parse_lshr(name)?,
+                        "ashr" => self.parse_ashr(name)?,
+                        "neg" => self.parse_neg(name)?,
+                        "not" => self.parse_not(name)?,
+                        "bswap" => self.parse_bswap(name)?,
+                        "ctpop" => self.parse_ctpop(name)?,
+                        "ctlz" => self.parse_ctlz(name)?,
+                        "cttz" => self.parse_cttz(name)?,
                         "call" => self.parse_call(name)?,
                         "cmp" => self.parse_cmp(name)?,
+                        "alloca" => self.parse_alloca(name)?,
+                        "load" => self.parse_load(name)?,
+                        "phi" => self.parse_phi(name)?,
+                        "select" => self.parse_select(name)?,
+                        "gep" => self.parse_gep(name)?,
+                        "atomic" => self.parse_atomic(name)?,
+                        "va_arg" => self.parse_va_arg(name)?,
+                        "asm" => self.parse_asm(Some(name))?,
                         _ => {
                             let ty = self.parse_type()?;
                             self.input.pop_front(); // the type
@@ -437,6 +805,32 @@ impl IrParser {
                 match instrinc.as_str() {
                     "ret" => self.parse_ret()?,
                     "br" => self.parse_br()?,
+                    "store" => self.parse_store()?,
+                    "memcpy" => self.parse_memcpy()?,
+                    "memset" => self.parse_memset()?,
+                    "atomic" => self.parse_atomic_store()?,
+                    "fence" => {
+                        self.input.pop_front(); // fence
+
+                        ir::Fence::new(self.parse_atomic_ordering()?)
+                    },
+                    "switch" => self.parse_switch()?,
+                    "tail" => self.parse_tail_call()?,
+                    "asm" => self.parse_asm(None)?,
+                    "va_start" => {
+                        self.input.pop_front(); // va_start
+
+                        ir::VaStart::new(self.parse_va_list()?)
+                    },
+                    "va_end" => {
+                        self.input.pop_front(); // va_end
+
+                        ir::VaEnd::new(self.parse_va_list()?)
+                    },
+                    "unreachable" => {
+                        self.input.pop_front(); // unreachable
+                        ir::Unreachable::new()
+                    },
                     _ => Err(IrError::UnkownInstrinc{loc: curr.loc.clone(), found: instrinc })?,
                 }
             } else {
@@ -461,8 +855,8 @@ impl IrParser {
 
         let curr = self.current_token()?;
 
-        let out:  Result<Box<dyn Ir>, IrError> = if let TokenType::Int(numeral) = &curr.typ {
-            Ok(ir::Return::new(Type::from_int(out_ty, *numeral)))
+        let out:  Result<Box<dyn Ir>, IrError> = if let Some(value) = self.parse_num(out_ty) {
+            Ok(ir::Return::new(value))
         } else if let TokenType::Var(var) = &curr.typ {
             Ok(ir::Return::new(Var {
                 name: var.to_owned(),
@@ -486,10 +880,12 @@ impl IrParser {
             ty: ty,
         };
 
+        self.var_types.insert(out.name.to_owned(), ty);
+
         let curr = self.current_token()?;
 
-        let out:  Result<Box<dyn Ir>, IrError>  = if let TokenType::Int(numeral) = &curr.typ {
-            Ok(ir::Assign::new(out, Type::from_int(ty, *numeral)))
+        let out:  Result<Box<dyn Ir>, IrError>  = if let Some(value) = self.parse_num(ty) {
+            Ok(ir::Assign::new(out, value))
         } else if let TokenType::Var(var) = &curr.typ {
             Ok(ir::Assign::new(out, Var { 
                 name: var.to_owned(),
@@ -497,6 +893,17 @@ impl IrParser {
             }))
         } else if let TokenType::Ident(cons) = &curr.typ {
             Ok(ir::Assign::new(out, Const::new(cons.to_string())))
+        } else if let TokenType::Func(func) = &curr.typ {
+            Ok(ir::Assign::new(out, Function { 
+                ty: FnTy(vec![], TypeMetadata::Void),
+                name: func.to_owned(), 
+                linkage: Linkage::External, 
+                visibility: Visibility::Default,
+                blocks: VecDeque::new(), 
+                attrs: vec![],
+                section: None,
+                align: None,
+            }))
         } else {
             Err(IrError::UndeterminedTokenSequence { 
                 loc: curr.loc.clone(), 
@@ -512,9 +919,15 @@ impl IrParser {
     fn parse_call(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
         self.input.pop_front(); // call
 
+        let callconv = self.parse_callconv();
+
         let func_ty = self.parse_type()?;
         self.input.pop_front();
 
+        if TokenType::LParam == self.current_token()?.typ {
+            return self.parse_call_indirect(var, func_ty, callconv);
+        }
+
         self.expect(TokenType::Ident(String::from("func name")))?;
 
         let target = &self.current_token()?;
@@ -530,139 +943,972 @@ impl IrParser {
             ty: func_ty
         };
 
-        let mut args = vec![];
+        self.var_types.insert(out.name.to_owned(), func_ty);
 
-        loop {
-            let ty = if let TokenType::Ident(ty) = &self.current_token()?.typ {
-                if let Some(ty) = TypeMetadata::parse(ty.to_owned()) {
-                    ty
-                } else {
-                    break
-                }
-            } else { break; };
+        let args = self.parse_call_args()?;
 
+        let mut ty = FnTy(vec![], func_ty);
+        ty.callconv = callconv;
+
+        Ok(ir::Call::new(Function { 
+            ty: ty,
+            name: target, 
+            linkage: Linkage::External, 
+            visibility: Visibility::Default,
+            blocks: VecDeque::new(), 
+            attrs: self.parse_attrs()?,
+            section: None,
+            align: None,
+        }, args, out))
+    }
+
+    fn parse_tail_call(&mut self) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // tail
+
+        self.expect(TokenType::Ident(String::from("call")))?;
+        self.input.pop_front(); // call
+
+        let callconv = self.parse_callconv();
+
+        let func_ty = self.parse_type()?;
+        self.input.pop_front();
+
+        self.expect(TokenType::Ident(String::from("func name")))?;
+
+        let target = if let TokenType::Ident(ident) = &self.current_token()?.typ {
+            ident.to_owned()
+        } else { unreachable!() };
+
+        self.input.pop_front(); // function name
+
+        let args = self.parse_call_args()?;
+
+        let mut ty = FnTy(vec![], func_ty);
+        ty.callconv = callconv;
+
+        Ok(ir::TailCall::new(Function { 
+            ty: ty,
+            name: target, 
+            linkage: Linkage::External, 
+            visibility: Visibility::Default,
+            blocks: VecDeque::new(), 
+            attrs: self.parse_attrs()?,
+            section: None,
+            align: None,
+        }, args))
+    }
+
+    fn parse_call_indirect(&mut self, var: String, ret: TypeMetadata, callconv: Option<CallConv>) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // (
+
+        let mut ty = FnTy(vec![], ret);
+        ty.callconv = callconv;
+
+        loop {
+            if TokenType::Comma == self.current_token()?.typ {
+                self.input.pop_front();
+            }
+
+            let current = self.current_token()?;
+
+            if TokenType::RParam == current.typ {
+                break;
+            }
+
+            if TokenType::TripleDot == current.typ {
+                self.input.pop_front();
+                ty.any_args = true;
+                break;
+            }
+
+            ty.args.push(self.parse_type()?);
+            self.input.pop_front();
+        }
+
+        self.expect(TokenType::RParam)?;
+        self.input.pop_front(); // )
+
+        self.expect(TokenType::Var(String::new()))?;
+
+        let ptr = if let TokenType::Var(name) = &self.current_token()?.typ {
+            Var {
+                name: name.to_owned(),
+                ty: TypeMetadata::ptr,
+            }
+        } else { unreachable!() };
+
+        self.input.pop_front(); // the function pointer
+
+        let out = Var {
+            name: var,
+            ty: ret
+        };
+
+        self.var_types.insert(out.name.to_owned(), ret);
+
+        let args = self.parse_call_args()?;
+
+        Ok(ir::CallIndirect::new(ptr, ty, args, out))
+    }
+
+    fn parse_call_args(&mut self) -> Result<Vec<Var>, IrError> {
+        let mut args = vec![];
+
+        loop {
+            let ty = if let TokenType::Ident(ty) = &self.current_token()?.typ {
+                if let Some(ty) = TypeMetadata::parse(ty.to_owned()) {
+                    ty
+                } else {
+                    break
+                }
+            } else { break; };
+
+            self.input.pop_front();
+
+            let var = self.current_token()?;
+
+            if let TokenType::Var(name) = &var.typ {
+                args.push(Var {
+                    name: name.to_string(),
+                    ty: ty
+                });
+            }
+
+            self.input.pop_front();
+        }
+
+        Ok(args)
+    }
+
+    /// parses the optional calling convention in front of the return type
+    fn parse_callconv(&mut self) -> Option<CallConv> {
+        let conv = if let Some(Token { typ: TokenType::Ident(name), .. }) = self.input.front() {
+            CallConv::parse(name)?
+        } else { return None; };
+
+        self.input.pop_front();
+
+        Some(conv)
+    }
+
+    fn parse_attrs(&mut self) -> Result<Vec<FunctionAttr>, IrError> {
+        let mut attrs = vec![];
+
+        while let Some(Token { typ: TokenType::Attr(name), loc }) = self.input.front() {
+            let attr = FunctionAttr::parse(name).ok_or_else(|| IrError::Unkown { 
+                what: "function attribute".to_owned(), 
+                name: name.to_owned(), 
+                loc: loc.to_owned()
+            })?;
+
+            if !attrs.contains(&attr) {
+                attrs.push(attr);
+            }
+
+            self.input.pop_front();
+        }
+
+        Ok(attrs)
+    }
+
+    fn parse_br(&mut self) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // br
+
+        let current = self.current_token()?;
+
+        match &current.typ {
+            TokenType::Ident(block) => {
+                let block = block.to_owned();
+
+                self.input.pop_front();
+
+                Ok(ir::Br::new(Box::from(Block { 
+                    name: block, 
+                    nodes: vec![], 
+                    varCount: 0
+                })))
+            },
+            TokenType::Cond => {
+                self.input.pop_front(); // cond
+
+                let var = self.expect(TokenType::Var(String::new()))?;
+
+                let var = if let TokenType::Var(var) = var.typ 
+                                                { var } else { unreachable!() };
+
+                self.input.pop_front();
+
+                self.expect(TokenType::Ident(String::new()))?;
+
+                let iftrue;
+
+                if let TokenType::Ident(ident) = &self.current_token()?.typ {
+                    iftrue = ident.to_owned();
+                } else { unreachable!() }
+
+                self.input.pop_front();
+
+                self.expect(TokenType::Comma)?;
+                self.input.pop_front();
+
+                self.expect(TokenType::Ident(String::new()))?;
+
+                let iffalse;
+
+                if let TokenType::Ident(ident) = &self.current_token()?.typ {
+                    iffalse = ident.to_owned();
+                } else { unreachable!() }
+                self.input.pop_front();
+
+                Ok(ir::BrCond::new(Var {
+                    name: var,
+                    ty: TypeMetadata::Void,
+                }, Block {
+                    name: iftrue,
+                    nodes: vec![],
+                    varCount: 0,
+                }, Block {
+                    name: iffalse,
+                    nodes: vec![],
+                    varCount: 0,
+                }))
+            },
+            _ => Err(IrError::UnexpectedToken(self.current_token()?.clone())),
+        }
+    }
+
+    fn parse_cast(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front();
+
+        self.expect(TokenType::Var(String::new()))?;
+
+        let in_var = if let TokenType::Var(name) = &self.current_token()?.typ {
+            Var {
+                name: name.to_owned(),
+                ty: *self.var_types.get(name).unwrap_or(&TypeMetadata::i32),
+            }
+        } else { unreachable!() };
+
+        self.input.pop_front();
+
+        self.expect_ident("to".into())?;
+        self.input.pop_front();
+
+        let out_ty = self.parse_type()?;
+        
+        self.input.pop_front();
+
+        let out = Var { 
+            name: var, 
+            ty: out_ty 
+        };
+
+        self.var_types.insert(out.name.to_owned(), out_ty);
+
+        Ok(ir::Cast::new(in_var, out_ty, out))
+    }
+
+    fn parse_alloca(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // alloca
+
+        let out = Var {
+            name: var,
+            ty: TypeMetadata::ptr,
+        };
+
+        self.var_types.insert(out.name.to_owned(), TypeMetadata::ptr);
+
+        Ok(match self.parse_element_type()? {
+            ElementType::Scalar(ty) => ir::Alloca::new(out, ty),
+            ElementType::Aggregate(ty) => ir::Alloca::new(out, ty),
+        })
+    }
+
+    fn parse_gep(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // gep
+
+        let ty = self.parse_aggregate_type()?;
+
+        self.expect(TokenType::Var(String::new()))?;
+
+        let ptr = if let TokenType::Var(name) = &self.current_token()?.typ {
+            Var {
+                name: name.to_owned(),
+                ty: TypeMetadata::ptr,
+            }
+        } else { unreachable!() };
+
+        self.input.pop_front();
+
+        self.expect(TokenType::Comma)?;
+        self.input.pop_front();
+
+        let out = Var {
+            name: var,
+            ty: TypeMetadata::ptr,
+        };
+
+        self.var_types.insert(out.name.to_owned(), TypeMetadata::ptr);
+
+        let curr = self.current_token()?.clone();
+
+        let node: Box<dyn Ir> = match &curr.typ {
+            TokenType::Int(index) => ir::Gep::new(ty, ptr, Type::i64(*index), out),
+            TokenType::Var(name) => {
+                let index = Var {
+                    name: name.to_owned(),
+                    ty: *self.var_types.get(name).unwrap_or(&TypeMetadata::i64),
+                };
+
+                ir::Gep::new(ty, ptr, index, out)
+            },
+            _ => Err(IrError::UndeterminedTokenSequence { 
+                loc: curr.loc.clone(), 
+                expected: "ints, vars - for a valid gep index".to_owned(), 
+            })?,
+        };
+
+        self.input.pop_front();
+
+        Ok(node)
+    }
+
+    fn parse_load(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // load
+
+        let ty = self.parse_type()?;
+        self.input.pop_front();
+
+        self.expect(TokenType::Var(String::new()))?;
+
+        let ptr = if let TokenType::Var(name) = &self.current_token()?.typ {
+            Var {
+                name: name.to_owned(),
+                ty: TypeMetadata::ptr,
+            }
+        } else { unreachable!() };
+
+        self.input.pop_front();
+
+        let out = Var {
+            name: var,
+            ty: ty,
+        };
+
+        self.var_types.insert(out.name.to_owned(), ty);
+
+        Ok(ir::Load::new(out, ptr, ty))
+    }
+
+    fn parse_va_list(&mut self) -> Result<Var, IrError> {
+        self.expect(TokenType::Var(String::new()))?;
+
+        let list = if let TokenType::Var(name) = &self.current_token()?.typ {
+            Var {
+                name: name.to_owned(),
+                ty: TypeMetadata::ptr,
+            }
+        } else { unreachable!() };
+
+        self.input.pop_front();
+
+        Ok(list)
+    }
+
+    fn parse_va_arg(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // va_arg
+
+        let ty = self.parse_type()?;
+        self.input.pop_front();
+
+        let list = self.parse_va_list()?;
+
+        let out = Var {
+            name: var,
+            ty: ty,
+        };
+
+        self.var_types.insert(out.name.to_owned(), ty);
+
+        Ok(ir::VaArg::new(list, out))
+    }
+
+    fn parse_asm(&mut self, var: Option<String>) -> Result<Box<dyn Ir>, IrError> {
+        let asm_loc = self.current_token()?.loc.clone();
+        self.input.pop_front(); // asm
+
+        let ty = if var.is_some() {
+            let ty = self.parse_type()?;
+            self.input.pop_front();
+            Some(ty)
+        } else { None };
+
+        self.expect(TokenType::String(String::new()))?;
+
+        let asm = if let TokenType::String(asm) = &self.current_token()?.typ {
+            asm.to_owned()
+        } else { unreachable!() };
+
+        self.input.pop_front();
+
+        let mut ins = vec![];
+        let mut out_reg = None;
+        let mut clobbers = vec![];
+
+        // the constraints: out(reg) in(reg ty %var, ...) clobber(reg, ...)
+        while let Some(Token { typ: TokenType::Ident(constraint), .. }) = self.input.front() {
+            let constraint = constraint.to_owned();
+
+            if !matches!(constraint.as_str(), "in" | "out" | "clobber") {
+                break;
+            }
+
+            self.input.pop_front(); // in, out or clobber
+
+            self.expect(TokenType::LParam)?;
             self.input.pop_front();
 
-            let var = self.current_token()?;
+            loop {
+                if TokenType::Comma == self.current_token()?.typ {
+                    self.input.pop_front();
+                }
+
+                if TokenType::RParam == self.current_token()?.typ {
+                    break;
+                }
+
+                self.expect(TokenType::Ident(String::new()))?;
+
+                let reg = if let TokenType::Ident(reg) = &self.current_token()?.typ {
+                    reg.to_owned()
+                } else { unreachable!() };
+
+                self.input.pop_front();
+
+                match constraint.as_str() {
+                    "in" => {
+                        let ty = self.parse_type()?;
+                        self.input.pop_front();
+
+                        ins.push((reg, self.parse_var_operand(ty)?));
+                    },
+                    "out" => out_reg = Some(reg),
+                    _ => clobbers.push(reg),
+                }
+            }
+
+            self.input.pop_front(); // )
+        }
+
+        let out = match (var, ty) {
+            (Some(var), Some(ty)) => {
+                let Some(reg) = out_reg else {
+                    Err(IrError::UndeterminedTokenSequence { 
+                        loc: asm_loc, 
+                        expected: "out(<register>) - for inline assembly with an output".to_owned(), 
+                    })?
+                };
+
+                self.var_types.insert(var.to_owned(), ty);
+
+                Some((reg, Var { name: var, ty: ty }))
+            },
+            _ => None,
+        };
+
+        Ok(ir::InlineAsm::new(asm, ins, out, clobbers))
+    }
+
+    fn parse_store(&mut self) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // store
+
+        let ty = self.parse_type()?;
+        self.input.pop_front();
+
+        let curr = self.current_token()?.clone();
+
+        let num = self.parse_num(ty);
+
+        let var = if let TokenType::Var(var) = &curr.typ {
+            Some(Var {
+                name: var.to_owned(),
+                ty: ty,
+            })
+        } else { None };
+
+        if num.is_none() && var.is_none() {
+            Err(IrError::UndeterminedTokenSequence { 
+                loc: curr.loc.clone(), 
+                expected: "ints, vars - for valid store nodes".to_owned(), 
+            })?
+        }
+
+        self.input.pop_front();
+
+        self.expect(TokenType::Comma)?;
+        self.input.pop_front();
+
+        self.expect(TokenType::Var(String::new()))?;
+
+        let ptr = if let TokenType::Var(name) = &self.current_token()?.typ {
+            Var {
+                name: name.to_owned(),
+                ty: TypeMetadata::ptr,
+            }
+        } else { unreachable!() };
+
+        self.input.pop_front();
+
+        if let Some(num) = num {
+            Ok(ir::Store::new(ptr, num))
+        } else {
+            Ok(ir::Store::new(ptr, var.expect("checked above")))
+        }
+    }
+
+    fn parse_memcpy(&mut self) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // memcpy
+
+        let (dst, src, size) = self.parse_mem_intrinsic()?;
+
+        Ok(match size {
+            SelectValue::Var(size) => ir::MemCpy::new(dst, src, size),
+            SelectValue::Const(size) => ir::MemCpy::new(dst, src, size),
+        })
+    }
+
+    fn parse_memset(&mut self) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // memset
+
+        let (dst, val, size) = self.parse_mem_intrinsic()?;
+
+        Ok(match size {
+            SelectValue::Var(size) => ir::MemSet::new(dst, val, size),
+            SelectValue::Const(size) => ir::MemSet::new(dst, val, size),
+        })
+    }
+
+    /// parses the operands of memory intrinsics: `%dst, ty %op, ty %size/size`
+    fn parse_mem_intrinsic(&mut self) -> Result<(Var, Var, SelectValue), IrError> {
+        self.expect(TokenType::Var(String::new()))?;
+
+        let dst = if let TokenType::Var(name) = &self.current_token()?.typ {
+            Var {
+                name: name.to_owned(),
+                ty: TypeMetadata::ptr,
+            }
+        } else { unreachable!() };
+
+        self.input.pop_front();
+
+        self.expect(TokenType::Comma)?;
+        self.input.pop_front();
+
+        let ty = self.parse_type()?;
+        self.input.pop_front();
+
+        self.expect(TokenType::Var(String::new()))?;
+
+        let op = if let TokenType::Var(name) = &self.current_token()?.typ {
+            Var {
+                name: name.to_owned(),
+                ty: ty,
+            }
+        } else { unreachable!() };
+
+        self.input.pop_front();
+
+        self.expect(TokenType::Comma)?;
+        self.input.pop_front();
+
+        let size_ty = self.parse_type()?;
+        self.input.pop_front();
+
+        let size = self.parse_select_value(size_ty)?;
+
+        Ok((dst, op, size))
+    }
+
+    fn parse_atomic(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // atomic
+
+        self.expect(TokenType::Ident(String::new()))?;
+
+        let curr = self.current_token()?.clone();
+        let op = if let TokenType::Ident(op) = &curr.typ {
+            op.to_owned()
+        } else { unreachable!() };
+
+        let rmw = match op.as_str() {
+            "add" => Some(AtomicRmwOp::Add),
+            "xchg" => Some(AtomicRmwOp::Xchg),
+            "and" => Some(AtomicRmwOp::And),
+            "or" => Some(AtomicRmwOp::Or),
+            "load" | "cmpxchg" => None,
+            _ => Err(IrError::Unkown { 
+                what: "atomic operation".to_owned(), 
+                name: op.to_owned(), 
+                loc: curr.loc.to_owned()
+            })?
+        };
+
+        self.input.pop_front(); // the operation
+
+        let ty = self.parse_type()?;
+        self.input.pop_front();
+
+        let out = Var {
+            name: var,
+            ty: ty,
+        };
+
+        let ptr = self.parse_var_operand(TypeMetadata::ptr)?;
+
+        let node: Box<dyn Ir> = if op == "load" {
+            ir::AtomicLoad::new(ptr, out.to_owned(), self.parse_atomic_ordering()?)
+        } else {
+            self.expect(TokenType::Comma)?;
+            self.input.pop_front();
+
+            let val = self.parse_var_operand(ty)?;
+
+            if let Some(rmw) = rmw {
+                ir::AtomicRmw::new(rmw, ptr, val, out.to_owned(), self.parse_atomic_ordering()?)
+            } else {
+                self.expect(TokenType::Comma)?;
+                self.input.pop_front();
+
+                let new = self.parse_var_operand(ty)?;
+
+                ir::AtomicCmpXchg::new(ptr, val, new, out.to_owned(), self.parse_atomic_ordering()?)
+            }
+        };
+
+        self.var_types.insert(out.name, ty);
+
+        Ok(node)
+    }
+
+    fn parse_atomic_store(&mut self) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // atomic
+
+        self.expect(TokenType::Ident(String::new()))?;
+
+        let curr = self.current_token()?.clone();
+        if curr.typ != TokenType::Ident("store".into()) {
+            Err(IrError::UndeterminedTokenSequence { 
+                loc: curr.loc.clone(), 
+                expected: "atomic store - for atomic nodes without an output".to_owned(), 
+            })?
+        }
+
+        self.input.pop_front(); // store
+
+        let ty = self.parse_type()?;
+        self.input.pop_front();
+
+        let val = self.parse_var_operand(ty)?;
+
+        self.expect(TokenType::Comma)?;
+        self.input.pop_front();
+
+        let ptr = self.parse_var_operand(TypeMetadata::ptr)?;
+
+        Ok(ir::AtomicStore::new(ptr, val, self.parse_atomic_ordering()?))
+    }
+
+    fn parse_with_overflow(&mut self, var: String, overflow: Var) -> Result<Box<dyn Ir>, IrError> {
+        let curr = self.current_token()?.clone();
+        let op = if let TokenType::Ident(op) = &curr.typ {
+            op.to_owned()
+        } else { unreachable!() };
+
+        let op = match op.as_str() {
+            "add" => OverflowOp::Add,
+            "sub" => OverflowOp::Sub,
+            "mul" => OverflowOp::Mul,
+            _ => Err(IrError::Unkown { 
+                what: "overflow checked operation".to_owned(), 
+                name: op, 
+                loc: curr.loc.to_owned()
+            })?
+        };
+
+        self.input.pop_front(); // the operation
+
+        let curr = self.current_token()?.clone();
+        if curr.typ != TokenType::Ident("overflow".into()) {
+            Err(IrError::UndeterminedTokenSequence { 
+                loc: curr.loc.clone(), 
+                expected: "add/sub/mul overflow - for nodes with two outputs".to_owned(), 
+            })?
+        }
+
+        self.input.pop_front(); // overflow
 
-            if let TokenType::Var(name) = &var.typ {
-                args.push(Var {
-                    name: name.to_string(),
-                    ty: ty
-                });
-            }
+        let ty = self.parse_type()?;
+        self.input.pop_front();
 
-            self.input.pop_front();
-        }
+        let ls = self.parse_var_operand(ty)?;
 
-        Ok(ir::Call::new(Function { 
-            ty: FnTy(vec![], func_ty),
-            name: target, 
-            linkage: Linkage::External, 
-            blocks: VecDeque::new(), 
-        }, args, out))
+        self.expect(TokenType::Comma)?;
+        self.input.pop_front();
+
+        let rs = self.parse_var_operand(ty)?;
+
+        let out = Var {
+            name: var,
+            ty: ty,
+        };
+
+        self.var_types.insert(out.name.to_owned(), ty);
+        self.var_types.insert(overflow.name.to_owned(), TypeMetadata::bool);
+
+        Ok(ir::WithOverflow::new(op, ls, rs, out, overflow))
     }
 
-    fn parse_br(&mut self) -> Result<Box<dyn Ir>, IrError> {
-        self.input.pop_front(); // br
+    fn parse_var_operand(&mut self, ty: TypeMetadata) -> Result<Var, IrError> {
+        self.expect(TokenType::Var(String::new()))?;
 
-        let current = self.current_token()?;
+        let var = if let TokenType::Var(name) = &self.current_token()?.typ {
+            Var {
+                name: name.to_owned(),
+                ty: ty,
+            }
+        } else { unreachable!() };
 
-        match &current.typ {
-            TokenType::Ident(block) => {
-                let block = block.to_owned();
+        self.input.pop_front();
 
-                self.input.pop_front();
+        Ok(var)
+    }
 
-                Ok(ir::Br::new(Box::from(Block { 
-                    name: block, 
-                    nodes: vec![], 
-                    varCount: 0
-                })))
-            },
-            TokenType::Cond => {
-                self.input.pop_front(); // cond
+    fn parse_atomic_ordering(&mut self) -> Result<AtomicOrdering, IrError> {
+        self.expect(TokenType::Ident(String::new()))?;
 
-                let var = self.expect(TokenType::Var(String::new()))?;
+        let curr = self.current_token()?;
+        let ident = if let TokenType::Ident(ident) = &curr.typ {
+            ident.to_owned()
+        } else { unreachable!() };
 
-                let var = if let TokenType::Var(var) = var.typ 
-                                                { var } else { unreachable!() };
+        let order = match ident.as_str() {
+            "relaxed" => AtomicOrdering::Relaxed,
+            "acquire" => AtomicOrdering::Acquire,
+            "release" => AtomicOrdering::Release,
+            "acq_rel" => AtomicOrdering::AcqRel,
+            "seq_cst" => AtomicOrdering::SeqCst,
+            _ => Err(IrError::Unkown { 
+                what: "memory ordering".to_owned(), 
+                name: ident, 
+                loc: curr.loc.to_owned()
+            })?
+        };
 
-                self.input.pop_front();
+        self.input.pop_front();
 
-                self.expect(TokenType::Ident(String::new()))?;
+        Ok(order)
+    }
 
-                let iftrue;
+    fn parse_phi(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // phi
 
-                if let TokenType::Ident(ident) = &self.current_token()?.typ {
-                    iftrue = ident.to_owned();
-                } else { unreachable!() }
+        let ty = self.parse_type()?;
+        self.input.pop_front();
 
-                self.input.pop_front();
+        let mut recive_from_blocks = vec![];
 
-                self.expect(TokenType::Comma)?;
-                self.input.pop_front();
+        loop {
+            self.expect(TokenType::LSquare)?;
+            self.input.pop_front();
 
-                self.expect(TokenType::Ident(String::new()))?;
+            self.expect(TokenType::Var(String::new()))?;
 
-                let iffalse;
+            let recive = if let TokenType::Var(name) = &self.current_token()?.typ {
+                Var {
+                    name: name.to_owned(),
+                    ty: ty,
+                }
+            } else { unreachable!() };
 
-                if let TokenType::Ident(ident) = &self.current_token()?.typ {
-                    iffalse = ident.to_owned();
-                } else { unreachable!() }
-                self.input.pop_front();
+            self.input.pop_front();
 
-                Ok(ir::BrCond::new(Var {
-                    name: var,
-                    ty: TypeMetadata::Void,
-                }, Block {
-                    name: iftrue,
-                    nodes: vec![],
-                    varCount: 0,
-                }, Block {
-                    name: iffalse,
+            self.expect(TokenType::Comma)?;
+            self.input.pop_front();
+
+            self.expect(TokenType::Ident(String::new()))?;
+
+            let block = if let TokenType::Ident(name) = &self.current_token()?.typ {
+                Block {
+                    name: name.to_owned(),
                     nodes: vec![],
                     varCount: 0,
-                }))
-            },
-            _ => Err(IrError::UnexpectedToken(self.current_token()?.clone())),
+                }
+            } else { unreachable!() };
+
+            self.input.pop_front();
+
+            self.expect(TokenType::RSquare)?;
+            self.input.pop_front();
+
+            recive_from_blocks.push((block, recive));
+
+            if let Ok(Token { typ: TokenType::Comma, .. }) = self.current_token() {
+                self.input.pop_front();
+            } else {
+                break;
+            }
         }
+
+        let out = Var {
+            name: var,
+            ty: ty,
+        };
+
+        self.var_types.insert(out.name.to_owned(), ty);
+
+        Ok(ir::Phi::new(out, recive_from_blocks))
     }
 
-    fn parse_cast(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
+    fn parse_select(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // select
+
+        let ty = self.parse_type()?;
         self.input.pop_front();
 
         self.expect(TokenType::Var(String::new()))?;
 
-        let in_var = if let TokenType::Var(name) = &self.current_token()?.typ {
+        let cond = if let TokenType::Var(name) = &self.current_token()?.typ {
             Var {
                 name: name.to_owned(),
-                ty: TypeMetadata::i32,
+                ty: TypeMetadata::bool,
             }
         } else { unreachable!() };
 
         self.input.pop_front();
 
-        self.expect_ident("to".into())?;
+        self.expect(TokenType::Comma)?;
         self.input.pop_front();
 
-        let out_ty = self.parse_type()?;
-        
+        let yes = self.parse_select_value(ty)?;
+
+        self.expect(TokenType::Comma)?;
         self.input.pop_front();
 
-        let out = Var { 
-            name: var, 
-            ty: out_ty 
+        let no = self.parse_select_value(ty)?;
+
+        let out = Var {
+            name: var,
+            ty: ty,
         };
 
-        Ok(ir::Cast::new(in_var, out_ty, out))
+        self.var_types.insert(out.name.to_owned(), ty);
+
+        Ok(match (yes, no) {
+            (SelectValue::Var(yes), SelectValue::Var(no)) => ir::Select::new(cond, yes, no, out),
+            (SelectValue::Var(yes), SelectValue::Const(no)) => ir::Select::new(cond, yes, no, out),
+            (SelectValue::Const(yes), SelectValue::Var(no)) => ir::Select::new(cond, yes, no, out),
+            (SelectValue::Const(yes), SelectValue::Const(no)) => ir::Select::new(cond, yes, no, out),
+        })
+    }
+
+    fn parse_select_value(&mut self, ty: TypeMetadata) -> Result<SelectValue, IrError> {
+        let curr = self.current_token()?;
+
+        let value = match &curr.typ {
+            TokenType::Var(name) => SelectValue::Var(Var {
+                name: name.to_owned(),
+                ty: ty,
+            }),
+
+            TokenType::Int(_) | TokenType::Float(_) => SelectValue::Const(self.parse_num(ty).expect("unreachable")),
+
+            _ => Err(IrError::ExpectedTokenButFoundAnUnexpectedOne {
+                expected: Token { typ: TokenType::Var(") or Int()".into()), loc: Loc::default() },
+                found: curr.clone(),
+            })?
+        };
+
+        self.input.pop_front();
+
+        Ok(value)
+    }
+
+    fn parse_switch(&mut self) -> Result<Box<dyn Ir>, IrError> {
+        self.input.pop_front(); // switch
+
+        let ty = self.parse_type()?;
+        self.input.pop_front();
+
+        self.expect(TokenType::Var(String::new()))?;
+
+        let var = if let TokenType::Var(name) = &self.current_token()?.typ {
+            Var {
+                name: name.to_owned(),
+                ty: ty,
+            }
+        } else { unreachable!() };
+
+        self.input.pop_front();
+
+        self.expect(TokenType::Comma)?;
+        self.input.pop_front();
+
+        self.expect(TokenType::Ident(String::new()))?;
+
+        let default = if let TokenType::Ident(name) = &self.current_token()?.typ {
+            Block {
+                name: name.to_owned(),
+                nodes: vec![],
+                varCount: 0,
+            }
+        } else { unreachable!() };
+
+        self.input.pop_front();
+
+        let mut cases = vec![];
+
+        while let Ok(Token { typ: TokenType::Comma, .. }) = self.current_token() {
+            self.input.pop_front();
+
+            self.expect(TokenType::LSquare)?;
+            self.input.pop_front();
+
+            self.expect(TokenType::Int(0))?;
+
+            let value = self.parse_num(ty).expect("checked above");
+
+            self.input.pop_front();
+
+            self.expect(TokenType::Comma)?;
+            self.input.pop_front();
+
+            self.expect(TokenType::Ident(String::new()))?;
+
+            let block = if let TokenType::Ident(name) = &self.current_token()?.typ {
+                Block {
+                    name: name.to_owned(),
+                    nodes: vec![],
+                    varCount: 0,
+                }
+            } else { unreachable!() };
+
+            self.input.pop_front();
+
+            self.expect(TokenType::RSquare)?;
+            self.input.pop_front();
+
+            cases.push((value, block));
+        }
+
+        Ok(ir::Switch::new(var, default, cases))
     }
 
-    fn parse_data_array(&mut self) -> Result<Vec<u8>, IrError> {
+    /// parses the bytes and symbol addresses (`ptr @func`, `ptr const+4`) of a data array
+    fn parse_data_array(&mut self) -> Result<(Vec<u8>, Vec<SymbolRef>), IrError> {
         self.expect(TokenType::LSquare)?;
         self.input.pop_front();
         
         let mut data = vec![];
+        let mut refs = vec![];
 
         loop {
             let current = self.current_token()?;
@@ -670,6 +1916,30 @@ impl IrParser {
             match &current.typ {
                 TokenType::Int(int) => data.push(*int as u8),
 
+                TokenType::Ident(ptr) if ptr == "ptr" => {
+                    self.input.pop_front(); // ptr
+
+                    let current = self.current_token()?;
+
+                    let symbol = match &current.typ {
+                        TokenType::Func(symbol) | TokenType::Ident(symbol) => symbol.to_owned(),
+                        _ => Err(IrError::UndeterminedTokenSequence { 
+                            loc: current.loc.clone(), 
+                            expected: "@func, const - for valid symbol addresses".to_owned(), 
+                        })?
+                    };
+
+                    let mut addend = 0;
+
+                    if let Some(Token { typ: TokenType::Int(int), .. }) = self.input.get(1) {
+                        addend = *int;
+                        self.input.pop_front(); // the symbol
+                    }
+
+                    refs.push(SymbolRef { at: data.len(), symbol: symbol, addend: addend });
+                    data.extend_from_slice(&[0; 8]);
+                },
+
                 TokenType::RSquare => break,
                 _ => Err(IrError::UnexpectedToken(current.clone()))?,
             };
@@ -683,7 +1953,16 @@ impl IrParser {
             }
         }
 
-        Ok(data)
+        Ok((data, refs))
+    }
+
+    /// returns the current token as a value of the given type if it is a number
+    fn parse_num(&self, ty: TypeMetadata) -> Option<Type> {
+        match self.input.front()?.typ {
+            TokenType::Int(int) => Some(Type::from_int(ty, int)),
+            TokenType::Float(float) => Some(Type::from_float(ty, float)),
+            _ => None,
+        }
     }
 
     fn current_token(&self) -> Result<&Token, IrError> {
@@ -803,6 +2082,10 @@ impl IrParser {
         } else { unreachable!() };
         self.input.pop_front();
 
+        let out_ty = TypeMetadata::bool;
+
+        self.var_types.insert(var.to_owned(), out_ty);
+
         Ok(Cmp::new(mode, Var {
             name: ls,
             ty: ty,
@@ -811,7 +2094,7 @@ impl IrParser {
             ty: ty 
         }, Var {
             name: var,
-            ty: ty
+            ty: out_ty
         }))
     }
 }
@@ -820,7 +2103,7 @@ macro_rules! ParserImplParseMath {
     ($func:ident, $node:ident) => {
         impl IrParser {
             fn $func(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
-                self.input.pop_front(); // add/sub/xor/or/and/mul/div
+                self.input.pop_front(); // add/sub/xor/or/and/mul/div/rem/shl/lshr/ashr
 
                 let ty = self.parse_type()?;
                 self.input.pop_front(); // out_ty
@@ -830,28 +2113,30 @@ macro_rules! ParserImplParseMath {
                     ty: ty,
                 };
 
+                self.var_types.insert(out.name.to_owned(), ty);
+
                 let curr = self.current_token()?;
 
                 Ok(match &curr.typ {
-                    TokenType::Int(op1) => {
-                        let op1 = *op1;
+                    TokenType::Int(_) | TokenType::Float(_) => {
+                        let op1 = self.parse_num(ty).expect("unreachable");
 
                         self.input.pop_front(); // num1
 
                         self.expect(TokenType::Comma)?;
                         self.input.pop_front(); // ,
 
-                        self.expect(TokenType::Int(0))?;
-                        let op2;
-                        if let TokenType::Int(int) = &self.current_token()?.typ {
-                            op2 = *int;
-                        } else { unreachable!() }
+                        let op2 = if let Some(op2) = self.parse_num(ty) {
+                            op2
+                        } else {
+                            Err(IrError::ExpectedTokenButFoundAnUnexpectedOne {
+                                found: self.current_token()?.to_owned(),
+                                expected: Token { typ: TokenType::Int(0), loc: Loc::default() },
+                            })?
+                        };
 
                         self.input.pop_front();
 
-                        let op1 = Type::from_int(ty, op1);
-                        let op2 = Type::from_int(ty, op2);
-
                         ir::$node::new(op1, op2, out)
                     },
 
@@ -877,13 +2162,11 @@ macro_rules! ParserImplParseMath {
                             self.input.pop_front();
     
                             ir::$node::new(op1, op2, out)
-                        } else if let TokenType::Int(op) = &self.current_token()?.typ {
+                        } else if let Some(op2) = self.parse_num(ty) {
                             let op1 = Var {
                                 name: op1,
                                 ty: ty
                             };
-    
-                            let op2 = Type::from_int(ty, *op);
 
                             self.input.pop_front();
     
@@ -913,4 +2196,63 @@ ParserImplParseMath!(parse_xor, Xor);
 ParserImplParseMath!(parse_or,  Or );
 ParserImplParseMath!(parse_and, And);
 ParserImplParseMath!(parse_mul, Mul);
-ParserImplParseMath!(parse_div, Div);
\ No newline at end of file
+ParserImplParseMath!(parse_div, Div);
+ParserImplParseMath!(parse_rem, Rem);
+ParserImplParseMath!(parse_shl, Shl);
+ParserImplParseMath!(parse_lshr, Lshr);
+ParserImplParseMath!(parse_ashr, Ashr);
+
+macro_rules! ParserImplParseUnary {
+    ($func:ident, $node:ident) => {
+        impl IrParser {
+            fn $func(&mut self, var: String) -> Result<Box<dyn Ir>, IrError> {
+                self.input.pop_front(); // neg/not
+
+                let ty = self.parse_type()?;
+                self.input.pop_front(); // out_ty
+
+                let out = Var {
+                    name: var,
+                    ty: ty,
+                };
+
+                self.var_types.insert(out.name.to_owned(), ty);
+
+                let curr = self.current_token()?;
+
+                Ok(match &curr.typ {
+                    TokenType::Var(op) => {
+                        let op = Var {
+                            name: op.to_owned(),
+                            ty: ty,
+                        };
+
+                        self.input.pop_front();
+
+                        ir::$node::new(op, out)
+                    },
+
+                    TokenType::Int(_) | TokenType::Float(_) => {
+                        let op = self.parse_num(ty).expect("unreachable");
+
+                        self.input.pop_front();
+
+                        ir::$node::new(op, out)
+                    },
+
+                    _ => Err(IrError::ExpectedTokenButFoundAnUnexpectedOne {
+                        expected: Token { typ: TokenType::Var(") or Int()".into()), loc: Loc::default() },
+                        found: curr.clone(),
+                    })?
+                })
+            }
+        }
+    };
+}
+
+ParserImplParseUnary!(parse_neg, Neg);
+ParserImplParseUnary!(parse_not, Not);
+ParserImplParseUnary!(parse_bswap, Bswap);
+ParserImplParseUnary!(parse_ctpop, Ctpop);
+ParserImplParseUnary!(parse_ctlz, Ctlz);
+ParserImplParseUnary!(parse_cttz, Cttz);
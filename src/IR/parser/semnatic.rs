@@ -1,7 +1,8 @@
 use std::collections::{BTreeMap, HashMap};
 
 use crate::Obj::Linkage;
-use crate::IR::{Block, Const, FnTy, Function, FunctionType, Type, TypeMetadata, Var};
+use crate::Target::CallConv;
+use crate::IR::{AggregateType, Block, Const, FnTy, Function, FunctionType, SymbolRef, Type, TypeMetadata, Var, VerifyError};
 
 use crate::prelude::ir::*;
 
@@ -15,6 +16,7 @@ pub struct IrSemnatic<'a> {
     input: &'a Vec<IrStmt>,
 
     const_sigs: HashMap<String, Linkage>,
+    type_sigs: Vec<String>,
     func_sigs: HashMap<String, (FunctionType, Linkage, /*the blocks*/Vec<String>)>,
 }
 
@@ -25,6 +27,7 @@ impl<'a> IrSemnatic<'a> {
             input: exprs,
 
             const_sigs: HashMap::new(),
+            type_sigs: Vec::new(),
             func_sigs: HashMap::new(),
         }
     }
@@ -33,22 +36,27 @@ impl<'a> IrSemnatic<'a> {
     pub fn verify(&mut self) -> Result<(), IrError> {
         for stmt in self.input {
             match stmt {
-                IrStmt::Func { name, ret, args, body, scope, location } => self.add_func(name, *ret, args, scope, body, location)?,
-                IrStmt::Const { name, data: _, location, scope } => self.add_const(name, scope, location)?
+                IrStmt::Func { name, ret, args, body, scope, callconv, location, .. } => self.add_func(name, *ret, args, scope, *callconv, body, location)?,
+                IrStmt::Const { name, location, scope, .. } => self.add_const(name, scope, location)?,
+                IrStmt::Global { name, location, scope, .. } => self.add_global(name, scope, location)?,
+                IrStmt::Type { name, ty: _, location } => self.add_type(name, location)?,
             }
         }
 
         for stmt in self.input {
             match stmt {
-                IrStmt::Func { name, ret, args, body, scope, location } => self.analizye_func(name, *ret, args, body, *scope, location)?,
-                IrStmt::Const { name, data, location, scope } => self.analyize_const(name, data, location, *scope)?,
+                IrStmt::Func { name, ret, args, body, scope, location, .. } => self.analizye_func(name, *ret, args, body, *scope, location)?,
+                IrStmt::Const { name, data, refs, location, scope, .. } => self.analyize_const(name, data, refs, location, *scope)?,
+                IrStmt::Global { refs, location, .. } => self.analyize_refs(refs, location)?,
+                IrStmt::Type { .. } => {}, // the parser already resolved the types
             }
         }
 
         Ok(())
     }
 
-    fn add_func(&mut self, name: &String, ret: TypeMetadata, args: &(BTreeMap<String, TypeMetadata>, bool),  scope: &Linkage, body: &BTreeMap<String, IrBlock>, loc: &Loc) -> Result<(), IrError> {
+    #[allow(clippy::too_many_arguments)]
+    fn add_func(&mut self, name: &String, ret: TypeMetadata, args: &(BTreeMap<String, TypeMetadata>, bool),  scope: &Linkage, callconv: Option<CallConv>, body: &Vec<(String, IrBlock)>, loc: &Loc) -> Result<(), IrError> {
         if self.func_sigs.contains_key(name) {
             Err(IrError::DefinedTwice {
                 loc: loc.clone(),
@@ -68,6 +76,8 @@ impl<'a> IrSemnatic<'a> {
             ty.activate_dynamic_arguments();
         }
 
+        ty.callconv = callconv;
+
         let mut blocks = vec![];
 
         for (name, _) in body {
@@ -91,7 +101,32 @@ impl<'a> IrSemnatic<'a> {
         Ok(())
     }
 
-    fn analizye_func(&mut self, name: &String, ret: TypeMetadata, args: &(BTreeMap<String, TypeMetadata>, bool), body: &BTreeMap<String, IrBlock>, scope: Linkage, loc: &Loc) -> Result<(), IrError> {
+    fn add_global(&mut self, name: &String, scope: &Linkage, loc: &Loc) -> Result<(), IrError> {
+        if self.func_sigs.contains_key(name) || self.const_sigs.contains_key(name) {
+            Err(IrError::DefinedTwice {
+                loc: loc.clone(),
+                name: name.to_owned()
+            })?
+        }
+
+        // globals are referenced the same way as constants
+        self.const_sigs.insert(name.to_owned(), *scope);
+        Ok(())
+    }
+
+    fn add_type(&mut self, name: &String, loc: &Loc) -> Result<(), IrError> {
+        if self.type_sigs.contains(name) {
+            Err(IrError::DefinedTwice {
+                loc: loc.clone(),
+                name: name.to_owned()
+            })?
+        }
+
+        self.type_sigs.push(name.to_owned());
+        Ok(())
+    }
+
+    fn analizye_func(&mut self, name: &String, ret: TypeMetadata, args: &(BTreeMap<String, TypeMetadata>, bool), body: &Vec<(String, IrBlock)>, scope: Linkage, loc: &Loc) -> Result<(), IrError> {
         let mut vars = HashMap::new();
 
         let mut blocks = vec![];
@@ -109,6 +144,8 @@ impl<'a> IrSemnatic<'a> {
 
         let func = name;
 
+        let mut phis = vec![];
+
         for (name, block) in body {
             if blocks.contains(name) {
                 Err(IrError::DefinedTwice {
@@ -135,8 +172,14 @@ impl<'a> IrSemnatic<'a> {
                     self.analiyze_assign_var(&mut vars, node, loc)?;
                 } else if let Some(node) = any.downcast_ref::<Assign<Var, Type>>() {
                     self.analiyze_assign_type(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Assign<Var, Function>>() {
+                    self.analiyze_assign_func(&mut vars, node, loc)?;
                 } else if let Some(node) = any.downcast_ref::<Call<Function, Vec<Var>, Var>>() {
                     self.analyize_call(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<CallIndirect>() {
+                    self.analyize_call_indirect(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<TailCall<Function, Vec<Var>>>() {
+                    self.analyize_tail_call(&mut vars, node, ret, loc)?;
                 } else if let Some(node) = any.downcast_ref::<Br<Box<Block>>>() {
                     self.analiyze_block(func, node, loc)?;
                 } else if let Some(node) = any.downcast_ref::<Add<Var, Type, Var>>() {
@@ -153,6 +196,14 @@ impl<'a> IrSemnatic<'a> {
                     self.analaysiz_mul_var_ty(&mut vars, node, loc)?;
                 } else if let Some(node) = any.downcast_ref::<Div<Var, Type, Var>>() {
                     self.analaysiz_div_var_ty(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Rem<Var, Type, Var>>() {
+                    self.analaysiz_rem_var_ty(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Shl<Var, Type, Var>>() {
+                    self.analaysiz_shl_var_ty(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Lshr<Var, Type, Var>>() {
+                    self.analaysiz_lshr_var_ty(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Ashr<Var, Type, Var>>() {
+                    self.analaysiz_ashr_var_ty(&mut vars, node, loc)?;
                 } else if let Some(node) = any.downcast_ref::<Add<Type, Type, Var>>() {
                     self.analaysiz_add_ty_ty(&mut vars, node, loc)?;
                 } else if let Some(node) = any.downcast_ref::<Sub<Type, Type, Var>>() {
@@ -167,6 +218,14 @@ impl<'a> IrSemnatic<'a> {
                     self.analaysiz_mul_ty_ty(&mut vars, node, loc)?;
                 } else if let Some(node) = any.downcast_ref::<Div<Type, Type, Var>>() {
                     self.analaysiz_div_ty_ty(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Rem<Type, Type, Var>>() {
+                    self.analaysiz_rem_ty_ty(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Shl<Type, Type, Var>>() {
+                    self.analaysiz_shl_ty_ty(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Lshr<Type, Type, Var>>() {
+                    self.analaysiz_lshr_ty_ty(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Ashr<Type, Type, Var>>() {
+                    self.analaysiz_ashr_ty_ty(&mut vars, node, loc)?;
                 } else if let Some(node) = any.downcast_ref::<Add<Var, Var, Var>>() {
                     self.analaysiz_add_var_var(&mut vars, node, loc)?;
                 } else if let Some(node) = any.downcast_ref::<Sub<Var, Var, Var>>() {
@@ -181,12 +240,112 @@ impl<'a> IrSemnatic<'a> {
                     self.analaysiz_mul_var_var(&mut vars, node, loc)?;
                 } else if let Some(node) = any.downcast_ref::<Div<Var, Var, Var>>() {
                     self.analaysiz_div_var_var(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Rem<Var, Var, Var>>() {
+                    self.analaysiz_rem_var_var(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Shl<Var, Var, Var>>() {
+                    self.analaysiz_shl_var_var(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Lshr<Var, Var, Var>>() {
+                    self.analaysiz_lshr_var_var(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Ashr<Var, Var, Var>>() {
+                    self.analaysiz_ashr_var_var(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Neg<Var, Var>>() {
+                    self.analaysiz_neg_var(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Neg<Type, Var>>() {
+                    self.analaysiz_neg_ty(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Not<Var, Var>>() {
+                    self.analaysiz_not_var(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Not<Type, Var>>() {
+                    self.analaysiz_not_ty(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Bswap<Var, Var>>() {
+                    self.analaysiz_bswap_var(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Bswap<Type, Var>>() {
+                    self.analaysiz_bswap_ty(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Ctpop<Var, Var>>() {
+                    self.analaysiz_ctpop_var(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Ctpop<Type, Var>>() {
+                    self.analaysiz_ctpop_ty(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Ctlz<Var, Var>>() {
+                    self.analaysiz_ctlz_var(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Ctlz<Type, Var>>() {
+                    self.analaysiz_ctlz_ty(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Cttz<Var, Var>>() {
+                    self.analaysiz_cttz_var(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Cttz<Type, Var>>() {
+                    self.analaysiz_cttz_ty(&mut vars, node, loc)?;
                 } else if let Some(node) = any.downcast_ref::<Cast<Var, TypeMetadata, Var>>() {
                     self.analaysiz_cast(&mut vars, node, loc)?;
                 } else if let Some(node) = any.downcast_ref::<BrCond<Var, Block, Block>>() {
                     self.analaysiz_br_cond(func, &mut vars, node, loc)?;
                 } else if let Some(node) = any.downcast_ref::<Cmp>() {
                     self.analaysiz_cmp(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Alloca<Var, TypeMetadata>>() {
+                    self.analaysiz_alloca(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Alloca<Var, AggregateType>>() {
+                    self.analaysiz_alloca(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Gep<Var>>() {
+                    self.analaysiz_gep(&mut vars, node, Some(&node.index), loc)?;
+                } else if let Some(node) = any.downcast_ref::<Gep<Type>>() {
+                    self.analaysiz_gep(&mut vars, node, None, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Load<Var, Var, TypeMetadata>>() {
+                    self.analaysiz_load(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Store<Var, Var>>() {
+                    self.analaysiz_store_var(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Store<Var, Type>>() {
+                    self.analaysiz_store_ty(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<MemCpy<Var>>() {
+                    self.analaysiz_operands(&vars, &[&node.dst, &node.src, &node.size], loc)?;
+                } else if let Some(node) = any.downcast_ref::<MemCpy<Type>>() {
+                    self.analaysiz_operands(&vars, &[&node.dst, &node.src], loc)?;
+                } else if let Some(node) = any.downcast_ref::<MemSet<Var>>() {
+                    self.analaysiz_operands(&vars, &[&node.dst, &node.val, &node.size], loc)?;
+                } else if let Some(node) = any.downcast_ref::<MemSet<Type>>() {
+                    self.analaysiz_operands(&vars, &[&node.dst, &node.val], loc)?;
+                } else if let Some(node) = any.downcast_ref::<AtomicLoad>() {
+                    self.analaysiz_atomic(&mut vars, &node.ptr, node.out.ty, &[], Some(&node.out), loc)?;
+                } else if let Some(node) = any.downcast_ref::<AtomicStore>() {
+                    self.analaysiz_atomic(&mut vars, &node.ptr, node.val.ty, &[&node.val], None, loc)?;
+                } else if let Some(node) = any.downcast_ref::<AtomicRmw>() {
+                    self.analaysiz_atomic(&mut vars, &node.ptr, node.out.ty, &[&node.val], Some(&node.out), loc)?;
+                } else if let Some(node) = any.downcast_ref::<AtomicCmpXchg>() {
+                    self.analaysiz_atomic(&mut vars, &node.ptr, node.out.ty, &[&node.expected, &node.new], Some(&node.out), loc)?;
+                } else if let Some(node) = any.downcast_ref::<VaStart>() {
+                    self.analaysiz_operands(&vars, &[&node.list], loc)?;
+                } else if let Some(node) = any.downcast_ref::<VaArg>() {
+                    self.analaysiz_with_out(&mut vars, &[&node.list], Some(&node.out), loc)?;
+                } else if let Some(node) = any.downcast_ref::<VaEnd>() {
+                    self.analaysiz_operands(&vars, &[&node.list], loc)?;
+                } else if let Some(node) = any.downcast_ref::<InlineAsm>() {
+                    self.analaysiz_inline_asm(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<WithOverflow>() {
+                    self.analaysiz_with_overflow(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Select<Var, Var>>() {
+                    self.analaysiz_select(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Select<Var, Type>>() {
+                    self.analaysiz_select(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Select<Type, Var>>() {
+                    self.analaysiz_select(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Select<Type, Type>>() {
+                    self.analaysiz_select(&mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Switch>() {
+                    self.analaysiz_switch(func, &mut vars, node, loc)?;
+                } else if let Some(node) = any.downcast_ref::<Phi>() {
+                    self.analaysiz_phi(func, &mut vars, node, loc.to_owned())?;
+
+                    phis.push((node, loc));
+                }
+            }
+        }
+
+        // the values of phis can be defined after them (e.g: in loops)
+        // so they are checked after the entire function was analyzed
+        for (phi, loc) in phis {
+            for (_, var) in &phi.recive_from_blocks {
+                if !vars.contains_key(&var.name) {
+                    Err(IrError::Unkown { 
+                        what: "variable".to_owned(), 
+                        name: var.name.to_owned(), 
+                        loc: loc.clone()
+                    })?
                 }
             }
         }
@@ -298,6 +457,30 @@ impl<'a> IrSemnatic<'a> {
         Ok(())
     }
 
+    fn analiyze_assign_func(&mut self, vars: &mut HashMap<String, TypeMetadata>, node: &Assign<Var, Function>, loc: Loc) -> Result<(), IrError> {
+        let name = &node.inner2.name;
+        if !self.func_sigs.contains_key(name) {
+            Err(IrError::Unkown {
+                what: "function".to_owned(),
+                loc: loc.to_owned(),
+                name: name.to_owned(),
+            })?
+        }
+
+        let name = node.inner1.name.to_owned();
+
+        if vars.contains_key(&name) {
+            Err(IrError::DefinedTwice { 
+                loc: loc.to_owned(), 
+                name: name.to_owned(),
+            })?
+        }
+
+        vars.insert(name, TypeMetadata::ptr);
+
+        Ok(())
+    }
+
     fn analyize_call(&mut self, vars: &mut HashMap<String, TypeMetadata>, node: &Call<Function, Vec<Var>, Var>, loc: Loc) -> Result<(), IrError> {
         let name = &node.inner1.name;
         let mut sig = node.inner1.ty.to_owned();
@@ -323,9 +506,77 @@ impl<'a> IrSemnatic<'a> {
 
         vars.insert(name, sig.ret);
 
+        Self::analyize_callconv(&sig, &node.inner1.ty, &loc)?;
+
+        self.analyize_call_args(vars, &sig, &node.inner2, loc)
+    }
+
+    fn analyize_tail_call(&mut self, vars: &mut HashMap<String, TypeMetadata>, node: &TailCall<Function, Vec<Var>>, fsig: TypeMetadata, loc: Loc) -> Result<(), IrError> {
+        let name = &node.inner1.name;
+
+        let sig = if let Some((ty, _, _)) = self.func_sigs.get(name) {
+            ty.to_owned()
+        } else {
+            Err(IrError::Unkown {
+                what: "function".to_owned(),
+                loc: loc.to_owned(),
+                name: name.to_owned(),
+            })?
+        };
+
+        // the result of the callee is returned to our caller
+        if sig.ret != fsig {
+            Err(IrError::FuncWrongReturnTyoe {
+                expected: fsig,
+                found: sig.ret,
+                loc: loc.to_owned(),
+            })?
+        }
+
+        Self::analyize_callconv(&sig, &node.inner1.ty, &loc)?;
+
+        self.analyize_call_args(vars, &sig, &node.inner2, loc)
+    }
+
+    fn analyize_callconv(sig: &FunctionType, call: &FunctionType, loc: &Loc) -> Result<(), IrError> {
+        if sig.callconv != call.callconv {
+            Err(IrError::FuncWrongCallConv {
+                expected: sig.callconv,
+                found: call.callconv,
+                loc: loc.to_owned(),
+            })?
+        }
+
+        Ok(())
+    }
+
+    fn analyize_call_indirect(&mut self, vars: &mut HashMap<String, TypeMetadata>, node: &CallIndirect, loc: Loc) -> Result<(), IrError> {
+        if !vars.contains_key(&node.ptr.name) {
+            Err(IrError::Unkown { 
+                what: "variable".to_owned(), 
+                name: node.ptr.name.to_owned(), 
+                loc: loc.to_owned(), 
+            })?
+        }
+
+        let name = node.out.name.to_owned();
+
+        if vars.contains_key(&name) {
+            Err(IrError::DefinedTwice { 
+                loc: loc.to_owned(), 
+                name: name.to_owned(),
+            })?
+        }
+
+        vars.insert(name, node.ty.ret);
+
+        self.analyize_call_args(vars, &node.ty, &node.args, loc)
+    }
+
+    fn analyize_call_args(&mut self, vars: &mut HashMap<String, TypeMetadata>, sig: &FunctionType, args: &[Var], loc: Loc) -> Result<(), IrError> {
         let mut index = 0;
 
-        for arg in &node.inner2 {
+        for arg in args {
             let arg = if let Some(var) = vars.get(&arg.name) {
                 var
             } else {
@@ -451,8 +702,261 @@ impl<'a> IrSemnatic<'a> {
         Ok(())
     }
 
-    fn analyize_const(&mut self, _: &String, _: &Vec<u8>, _: &Loc, _: Linkage) -> Result<(), IrError> {
-        Ok(()) // what can go wrong on constants?
+    fn analaysiz_alloca<T>(&mut self, vars: &mut HashMap<String, TypeMetadata>, node: &Alloca<Var, T>, loc: Loc) -> Result<(), IrError> {
+        if vars.contains_key(&node.inner1.name) {
+            Err(IrError::DefinedTwice {
+                name: node.inner1.name.to_owned(), 
+                loc: loc
+            })?
+        }
+
+        vars.insert(node.inner1.name.to_owned(), node.inner1.ty);
+
+        Ok(())
+    }
+
+    fn analaysiz_load(&mut self, vars: &mut HashMap<String, TypeMetadata>, node: &Load<Var, Var, TypeMetadata>, loc: Loc) -> Result<(), IrError> {
+        if !vars.contains_key(&node.inner2.name) {
+            Err(IrError::Unkown { 
+                what: "variable".to_owned(), 
+                name: node.inner2.name.to_owned(), 
+                loc: loc.clone()
+            })?
+        }
+
+        if vars.contains_key(&node.inner1.name) {
+            Err(IrError::DefinedTwice {
+                name: node.inner1.name.to_owned(), 
+                loc: loc
+            })?
+        }
+
+        vars.insert(node.inner1.name.to_owned(), node.inner3);
+
+        Ok(())
+    }
+
+    fn analaysiz_store_var(&mut self, vars: &mut HashMap<String, TypeMetadata>, node: &Store<Var, Var>, loc: Loc) -> Result<(), IrError> {
+        if !vars.contains_key(&node.inner2.name) {
+            Err(IrError::Unkown { 
+                what: "variable".to_owned(), 
+                name: node.inner2.name.to_owned(), 
+                loc: loc.clone()
+            })?
+        }
+
+        self.analaysiz_store_ptr(vars, &node.inner1, loc)
+    }
+
+    fn analaysiz_store_ty(&mut self, vars: &mut HashMap<String, TypeMetadata>, node: &Store<Var, Type>, loc: Loc) -> Result<(), IrError> {
+        self.analaysiz_store_ptr(vars, &node.inner1, loc)
+    }
+
+    fn analaysiz_store_ptr(&mut self, vars: &mut HashMap<String, TypeMetadata>, ptr: &Var, loc: Loc) -> Result<(), IrError> {
+        if !vars.contains_key(&ptr.name) {
+            Err(IrError::Unkown { 
+                what: "variable".to_owned(), 
+                name: ptr.name.to_owned(), 
+                loc: loc
+            })?
+        }
+
+        Ok(())
+    }
+
+    fn analaysiz_operands(&mut self, vars: &HashMap<String, TypeMetadata>, ops: &[&Var], loc: Loc) -> Result<(), IrError> {
+        for op in ops {
+            if !vars.contains_key(&op.name) {
+                Err(IrError::Unkown { 
+                    what: "variable".to_owned(), 
+                    name: op.name.to_owned(), 
+                    loc: loc.clone()
+                })?
+            }
+        }
+
+        Ok(())
+    }
+
+    fn analaysiz_atomic(&mut self, vars: &mut HashMap<String, TypeMetadata>, ptr: &Var, ty: TypeMetadata, ops: &[&Var], out: Option<&Var>, loc: Loc) -> Result<(), IrError> {
+        if let Err(err) = verify_atomic(ptr, ty) {
+            Err(IrError::Boxed {
+                loc: loc.clone(),
+                err: Box::from(err),
+            })?
+        }
+
+        let ops = [ptr].into_iter().chain(ops.iter().copied()).collect::<Vec<_>>();
+        self.analaysiz_with_out(vars, &ops, out, loc)
+    }
+
+    fn analaysiz_with_out(&mut self, vars: &mut HashMap<String, TypeMetadata>, ops: &[&Var], out: Option<&Var>, loc: Loc) -> Result<(), IrError> {
+        self.analaysiz_operands(vars, ops, loc.clone())?;
+
+        if let Some(out) = out {
+            if vars.contains_key(&out.name) {
+                Err(IrError::DefinedTwice {
+                    loc: loc,
+                    name: out.name.to_owned(),
+                })?
+            }
+
+            vars.insert(out.name.to_owned(), out.ty);
+        }
+
+        Ok(())
+    }
+
+    fn analaysiz_inline_asm(&mut self, vars: &mut HashMap<String, TypeMetadata>, node: &InlineAsm, loc: Loc) -> Result<(), IrError> {
+        // the registers are checked by the backend as their names depend on the architecture
+        let ins = node.ins.iter().map(|(_, input)| input).collect::<Vec<_>>();
+        self.analaysiz_with_out(vars, &ins, node.out.as_ref().map(|(_, out)| out), loc)
+    }
+
+    fn analaysiz_with_overflow(&mut self, vars: &mut HashMap<String, TypeMetadata>, node: &WithOverflow, loc: Loc) -> Result<(), IrError> {
+        self.analaysiz_operands(vars, &[&node.ls, &node.rs], loc.clone())?;
+
+        for out in [&node.out, &node.overflow] {
+            if vars.contains_key(&out.name) {
+                Err(IrError::DefinedTwice {
+                    loc: loc.clone(),
+                    name: out.name.to_owned(),
+                })?
+            }
+
+            vars.insert(out.name.to_owned(), out.ty);
+        }
+
+        Ok(())
+    }
+
+    fn analaysiz_phi(&mut self, func: &String, vars: &mut HashMap<String, TypeMetadata>, node: &Phi, loc: Loc) -> Result<(), IrError> {
+        let (_, _, blocks) = self.func_sigs.get(func).unwrap();
+
+        for (block, _) in &node.recive_from_blocks {
+            if !blocks.contains(&block.name) {
+                Err(IrError::Unkown { 
+                    what: "block".to_owned(), 
+                    name: block.name.to_owned(), 
+                    loc: loc.clone()
+                })?
+            }
+        }
+
+        if vars.contains_key(&node.out.name) {
+            Err(IrError::DefinedTwice {
+                name: node.out.name.to_owned(), 
+                loc: loc
+            })?
+        }
+
+        vars.insert(node.out.name.to_owned(), node.out.ty);
+
+        Ok(())
+    }
+
+    fn analaysiz_switch(&mut self, func: &String, vars: &mut HashMap<String, TypeMetadata>, node: &Switch, loc: Loc) -> Result<(), IrError> {
+        let (_, _, blocks) = self.func_sigs.get(func).unwrap();
+
+        for block in std::iter::once(&node.default).chain(node.cases.iter().map(|(_, block)| block)) {
+            if !blocks.contains(&block.name) {
+                Err(IrError::Unkown { 
+                    what: "block".to_owned(), 
+                    name: block.name.to_owned(), 
+                    loc: loc.clone()
+                })?
+            }
+        }
+
+        if !vars.contains_key(&node.var.name) {
+            Err(IrError::Unkown { 
+                what: "variable".to_owned(), 
+                name: node.var.name.to_owned(), 
+                loc: loc
+            })?
+        }
+
+        Ok(())
+    }
+
+    fn analaysiz_select<T: SelectValue, U: SelectValue>(&mut self, vars: &mut HashMap<String, TypeMetadata>, node: &Select<T, U>, loc: Loc) -> Result<(), IrError> {
+        let used = std::iter::once(&node.cond)
+            .chain(node.yes.var())
+            .chain(node.no.var());
+
+        for var in used {
+            if !vars.contains_key(&var.name) {
+                Err(IrError::Unkown { 
+                    what: "variable".to_owned(), 
+                    name: var.name.to_owned(), 
+                    loc: loc.clone()
+                })?
+            }
+        }
+
+        if vars.contains_key(&node.out.name) {
+            Err(IrError::DefinedTwice {
+                name: node.out.name.to_owned(), 
+                loc: loc
+            })?
+        }
+
+        vars.insert(node.out.name.to_owned(), node.out.ty);
+
+        Ok(())
+    }
+
+    fn analaysiz_gep<T>(&mut self, vars: &mut HashMap<String, TypeMetadata>, node: &Gep<T>, index: Option<&Var>, loc: Loc) -> Result<(), IrError> {
+        for var in std::iter::once(&node.ptr).chain(index) {
+            if !vars.contains_key(&var.name) {
+                Err(IrError::Unkown { 
+                    what: "variable".to_owned(), 
+                    name: var.name.to_owned(), 
+                    loc: loc.clone()
+                })?
+            }
+        }
+
+        if vars.contains_key(&node.out.name) {
+            Err(IrError::DefinedTwice {
+                name: node.out.name.to_owned(), 
+                loc: loc
+            })?
+        }
+
+        vars.insert(node.out.name.to_owned(), node.out.ty);
+
+        Ok(())
+    }
+
+    fn analyize_const(&mut self, _: &String, _: &Vec<u8>, refs: &Vec<SymbolRef>, loc: &Loc, _: Linkage) -> Result<(), IrError> {
+        self.analyize_refs(refs, loc)
+    }
+
+    fn analyize_refs(&mut self, refs: &Vec<SymbolRef>, loc: &Loc) -> Result<(), IrError> {
+        for sym in refs {
+            if !self.func_sigs.contains_key(&sym.symbol) && !self.const_sigs.contains_key(&sym.symbol) {
+                Err(IrError::Unkown { 
+                    what: "symbol".to_owned(), 
+                    name: sym.symbol.to_owned(), 
+                    loc: loc.clone()
+                })?
+            }
+        }
+
+        Ok(())
+    }
+
+    /// bitwise operations, shifts, remainders and bit counting only work on integers
+    fn analaysiz_int_only(&self, node: &dyn Ir, ty: TypeMetadata, loc: &Loc) -> Result<(), IrError> {
+        if node.opcode().int_only() && ty.float() {
+            Err(IrError::Boxed {
+                loc: loc.to_owned(),
+                err: Box::from(VerifyError::IntOnlyOperation(ty)),
+            })?
+        }
+
+        Ok(())
     }
 }   
 
@@ -476,6 +980,8 @@ macro_rules! SemnaticImplMathVarTy {
                     })?
                 }
 
+                self.analaysiz_int_only(node, node.inner2.into(), &loc)?;
+
                 vars.insert(node.inner3.name.to_owned(), node.inner2.into());
                 
                 Ok(())
@@ -491,6 +997,10 @@ SemnaticImplMathVarTy!(analaysiz_or_var_ty,  Or );
 SemnaticImplMathVarTy!(analaysiz_and_var_ty, And);
 SemnaticImplMathVarTy!(analaysiz_mul_var_ty, Mul);
 SemnaticImplMathVarTy!(analaysiz_div_var_ty, Div);
+SemnaticImplMathVarTy!(analaysiz_rem_var_ty, Rem);
+SemnaticImplMathVarTy!(analaysiz_shl_var_ty, Shl);
+SemnaticImplMathVarTy!(analaysiz_lshr_var_ty, Lshr);
+SemnaticImplMathVarTy!(analaysiz_ashr_var_ty, Ashr);
 
 macro_rules! SemnaticImplMathTyTy {
     ($func:ident, $node:ident) => {
@@ -503,6 +1013,8 @@ macro_rules! SemnaticImplMathTyTy {
                     })?
                 }
 
+                self.analaysiz_int_only(node, node.inner1.into(), &loc)?;
+
                 vars.insert(node.inner3.name.to_owned(), node.inner1.into());
                 
                 Ok(())
@@ -518,6 +1030,10 @@ SemnaticImplMathTyTy!(analaysiz_or_ty_ty,  Or );
 SemnaticImplMathTyTy!(analaysiz_and_ty_ty, And);
 SemnaticImplMathTyTy!(analaysiz_mul_ty_ty, Mul);
 SemnaticImplMathTyTy!(analaysiz_div_ty_ty, Div);
+SemnaticImplMathTyTy!(analaysiz_rem_ty_ty, Rem);
+SemnaticImplMathTyTy!(analaysiz_shl_ty_ty, Shl);
+SemnaticImplMathTyTy!(analaysiz_lshr_ty_ty, Lshr);
+SemnaticImplMathTyTy!(analaysiz_ashr_ty_ty, Ashr);
 
 macro_rules! SemnaticImplMathVarVar {
     ($func:ident, $node:ident) => {
@@ -548,9 +1064,11 @@ macro_rules! SemnaticImplMathVarVar {
                     })?
                 }
 
-                let ty = if let Some(var) = vars.get(&node.inner2.name) { var } else { unreachable!() };
+                let ty = if let Some(var) = vars.get(&node.inner2.name) { *var } else { unreachable!() };
 
-                vars.insert(node.inner3.name.to_owned(), *ty);
+                self.analaysiz_int_only(node, ty, &loc)?;
+
+                vars.insert(node.inner3.name.to_owned(), ty);
                 
                 Ok(())
             }
@@ -564,4 +1082,73 @@ SemnaticImplMathVarVar!(analaysiz_xor_var_var, Xor);
 SemnaticImplMathVarVar!(analaysiz_or_var_var,  Or );
 SemnaticImplMathVarVar!(analaysiz_and_var_var, And);
 SemnaticImplMathVarVar!(analaysiz_mul_var_var, Mul);
-SemnaticImplMathVarVar!(analaysiz_div_var_var, Div);
\ No newline at end of file
+SemnaticImplMathVarVar!(analaysiz_div_var_var, Div);
+SemnaticImplMathVarVar!(analaysiz_rem_var_var, Rem);
+SemnaticImplMathVarVar!(analaysiz_shl_var_var, Shl);
+SemnaticImplMathVarVar!(analaysiz_lshr_var_var, Lshr);
+SemnaticImplMathVarVar!(analaysiz_ashr_var_var, Ashr);
+
+macro_rules! SemnaticImplUnaryVar {
+    ($func:ident, $node:ident) => {
+        impl<'a> IrSemnatic<'a> {
+            fn $func(&mut self, vars: &mut HashMap<String, TypeMetadata>, node: &$node<Var, Var>, loc: Loc) -> Result<(), IrError> {
+                if !vars.contains_key(&node.inner1.name) {
+                    Err(IrError::Unkown {
+                        loc: loc.to_owned(),
+                        name: node.inner1.name.to_owned(),
+
+                        what: "variable".into(),
+                    })?
+                }
+
+                if vars.contains_key(&node.inner2.name) {
+                    Err(IrError::DefinedTwice {
+                        loc: loc.to_owned(),
+                        name: node.inner2.name.to_owned(),
+                    })?
+                }
+
+                self.analaysiz_int_only(node, node.inner2.ty, &loc)?;
+
+                vars.insert(node.inner2.name.to_owned(), node.inner2.ty);
+                
+                Ok(())
+            }
+        }
+    };
+}
+
+SemnaticImplUnaryVar!(analaysiz_neg_var, Neg);
+SemnaticImplUnaryVar!(analaysiz_not_var, Not);
+SemnaticImplUnaryVar!(analaysiz_bswap_var, Bswap);
+SemnaticImplUnaryVar!(analaysiz_ctpop_var, Ctpop);
+SemnaticImplUnaryVar!(analaysiz_ctlz_var, Ctlz);
+SemnaticImplUnaryVar!(analaysiz_cttz_var, Cttz);
+
+macro_rules! SemnaticImplUnaryTy {
+    ($func:ident, $node:ident) => {
+        impl<'a> IrSemnatic<'a> {
+            fn $func(&mut self, vars: &mut HashMap<String, TypeMetadata>, node: &$node<Type, Var>, loc: Loc) -> Result<(), IrError> {
+                if vars.contains_key(&node.inner2.name) {
+                    Err(IrError::DefinedTwice {
+                        loc: loc.to_owned(),
+                        name: node.inner2.name.to_owned(),
+                    })?
+                }
+
+                self.analaysiz_int_only(node, node.inner1.into(), &loc)?;
+
+                vars.insert(node.inner2.name.to_owned(), node.inner1.into());
+                
+                Ok(())
+            }
+        }
+    };
+}
+
+SemnaticImplUnaryTy!(analaysiz_neg_ty, Neg);
+SemnaticImplUnaryTy!(analaysiz_not_ty, Not);
+SemnaticImplUnaryTy!(analaysiz_bswap_ty, Bswap);
+SemnaticImplUnaryTy!(analaysiz_ctpop_ty, Ctpop);
+SemnaticImplUnaryTy!(analaysiz_ctlz_ty, Ctlz);
+SemnaticImplUnaryTy!(analaysiz_cttz_ty, Cttz);
@@ -1,10 +1,12 @@
-use std::fmt::Display;
+use std::{cmp::Ordering, fmt::Display, hash::Hash};
 
 /// Stores a type and a value of that type
 /// 
 /// If you want an empty Type consider using `TypeMetadata`
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy)]
 pub enum Type {
+    /// Just an u8 with a value
+    u8(u8),
     /// Just an u16 with a value
     u16(u16),
     /// Just an u32 with a value
@@ -12,6 +14,8 @@ pub enum Type {
     /// Just an u64 with a value
     u64(u64),
 
+    /// Just an i8 with a value
+    i8(i8),
     /// Just an i16 with a value
     i16(i16),
     /// Just an i32 with a value
@@ -21,6 +25,14 @@ pub enum Type {
     /// 64Bit pointer
     ptr(i64),
 
+    /// A boolean (1Bit intenger)
+    bool(bool),
+
+    /// Just an f32 with a value
+    f32(f32),
+    /// Just an f64 with a value
+    f64(f64),
+
     /// Notype
     Void,
 }
@@ -28,6 +40,8 @@ pub enum Type {
 /// Stores type metadata (just the type without data)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TypeMetadata {
+    /// u8
+    u8,
     /// u16
     u16,
     /// u32
@@ -35,6 +49,8 @@ pub enum TypeMetadata {
     /// u64
     u64,
 
+    /// i8
+    i8,
     /// i16
     i16,
     /// i32
@@ -44,48 +60,171 @@ pub enum TypeMetadata {
     /// ptr
     ptr,
 
+    /// bool (i1)
+    bool,
+
+    /// f32
+    f32,
+    /// f64
+    f64,
+
     /// Notype
     Void,
 }
 
 impl Type {
     /// Returns the inner value
+    /// 
+    /// Floats are returned as their raw bits
     pub fn val(&self) -> u64 {
         match self {
+            Type::u8(val) => *val as u64,
             Type::u16(val) => *val as u64,
             Type::u32(val) => *val as u64,
             Type::u64(val) => *val as u64,
+            Type::i8(val) => *val as u64,
             Type::i16(val) => *val as u64,
             Type::i32(val) => *val as u64,
             Type::i64(val) => *val as u64,
             Type::ptr(adr) => *adr as u64,
+            Type::bool(val) => *val as u64,
+            Type::f32(val) => val.to_bits() as u64,
+            Type::f64(val) => val.to_bits(),
             Type::Void => 0,
         }
     }
 
+    /// Returns the inner value as a float
+    pub fn val_float(&self) -> f64 {
+        match self {
+            Type::f32(val) => *val as f64,
+            Type::f64(val) => *val,
+            Type::i8(val) => *val as f64,
+            Type::i16(val) => *val as f64,
+            Type::i32(val) => *val as f64,
+            Type::i64(val) => *val as f64,
+            Type::ptr(adr) => *adr as f64,
+            _ => self.val() as f64,
+        }
+    }
+
+    /// Returns the inner value formatted like it is written in the ir
+    pub fn fmt_val(&self) -> String {
+        match self {
+            Type::u8(val) => val.to_string(),
+            Type::u16(val) => val.to_string(),
+            Type::u32(val) => val.to_string(),
+            Type::u64(val) => val.to_string(),
+            Type::i8(val) => val.to_string(),
+            Type::i16(val) => val.to_string(),
+            Type::i32(val) => val.to_string(),
+            Type::i64(val) => val.to_string(),
+            Type::ptr(adr) => adr.to_string(),
+            Type::bool(val) => (*val as u8).to_string(),
+            Type::f32(val) => format!("{:?}", val),
+            Type::f64(val) => format!("{:?}", val),
+            Type::Void => String::new(),
+        }
+    }
+
     /// puts the intenger into a type respecting the type metadata
     pub fn from_int(ty: TypeMetadata, value: i64) -> Self {
         match ty {
+            TypeMetadata::u8 => Type::u8(value as u8),
             TypeMetadata::u16 => Type::u16(value as u16),
             TypeMetadata::u32 => Type::u32(value as u32),
             TypeMetadata::u64 => Type::u64(value as u64),
+            TypeMetadata::i8 => Type::i8(value as i8),
             TypeMetadata::i16 => Type::i16(value as i16),
             TypeMetadata::i32 => Type::i32(value as i32),
             TypeMetadata::i64 => Type::i64(value as i64),
             TypeMetadata::ptr => Type::ptr(value as i64),
+            TypeMetadata::bool => Type::bool(value & 1 == 1),
+            TypeMetadata::f32 => Type::f32(value as f32),
+            TypeMetadata::f64 => Type::f64(value as f64),
             TypeMetadata::Void => Type::Void,
         }
     }
+
+    /// puts the float into a type respecting the type metadata
+    pub fn from_float(ty: TypeMetadata, value: f64) -> Self {
+        match ty {
+            TypeMetadata::f32 => Type::f32(value as f32),
+            TypeMetadata::f64 => Type::f64(value),
+            _ => Type::from_int(ty, value as i64),
+        }
+    }
+
+    /// Returns the variant index and a bitwise representation of the value
+    /// (used for comparing and hashing)
+    fn key(&self) -> (u8, u64) {
+        let idx = match self {
+            Type::u8(_) => 0,
+            Type::u16(_) => 1,
+            Type::u32(_) => 2,
+            Type::u64(_) => 3,
+            Type::i8(_) => 4,
+            Type::i16(_) => 5,
+            Type::i32(_) => 6,
+            Type::i64(_) => 7,
+            Type::ptr(_) => 8,
+            Type::bool(_) => 9,
+            Type::f32(_) => 10,
+            Type::f64(_) => 11,
+            Type::Void => 12,
+        };
+
+        (idx, self.val())
+    }
+}
+
+impl PartialEq for Type {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Type {}
+
+impl Hash for Type {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl PartialOrd for Type {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Type {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Type::i8(ls), Type::i8(rs)) => ls.cmp(rs),
+            (Type::i16(ls), Type::i16(rs)) => ls.cmp(rs),
+            (Type::i32(ls), Type::i32(rs)) => ls.cmp(rs),
+            (Type::i64(ls), Type::i64(rs)) => ls.cmp(rs),
+            (Type::ptr(ls), Type::ptr(rs)) => ls.cmp(rs),
+            (Type::f32(ls), Type::f32(rs)) => ls.total_cmp(rs),
+            (Type::f64(ls), Type::f64(rs)) => ls.total_cmp(rs),
+            _ => self.key().cmp(&other.key()),
+        }
+    }
 }
 
 impl TypeMetadata {
     /// Returns the size of the type in bits
     pub fn bitSize(&self) -> usize {
         match self {
+            TypeMetadata::bool => 1,
+            TypeMetadata::u8 | TypeMetadata::i8 => 8,
             TypeMetadata::u16 | TypeMetadata::i16 => 16,
             TypeMetadata::u32 | TypeMetadata::i32 => 32,
             TypeMetadata::u64 | TypeMetadata::i64 => 64,
             TypeMetadata::ptr => 64,
+            TypeMetadata::f32 => 32,
+            TypeMetadata::f64 => 64,
             TypeMetadata::Void => 0,
         }
     }
@@ -93,7 +232,7 @@ impl TypeMetadata {
     /// Returns the size of the type in bytes
     pub fn byteSize(&self) -> usize {
         if *self != TypeMetadata::Void {
-            self.bitSize() / 8
+            self.bitSize().div_ceil(8)
         } else {
             0
         }
@@ -102,6 +241,7 @@ impl TypeMetadata {
     /// Returns if it is a signed type
     pub fn signed(&self) -> bool {
         match self {
+            TypeMetadata::i8 => true,
             TypeMetadata::i16 => true,
             TypeMetadata::i32 => true,
             TypeMetadata::i64 => true,
@@ -110,19 +250,31 @@ impl TypeMetadata {
         }
     }
 
+    /// Returns if it is a floating point type
+    pub fn float(&self) -> bool {
+        matches!(self, TypeMetadata::f32 | TypeMetadata::f64)
+    }
+
     /// returns the parsed typemetadata
     pub fn parse(string: String) -> Option<Self> {
         match string.as_str() {
+            "u8" => Some(TypeMetadata::u8),
             "u16" => Some(TypeMetadata::u16),
             "u32" => Some(TypeMetadata::u32),
             "u64" => Some(TypeMetadata::u64),
 
+            "i8" => Some(TypeMetadata::i8),
             "i16" => Some(TypeMetadata::i16),
             "i32" => Some(TypeMetadata::i32),
             "i64" => Some(TypeMetadata::i64),
 
             "ptr" => Some(TypeMetadata::ptr),
 
+            "bool" | "i1" => Some(TypeMetadata::bool),
+
+            "f32" => Some(TypeMetadata::f32),
+            "f64" => Some(TypeMetadata::f64),
+
             "void" => Some(TypeMetadata::Void),
 
             _ => None,
@@ -133,13 +285,18 @@ impl TypeMetadata {
 impl Display for Type {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", match &self {
+            Type::u8(i) => format!("u8 {}", i),
             Type::u16(i) => format!("u16 {}", i),
             Type::u32(i) => format!("u32 {}", i),
             Type::u64(i) => format!("u64 {}", i),
+            Type::i8(i) => format!("i8 {}", i),
             Type::i16(i) => format!("i16 {}", i),
             Type::i32(i) => format!("i32 {}", i),
             Type::i64(i) => format!("i64 {}", i),
             Type::ptr(adr) => format!("ptr {:#04x}", adr),
+            Type::bool(b) => format!("bool {}", *b as u8),
+            Type::f32(i) => format!("f32 {:?}", i),
+            Type::f64(i) => format!("f64 {:?}", i),
             Type::Void => format!("void"),
         })
     }
@@ -148,13 +305,18 @@ impl Display for Type {
 impl Display for TypeMetadata {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", match &self {
+            TypeMetadata::u8 => "u8",
             TypeMetadata::u16 => "u16",
             TypeMetadata::u32 => "u32",
             TypeMetadata::u64 => "u64",
+            TypeMetadata::i8 => "i8",
             TypeMetadata::i16 => "i16",
             TypeMetadata::i32 => "i32",
             TypeMetadata::i64 => "i64",
             TypeMetadata::ptr => "ptr",
+            TypeMetadata::bool => "bool",
+            TypeMetadata::f32 => "f32",
+            TypeMetadata::f64 => "f64",
             TypeMetadata::Void => "void",
         })
     }
@@ -163,13 +325,18 @@ impl Display for TypeMetadata {
 impl From<Type> for TypeMetadata {
     fn from(value: Type) -> Self {
         match value {
+            Type::u8(_) => TypeMetadata::u8,
             Type::u16(_) => TypeMetadata::u16,
             Type::u32(_) => TypeMetadata::u32,
             Type::u64(_) => TypeMetadata::u64,
+            Type::i8(_) => TypeMetadata::i8,
             Type::i16(_) => TypeMetadata::i16,
             Type::i32(_) => TypeMetadata::i32,
             Type::i64(_) => TypeMetadata::i64,
             Type::ptr(_) => TypeMetadata::ptr,
+            Type::bool(_) => TypeMetadata::bool,
+            Type::f32(_) => TypeMetadata::f32,
+            Type::f64(_) => TypeMetadata::f64,
             Type::Void => TypeMetadata::Void,
         }
     }
@@ -178,13 +345,18 @@ impl From<Type> for TypeMetadata {
 impl From<TypeMetadata> for Type {
     fn from(value: TypeMetadata) -> Self {
         match value {
+            TypeMetadata::u8 => Type::u8(0),
             TypeMetadata::u16 => Type::u16(0),
             TypeMetadata::u32 => Type::u32(0),
             TypeMetadata::u64 => Type::u64(0),
+            TypeMetadata::i8 => Type::i8(0),
             TypeMetadata::i16 => Type::i16(0),
             TypeMetadata::i32 => Type::i32(0),
             TypeMetadata::i64 => Type::i64(0),
             TypeMetadata::ptr => Type::ptr(0),
+            TypeMetadata::bool => Type::bool(false),
+            TypeMetadata::f32 => Type::f32(0.0),
+            TypeMetadata::f64 => Type::f64(0.0),
             TypeMetadata::Void => Type::Void,
         }
     }
@@ -4,7 +4,7 @@ mod exe;
 
 pub use wrapper::{
     ObjectBuilder,
-    Decl, Link, Linkage,
+    Decl, Link, Linkage, Visibility, TlsModel,
 };
 pub use dll::*;
 pub use exe::*;
\ No newline at end of file
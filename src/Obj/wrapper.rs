@@ -1,7 +1,7 @@
 use gimli::write::{Address, DwarfUnit, EndianVec, FileId, Range, RelocateWriter};
 use gimli::LittleEndian;
-use object::write::{Object, Relocation, SectionId, Symbol, SymbolId, SymbolSection};
-use object::{Architecture, BinaryFormat, Endianness, FileFlags, RelocationEncoding, RelocationFlags, RelocationKind, SectionKind, SymbolFlags, SymbolKind, SymbolScope};
+use object::write::{Comdat, Object, Relocation, SectionId, Symbol, SymbolId, SymbolSection};
+use object::{Architecture, BinaryFormat, ComdatKind, Endianness, FileFlags, RelocationEncoding, RelocationFlags, RelocationKind, SectionFlags, SectionKind, SymbolFlags, SymbolKind, SymbolScope};
 
 use crate::debug::DebugRegistry;
 use crate::prelude::Triple;
@@ -15,6 +15,7 @@ use std::error::Error;
 enum ObjectError {
     UnsupportedArch(Arch),
     DefWithoutDecl(String),
+    UnsupportedThreadLocal(BinaryFormat),
 }
 
 impl std::fmt::Display for ObjectError {
@@ -22,6 +23,7 @@ impl std::fmt::Display for ObjectError {
         write!(f, "{}", match self {
             ObjectError::UnsupportedArch(arch) => format!("unsupported architecture for writing to object files: {:?}", arch),
             ObjectError::DefWithoutDecl(name) => format!("definition without an corresponding decleration: {}", name),
+            ObjectError::UnsupportedThreadLocal(format) => format!("thread local storage isn't supported for {:?} object files", format),
         })
     }
 }
@@ -70,6 +72,10 @@ pub enum Decl {
     Data,
     /// A constant data
     Constant,
+    /// A zero initialized mutable data (placed in .bss)
+    ZeroedData,
+    /// A rarely executed function (placed in .text.unlikely)
+    ColdFunction,
 }
 
 /// Links from one symbol to another
@@ -96,6 +102,11 @@ pub enum Linkage {
     Extern,
     /// Only aviable in the object file
     Internal,
+    /// Can be seen outside of the object file, but another (non weak) definition takes precedence
+    Weak,
+    /// Can be seen outside of the object file, the linker only keeps one of the definitions with the same name
+    /// (placed in its own COMDAT group)
+    LinkOnce,
 }
 
 impl Display for Linkage {
@@ -104,6 +115,50 @@ impl Display for Linkage {
             Linkage::External => "",
             Linkage::Extern => "extern",
             Linkage::Internal => "local",
+            Linkage::Weak => "weak",
+            Linkage::LinkOnce => "linkonce",
+        })
+    }
+}
+
+/// The visibility of the target symbol when the object file gets linked into a shared library
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Visibility {
+    /// Exported from the shared library (can be preempted)
+    #[default]
+    Default,
+    /// Not exported from the shared library
+    Hidden,
+    /// Exported from the shared library, but can't be preempted
+    Protected,
+}
+
+impl Display for Visibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            Visibility::Default => "default",
+            Visibility::Hidden => "hidden",
+            Visibility::Protected => "protected",
+        })
+    }
+}
+
+/// How the adress of a thread local symbol is computed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TlsModel {
+    /// The symbol is defined in the executable (its offset to the thread pointer is known at link time)
+    #[default]
+    LocalExec,
+    /// The symbol is defined in the executable or a shared library which is loaded at startup
+    /// (its offset to the thread pointer is read from the global offset table)
+    InitialExec,
+}
+
+impl Display for TlsModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            TlsModel::LocalExec => "localexec",
+            TlsModel::InitialExec => "initialexec",
         })
     }
 }
@@ -114,8 +169,18 @@ impl Display for Linkage {
 pub struct ObjectBuilder {
     pub(crate) defines: BTreeMap<String, Vec<u8>>,
     pub(crate) links: Vec<Link>,
+    /// links which place the absolute address of their destination into data
+    pub(crate) adr_links: Vec<Link>,
 
     pub(crate) decls: Vec<(String, Decl, Linkage)>,
+    /// the visibility of the symbols (symbols which aren't in here have the default visibility)
+    pub(crate) visibilities: BTreeMap<String, Visibility>,
+    /// the thread local symbols (they are placed in .tdata/.tbss and links to them use the tls relocations of the model)
+    pub(crate) thread_locals: BTreeMap<String, TlsModel>,
+    /// the sections of the symbols which aren't placed in the default section of their decl
+    pub(crate) sections: BTreeMap<String, String>,
+    /// the alignment of the symbols (symbols which aren't in here are aligned to 1 byte)
+    pub(crate) aligns: BTreeMap<String, u64>,
 
     pub(crate) triple: Triple,
 
@@ -132,7 +197,12 @@ impl ObjectBuilder {
             defines: BTreeMap::new(),
 
             links: vec![],
+            adr_links: vec![],
             decls: vec![],
+            visibilities: BTreeMap::new(),
+            thread_locals: BTreeMap::new(),
+            sections: BTreeMap::new(),
+            aligns: BTreeMap::new(),
 
             flags: None,
 
@@ -154,6 +224,26 @@ impl ObjectBuilder {
         self.decls.push((decl.0.to_string(), decl.1, decl.2));
     }
 
+    /// Sets the visibility of the symbol
+    pub fn visibility(&mut self, name: &str, visibility: Visibility) {
+        self.visibilities.insert(name.to_string(), visibility);
+    }
+
+    /// Sets that the symbol is thread local and which model is used to access it
+    pub fn thread_local(&mut self, name: &str, model: TlsModel) {
+        self.thread_locals.insert(name.to_string(), model);
+    }
+
+    /// Places the symbol in the section with the given name (on mach-o the name is written as `segment,section`)
+    pub fn section(&mut self, name: &str, section: &str) {
+        self.sections.insert(name.to_string(), section.to_string());
+    }
+
+    /// Sets the alignment of the symbol (needs to be a power of two)
+    pub fn align(&mut self, name: &str, align: u64) {
+        self.aligns.insert(name.to_string(), align);
+    }
+
     /// Defines a symbol
     pub fn define(&mut self, name: &str, data: Vec<u8>) {
         self.defines.insert(name.to_string(), data);
@@ -164,6 +254,12 @@ impl ObjectBuilder {
         self.links.push(link);
     }
 
+    /// Places the absolute address of the destination (plus the addend) at the offset `at` into the data of the source
+    /// (e.g: the entries of vtables)
+    pub fn link_adr(&mut self, link: Link) {
+        self.adr_links.push(link);
+    }
+
     /// Writes the object file into the the specified file
     pub fn emit(&self, file: File, debug: Option<DebugRegistry>) -> Result<(), Box<dyn Error>> {
         let align = 1;
@@ -231,6 +327,25 @@ impl ObjectBuilder {
         let secText = obj.add_section(vec![], ".text".as_bytes().to_vec(), SectionKind::Text);
         let secData = obj.add_section(vec![], ".data".as_bytes().to_vec(), SectionKind::Data);
         let secConsts = obj.add_section(vec![], ".rodata".as_bytes().to_vec(), SectionKind::ReadOnlyData);
+        let secBss = obj.add_section(vec![], ".bss".as_bytes().to_vec(), SectionKind::UninitializedData);
+        let secCold = obj.add_section(vec![], ".text.unlikely".as_bytes().to_vec(), SectionKind::Text);
+        // constants which hold addresses need to be writable by the dynamic linker before they get read only
+        let secRelRo = obj.add_section(vec![], ".data.rel.ro".as_bytes().to_vec(), SectionKind::ReadOnlyDataWithRel);
+
+        if !self.thread_locals.is_empty() && obj.format() != BinaryFormat::Elf {
+            Err( ObjectError::UnsupportedThreadLocal(obj.format()) )?
+        }
+
+        // the tls sections are only added if they are used, because they make the linker create the tls segment
+        let defined_tls = |zeroed: bool| self.decls.iter().any(|(name, decl, link)| {
+            *link != Linkage::Extern && self.thread_locals.contains_key(name) && (*decl == Decl::ZeroedData) == zeroed
+        });
+
+        let secTData = if defined_tls(false) { Some(obj.add_section(vec![], ".tdata".as_bytes().to_vec(), SectionKind::Tls)) } else { None };
+        let secTBss = if defined_tls(true) { Some(obj.add_section(vec![], ".tbss".as_bytes().to_vec(), SectionKind::UninitializedTls)) } else { None };
+
+        // the sections which were requested by the symbols
+        let mut custom_sections: BTreeMap<String, SectionId> = BTreeMap::new();
 
         let mut syms: BTreeMap<String, (Option<SectionId>, Option</*offsest*/u64>, SymbolId, Decl, /*size*/u64, /*link*/Linkage)> = BTreeMap::new();
 
@@ -254,6 +369,106 @@ impl ObjectBuilder {
             let decl = decl.unwrap();
             let link = link.unwrap();
 
+            let relocated = self.adr_links.iter().any(|adr| adr.from == name);
+
+            let visibility = self.visibilities.get(&name).copied().unwrap_or_default();
+
+            let tls = self.thread_locals.contains_key(&name);
+
+            let format = obj.format();
+
+            // linkonce symbols are placed in their own section which is the COMDAT group
+            // (coff doesn't support weak definitions, so they are handled like linkonce symbols)
+            let comdat = match link {
+                Linkage::LinkOnce => format != BinaryFormat::MachO,
+                Linkage::Weak => format == BinaryFormat::Coff,
+                _ => false,
+            };
+
+            let weak = matches!(link, Linkage::Weak | Linkage::LinkOnce) && format != BinaryFormat::Coff;
+
+            let custom = self.sections.get(&name);
+
+            let section = if comdat || custom.is_some() {
+                let (base, mut kind) = match decl {
+                    Decl::ZeroedData if tls => (".tbss", SectionKind::UninitializedTls),
+                    _ if tls => (".tdata", SectionKind::Tls),
+                    Decl::Function => (".text", SectionKind::Text),
+                    Decl::Data => (".data", SectionKind::Data),
+                    Decl::Constant if relocated => (".data.rel.ro", SectionKind::ReadOnlyDataWithRel),
+                    Decl::Constant => (".rodata", SectionKind::ReadOnlyData),
+                    Decl::ZeroedData => (".bss", SectionKind::UninitializedData),
+                    Decl::ColdFunction => (".text.unlikely", SectionKind::Text),
+                };
+
+                // mach-o sections are written as `segment,section`
+                let (segment, base) = match custom.and_then(|custom| custom.split_once(',')) {
+                    Some((segment, section)) if format == BinaryFormat::MachO => (segment, section),
+                    _ => ("", custom.map(String::as_str).unwrap_or(base)),
+                };
+
+                // the loader calls the function pointers in the init/fini arrays
+                if format == BinaryFormat::Elf {
+                    if base.starts_with(".init_array") {
+                        kind = SectionKind::Elf(object::elf::SHT_INIT_ARRAY);
+                    } else if base.starts_with(".fini_array") {
+                        kind = SectionKind::Elf(object::elf::SHT_FINI_ARRAY);
+                    } else if base.starts_with(".preinit_array") {
+                        kind = SectionKind::Elf(object::elf::SHT_PREINIT_ARRAY);
+                    }
+                }
+
+                let elf_flags = match kind {
+                    SectionKind::Text => object::elf::SHF_ALLOC | object::elf::SHF_EXECINSTR,
+                    SectionKind::ReadOnlyData => object::elf::SHF_ALLOC,
+                    SectionKind::Tls | SectionKind::UninitializedTls => object::elf::SHF_ALLOC | object::elf::SHF_WRITE | object::elf::SHF_TLS,
+                    _ => object::elf::SHF_ALLOC | object::elf::SHF_WRITE,
+                };
+
+                if comdat {
+                    let name = if format == BinaryFormat::Coff { format!("{}${}", base, name) } else { format!("{}.{}", base, name) };
+
+                    let section = obj.add_section(segment.as_bytes().to_vec(), name.into_bytes(), kind);
+
+                    if format == BinaryFormat::Elf {
+                        // the members of COMDAT groups need to be flagged
+                        obj.section_mut(section).flags = SectionFlags::Elf { sh_flags: (elf_flags | object::elf::SHF_GROUP) as u64 };
+                    }
+
+                    section
+                } else if let Some(section) = custom_sections.get(base) {
+                    *section
+                } else {
+                    let section = obj.add_section(segment.as_bytes().to_vec(), base.as_bytes().to_vec(), kind);
+
+                    if let SectionKind::Elf(_) = kind {
+                        obj.section_mut(section).flags = SectionFlags::Elf { sh_flags: elf_flags as u64 };
+                    }
+
+                    custom_sections.insert(base.to_owned(), section);
+
+                    section
+                }
+            } else {
+                match decl {
+                    Decl::ZeroedData if tls => secTBss.expect("the .tbss section should exist"),
+                    _ if tls => secTData.expect("the .tdata section should exist"),
+                    Decl::Function => secText,
+                    Decl::Data => secData,
+                    Decl::Constant if relocated => secRelRo,
+                    Decl::Constant => secConsts,
+                    Decl::ZeroedData => secBss,
+                    Decl::ColdFunction => secCold,
+                }
+            };
+
+            let kind = match decl {
+                _ if tls => SymbolKind::Tls,
+                Decl::Function | Decl::ColdFunction => SymbolKind::Text,
+                Decl::Data | Decl::ZeroedData => SymbolKind::Data,
+                Decl::Constant => SymbolKind::Label,
+            };
+
             let sym = obj.add_symbol(Symbol {
                 name: name.clone().as_bytes().to_vec(),
                 value: 0,
@@ -264,33 +479,40 @@ impl ObjectBuilder {
                         0
                     }
                 },
-                kind: {
-                    match decl {
-                        Decl::Function => SymbolKind::Text,
-                        Decl::Data => SymbolKind::Data,
-                        Decl::Constant => SymbolKind::Label,
-                    }
-                },
+                kind: kind,
                 scope: {
                     match link {
                         Linkage::Extern => SymbolScope::Dynamic,
-                        Linkage::External => SymbolScope::Linkage,
                         Linkage::Internal => SymbolScope::Compilation,
+                        _ if visibility == Visibility::Hidden => SymbolScope::Linkage,
+                        _ => SymbolScope::Dynamic,
                     }
                 },
-                weak: false,
+                weak: weak,
                 section: {
                     if *link != Linkage::Extern {
-                        match decl {
-                            Decl::Function => SymbolSection::Section(secText),
-                            Decl::Data => SymbolSection::Section(secData),
-                            Decl::Constant => SymbolSection::Section(secConsts),
-                        }
+                        SymbolSection::Section(section)
                     } else {
                         SymbolSection::Undefined
                     }
                 },
-                flags: SymbolFlags::None,
+                flags: {
+                    // only elf has protected symbols
+                    if visibility == Visibility::Protected && format == BinaryFormat::Elf && *link != Linkage::Internal {
+                        let bind = if weak { object::elf::STB_WEAK } else { object::elf::STB_GLOBAL };
+
+                        let ty = match kind {
+                            SymbolKind::Text => object::elf::STT_FUNC,
+                            SymbolKind::Data => object::elf::STT_OBJECT,
+                            SymbolKind::Tls => object::elf::STT_TLS,
+                            _ => object::elf::STT_NOTYPE,
+                        };
+
+                        SymbolFlags::Elf { st_info: (bind << 4) + ty, st_other: object::elf::STV_PROTECTED }
+                    } else {
+                        SymbolFlags::None
+                    }
+                },
             });
 
             /*let def_section = match decl {
@@ -301,13 +523,25 @@ impl ObjectBuilder {
 
 
             if *link != Linkage::Extern {
+                let align = self.aligns.get(&name).copied().unwrap_or(align);
+
                 let def_offset = match decl {
-                    Decl::Function => obj.add_symbol_data(sym, secText, &data, align),
-                    Decl::Data => obj.add_symbol_data(sym, secData, &data, align),
-                    Decl::Constant => obj.add_symbol_data(sym, secConsts, &data, align),
+                    Decl::ZeroedData => obj.add_symbol_bss(sym, section, data.len() as u64, align),
+                    _ => obj.add_symbol_data(sym, section, &data, align),
                 };
+
+                if comdat {
+                    // coff needs the section symbol to hold the selection of the COMDAT
+                    obj.section_symbol(section);
+
+                    obj.add_comdat(Comdat {
+                        kind: ComdatKind::Any,
+                        symbol: sym,
+                        sections: vec![section],
+                    });
+                }
     
-                syms.insert(name.clone(), (None, Some(def_offset), sym, *decl, data.len() as u64, *link));
+                syms.insert(name.clone(), (Some(section), Some(def_offset), sym, *decl, data.len() as u64, *link));
             } else {
                 syms.insert(name.clone(), (None, None, sym, *decl, 0, *link));
             }
@@ -328,8 +562,9 @@ impl ObjectBuilder {
                 size: 0,
                 kind: {
                     match decl {
-                        Decl::Function => SymbolKind::Text,
-                        Decl::Data => SymbolKind::Data,
+                        _ if self.thread_locals.contains_key(name) => SymbolKind::Tls,
+                        Decl::Function | Decl::ColdFunction => SymbolKind::Text,
+                        Decl::Data | Decl::ZeroedData => SymbolKind::Data,
                         Decl::Constant => SymbolKind::Label,
                     }
                 },
@@ -343,30 +578,58 @@ impl ObjectBuilder {
         }
 
         for link in &self.links {
-            let (_, off, _, _, _, _) = syms.get(&link.from).expect("expectd valid link source");
-            let (_, _, to_sym, ty, _, _) = syms.get(&link.to).expect("expected valid link destination");
+            let (section, off, _, from_ty, _, _) = syms.get(&link.from).expect("expectd valid link source");
+            let (_, _, to_sym, _, _, _) = syms.get(&link.to).expect("expected valid link destination");
+
+            let off = if let Some(off) = off { *off } else { 0 };
+            let section = section.expect("expected the link source to be defined");
+
+            if !matches!(from_ty, Decl::Function | Decl::ColdFunction) {
+                // links out of data (e.g: jump tables) are relative to the position of the link
+                obj.add_relocation(section, Relocation {
+                    offset: link.at as u64 + off,
+                    symbol: to_sym.to_owned(),
+                    addend: link.addend,
+                    flags: RelocationFlags::Generic { 
+                        kind: RelocationKind::Relative, 
+                        encoding: RelocationEncoding::Generic, 
+                        size: 32, 
+                    },
+                })?;
+
+                continue;
+            }
 
-            let mut addend = 0;
             let mut offset = 0;
 
             if self.triple.getCallConv() == Ok(CallConv::WindowsFastCall) {
-                addend = match ty {
-                    Decl::Function => 0,
-                    _ => -1,
-                };
                 offset = -3;
             } else if self.triple.getCallConv() == Ok(CallConv::SystemV) {
-                addend = match ty {
-                    Decl::Function => 0,
-                    _ => -1,
-                };
                 offset = -3;
             }
 
-            obj.add_relocation(secText, Relocation {
-                offset: (link.at as i64 + offset) as u64 + {if let Some(off) = off { *off } else { 0 }},
+            if let Some(model) = self.thread_locals.get(&link.to) {
+                // local exec: the offset to the thread pointer, initial exec: the got entry which holds the offset
+                let r_type = match (self.triple.arch, model) {
+                    (Target::Arch::X86_64, TlsModel::LocalExec) => object::elf::R_X86_64_TPOFF32,
+                    (Target::Arch::X86_64, TlsModel::InitialExec) => object::elf::R_X86_64_GOTTPOFF,
+                    (arch, _) => Err( ObjectError::UnsupportedArch(arch) )?,
+                };
+
+                obj.add_relocation(section, Relocation {
+                    offset: (link.at as i64 + offset) as u64 + off,
+                    symbol: to_sym.to_owned(),
+                    addend: link.addend,
+                    flags: RelocationFlags::Elf { r_type: r_type },
+                })?;
+
+                continue;
+            }
+
+            obj.add_relocation(section, Relocation {
+                offset: (link.at as i64 + offset) as u64 + off,
                 symbol: to_sym.to_owned(),
-                addend: link.addend + addend,
+                addend: link.addend,
                 flags: RelocationFlags::Generic { 
                     kind: RelocationKind::PltRelative, 
                     encoding: {
@@ -383,6 +646,31 @@ impl ObjectBuilder {
             })?;
         }
 
+        for link in &self.adr_links {
+            let (section, off, _, from_ty, _, _) = syms.get(&link.from).expect("expectd valid link source");
+            let (_, _, to_sym, _, _, _) = syms.get(&link.to).expect("expected valid link destination");
+
+            let off = if let Some(off) = off { *off } else { 0 };
+
+            if *from_ty == Decl::ZeroedData {
+                panic!("zero initialized data can't hold addresses");
+            }
+
+            let section = section.expect("expected the link source to be defined");
+
+            // R_X86_64_64 / IMAGE_REL_AMD64_ADDR64 on x64
+            obj.add_relocation(section, Relocation {
+                offset: link.at as u64 + off,
+                symbol: to_sym.to_owned(),
+                addend: link.addend,
+                flags: RelocationFlags::Generic { 
+                    kind: RelocationKind::Absolute, 
+                    encoding: RelocationEncoding::Generic, 
+                    size: 64, 
+                },
+            })?;
+        }
+
         if let Some(debug) = debug {
             if self.debug {
                 self.emit_dwarf(&mut obj, &syms, debug)?;
@@ -417,7 +705,7 @@ impl ObjectBuilder {
         let subprogram = dwarf.unit.add(dwarf.unit.root(), gimli::DW_TAG_subprogram);
         let entry = dwarf.unit.get_mut(subprogram);
 
-        if *link == Linkage::External {
+        if *link != Linkage::Internal {
             entry.set(gimli::DW_AT_external, AttributeValue::Flag(true));
         }
 
@@ -462,7 +750,7 @@ impl ObjectBuilder {
         let mut range_vec = vec![];
 
         for (_, (_, _, id, decl, size, link)) in syms {
-            if *decl == Decl::Function && (*link == Linkage::External || *link == Linkage::Internal) {
+            if *decl == Decl::Function && *link != Linkage::Extern {
                 range_vec.push( self.emit_range(id, *size) );
             }
         }
@@ -500,7 +788,7 @@ impl ObjectBuilder {
         let file = dwarf.unit.line_program.add_file(file_string, dir_id, None);
 
         for (name, (_, _, id, decl, size, link)) in syms {
-            if *decl == Decl::Function && (*link == Linkage::External || *link == Linkage::Internal) {
+            if *decl == Decl::Function && *link != Linkage::Extern {
                 self.emit_debug_info(&mut dwarf, name, link, id, *size, &debug, file);
             }
         }
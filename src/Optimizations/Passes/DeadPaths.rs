@@ -0,0 +1,110 @@
+use crate::{prelude::*, Optimizations::Pass};
+
+/// ## Pass PruneDeadPaths <br>
+/// Removes code paths which end in an unreachable node <br>
+/// For example: <br>
+///  * The nodes after an unreachable node get removed
+///  * The nodes after a call to a noreturn function get replaced by an unreachable node
+///  * A conditional branch to a block which always runs into unreachable becomes a branch to the other block
+///  * The cases of a switch which always run into unreachable branch to the default block
+///  * Unreachable blocks which aren't branched to anymore get removed
+pub struct PruneDeadPaths {}
+
+/// Creates a new PruneDeadPaths pass which is heap allocated
+pub fn PruneDeadPaths() -> Box<PruneDeadPaths> {
+    Box::from( PruneDeadPaths {} )
+}
+
+/// Returns if the block can never be executed (it always runs into unreachable)
+///
+/// Blocks with calls aren't dead, because the called function could never return (like `exit`)
+fn is_dead(block: &Block) -> bool {
+    let calls = block.nodes.iter().any(|node| matches!(node.opcode(), Opcode::Call | Opcode::CallIndirect));
+
+    !calls && block.nodes.last().is_some_and(|node| node.opcode() == Opcode::Unreachable)
+}
+
+impl Pass for PruneDeadPaths {
+    fn run(&self, block: &mut Block) {
+        // a noreturn function never returns to the call
+        if let Some(pos) = block.nodes.iter().position(|node| {
+            node.opcode() == Opcode::Call && node.callee().is_some_and(|callee| callee.has_attr(FunctionAttr::NoReturn))
+        }) {
+            block.nodes.truncate(pos + 1);
+            block.nodes.push(Unreachable::new());
+        }
+
+        // everything after an unreachable node can't be executed
+        if let Some(pos) = block.nodes.iter().position(|node| node.opcode() == Opcode::Unreachable) {
+            block.nodes.truncate(pos + 1);
+        }
+    }
+
+    fn run_func(&self, func: &mut Function) {
+        for block in &mut func.blocks {
+            self.run(block);
+        }
+
+        // pruning a branch can make its block dead, so this runs until nothing changes anymore
+        loop {
+            // the entry block is always executed
+            let dead = func.blocks.iter().skip(1)
+                .filter(|block| is_dead(block))
+                .map(|block| block.name.to_owned())
+                .collect::<Vec<String>>();
+
+            if dead.is_empty() {
+                return;
+            }
+
+            let mut changed = false;
+
+            for block in &mut func.blocks {
+                let Some(node) = block.nodes.last_mut() else { continue };
+
+                match node.opcode() {
+                    Opcode::BrCond if node.successors().iter().any(|target| dead.contains(&target.name)) => {
+                        let live = node.successors().into_iter().find(|target| !dead.contains(&target.name)).cloned();
+
+                        *node = match live {
+                            Some(live) => Br::new(Box::from(live)),
+                            None => Unreachable::new(),
+                        };
+                        changed = true;
+                    },
+                    Opcode::Switch => {
+                        // the first successor is the default block which also gets every value of a dead case
+                        let mut successors = node.successors_mut();
+                        let default = successors[0].clone();
+
+                        for case in successors.iter_mut().skip(1) {
+                            if dead.contains(&case.name) && case.name != default.name {
+                                **case = default.clone();
+                                changed = true;
+                            }
+                        }
+                    },
+                    _ => {},
+                }
+            }
+
+            // dead blocks can only be removed if nothing branches or falls through to them
+            let cfg = func.cfg();
+
+            let removable = func.blocks.iter()
+                .enumerate()
+                .map(|(index, block)| index != 0 && dead.contains(&block.name) && cfg.predecessors(index).is_empty())
+                .collect::<Vec<bool>>();
+
+            if !changed && !removable.contains(&true) {
+                return;
+            }
+
+            let mut index = 0;
+            func.blocks.retain(|_| {
+                index += 1;
+                !removable[index - 1]
+            });
+        }
+    }
+}
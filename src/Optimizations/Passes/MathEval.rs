@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use crate::{prelude::*, Optimizations::Pass};
 
 /// ## Pass InlineConstValue <br>
@@ -9,84 +11,224 @@ pub fn PreComputeValue() -> Box<PreComputeValue> {
     Box::from( PreComputeValue {} )
 }
 
+/// Computes the result of an operation on two constants.
+///
+/// Returns `None` if the operation can't be computed for the type (e.g: xor on floats)
+fn eval(ty: TypeMetadata, ls: Type, rs: Type, int: fn(u64, u64) -> u64, float: Option<fn(f64, f64) -> f64>) -> Option<Type> {
+    if ty.float() {
+        let float = float?;
+        return Some(Type::from_float(ty, float(ls.val_float(), rs.val_float())));
+    }
+
+    let res = int(ls.val(), rs.val());
+
+    Some(match ty {
+        TypeMetadata::u8 => Type::u8(res as u8),
+        TypeMetadata::u16 => Type::u16(res as u16),
+        TypeMetadata::u32 => Type::u32(res as u32),
+        TypeMetadata::u64 => Type::u64(res as u64),
+        TypeMetadata::i8 => Type::i8(res as i8),
+        TypeMetadata::i16 => Type::i16(res as i16),
+        TypeMetadata::i32 => Type::i32(res as i32),
+        TypeMetadata::i64 => Type::i64(res as i64),
+        TypeMetadata::ptr => Type::ptr(res as i64),
+        TypeMetadata::bool => Type::bool(res & 1 == 1),
+        TypeMetadata::f32 | TypeMetadata::f64 => unreachable!(),
+        TypeMetadata::Void => Type::Void,
+    })
+}
+
+/// Computes the result of a shift of a constant.
+///
+/// The shift amount is masked like the cpu does it (to 5 bits, or 6 bits for 64 bit types).
+/// The shift function gets the value, the bit size of the type and the shift amount
+fn eval_shift(ty: TypeMetadata, ls: Type, rs: Type, shift: fn(u64, u32, u32) -> u64) -> Option<Type> {
+    if ty.float() || ty == TypeMetadata::Void {
+        return None;
+    }
+
+    let bits = ty.bitSize() as u32;
+    let amount = rs.val() as u32 & if bits > 32 { 63 } else { 31 };
+
+    Some(Type::from_int(ty, shift(ls.val(), bits, amount) as i64))
+}
+
+/// Computes the result of a bit manipulation of a constant.
+///
+/// The bit function gets the value (zero extended from its bit size) and the bit size of the type
+fn eval_bits(ty: TypeMetadata, val: Type, bits: fn(u64, u32) -> u64) -> Option<Type> {
+    if ty.float() || ty == TypeMetadata::Void {
+        return None;
+    }
+
+    let size = ty.bitSize() as u32;
+    let val = val.val() & (u64::MAX >> (64 - size));
+
+    Some(Type::from_int(ty, bits(val, size) as i64))
+}
+
+/// Computes the result of an integer division or remainder of two constants.
+///
+/// Returns `None` for a division by zero or an overflowing signed division
+fn eval_div(ty: TypeMetadata, ls: Type, rs: Type, signed: fn(i64, i64) -> Option<i64>, unsigned: fn(u64, u64) -> Option<u64>) -> Option<Type> {
+    let res = if ty.signed() {
+        let (ls, rs) = (ls.val() as i64, rs.val() as i64);
+
+        // the cpu traps on MIN / -1
+        if ls == i64::MIN >> (64 - ty.bitSize()) && rs == -1 {
+            return None;
+        }
+
+        signed(ls, rs)?
+    } else {
+        unsigned(ls.val(), rs.val())? as i64
+    };
+
+    Some(Type::from_int(ty, res))
+}
+
+/// Chooses the value of the select node if the condition is a known constant
+/// (or if both values are the same constant)
+fn eval_select(consts: &HashMap<String, Type>, node: &dyn Ir) -> Option<Box<dyn Ir>> {
+    let [cond, yes, no] = node.operands()[..] else { return None };
+
+    let value = match cond.var().and_then(|cond| consts.get(&cond.name)) {
+        Some(cond) if cond.val() & 1 == 1 => yes,
+        Some(_) => no,
+        None if yes.constant().is_some() && yes.constant() == no.constant() => yes,
+        None => return None,
+    };
+
+    let out = node.result()?.clone();
+
+    Some(match value {
+        Operand::Var(var) => Assign::new(out, var.clone()),
+        Operand::Const(constant) => Assign::new(out, *constant),
+    })
+}
+
+/// Computes the result of the node if all of its operands are constants
+fn eval_node(node: &dyn Ir) -> Option<Type> {
+    let ty = node.result()?.ty;
+
+    let operands = node.operands().iter()
+        .map(Operand::constant)
+        .collect::<Option<Vec<Type>>>()?;
+
+    match (node.opcode(), operands.as_slice()) {
+        (Opcode::Add, &[ls, rs]) => eval(ty, ls, rs, u64::wrapping_add, Some(|ls, rs| ls + rs)),
+        (Opcode::Sub, &[ls, rs]) => eval(ty, ls, rs, u64::wrapping_sub, Some(|ls, rs| ls - rs)),
+        (Opcode::Mul, &[ls, rs]) => eval(ty, ls, rs, u64::wrapping_mul, Some(|ls, rs| ls * rs)),
+        (Opcode::Div, &[ls, rs]) if ty.float() => eval(ty, ls, rs, u64::wrapping_div, Some(|ls, rs| ls / rs)),
+        (Opcode::Div, &[ls, rs]) => eval_div(ty, ls, rs, i64::checked_div, u64::checked_div),
+        (Opcode::Rem, &[ls, rs]) => eval_div(ty, ls, rs, i64::checked_rem, u64::checked_rem),
+        (Opcode::Shl, &[ls, rs]) => eval_shift(ty, ls, rs, |ls, _, amount| ls << amount),
+        // the value gets zero extended from its bit size
+        (Opcode::Lshr, &[ls, rs]) => eval_shift(ty, ls, rs, |ls, bits, amount| (ls & (u64::MAX >> (64 - bits))) >> amount),
+        // the value gets sign extended from its bit size
+        (Opcode::Ashr, &[ls, rs]) => eval_shift(ty, ls, rs, |ls, bits, amount| (((ls << (64 - bits)) as i64 >> (64 - bits)) >> amount) as u64),
+        (Opcode::And, &[ls, rs]) => eval(ty, ls, rs, |ls, rs| ls & rs, None),
+        (Opcode::Xor, &[ls, rs]) => eval(ty, ls, rs, |ls, rs| ls ^ rs, None),
+        (Opcode::Or, &[ls, rs]) => eval(ty, ls, rs, |ls, rs| ls | rs, None),
+        (Opcode::Neg, &[val]) if ty.float() => Some(Type::from_float(ty, -val.val_float())),
+        (Opcode::Neg, &[val]) => Some(Type::from_int(ty, (val.val() as i64).wrapping_neg())),
+        (Opcode::Not, &[val]) if !ty.float() => Some(Type::from_int(ty, !val.val() as i64)),
+        (Opcode::Bswap, &[val]) => eval_bits(ty, val, |val, bits| if bits < 8 { val } else { val.swap_bytes() >> (64 - bits) }),
+        (Opcode::Ctpop, &[val]) => eval_bits(ty, val, |val, _| val.count_ones() as u64),
+        (Opcode::Ctlz, &[val]) => eval_bits(ty, val, |val, bits| (val.leading_zeros() - (64 - bits)) as u64),
+        (Opcode::Cttz, &[val]) => eval_bits(ty, val, |val, bits| val.trailing_zeros().min(bits) as u64),
+        _ => None,
+    }
+}
+
+/// Computes the result of an overflow checked node and if it overflowed
+/// if both of its operands are known constants
+fn eval_overflow_node(consts: &HashMap<String, Type>, node: &dyn Ir) -> Option<(Type, bool)> {
+    let Opcode::WithOverflow(op) = node.opcode() else { return None };
+    let [ls, rs] = node.operands()[..] else { return None };
+
+    let ls = consts.get(&ls.var()?.name)?;
+    let rs = consts.get(&rs.var()?.name)?;
+
+    Some(eval_overflow(node.result()?.ty, op, *ls, *rs))
+}
+
+/// Computes the result of an overflow checked operation on two constants and if it overflowed.
+///
+/// The operands are sign or zero extended from their bit size depending on the type
+fn eval_overflow(ty: TypeMetadata, op: OverflowOp, ls: Type, rs: Type) -> (Type, bool) {
+    let bits = ty.bitSize() as u32;
+
+    let extend = |val: u64| if ty.signed() {
+        ((val << (64 - bits)) as i64 >> (64 - bits)) as i128
+    } else {
+        (val & (u64::MAX >> (64 - bits))) as i128
+    };
+
+    let (ls, rs) = (extend(ls.val()), extend(rs.val()));
+
+    let res = match op {
+        OverflowOp::Add => ls.checked_add(rs),
+        OverflowOp::Sub => ls.checked_sub(rs),
+        OverflowOp::Mul => ls.checked_mul(rs),
+    };
+
+    let (min, max) = if ty.signed() {
+        (-(1i128 << (bits - 1)), (1i128 << (bits - 1)) - 1)
+    } else {
+        (0, (1i128 << bits) - 1)
+    };
+
+    let wrapped = match op {
+        OverflowOp::Add => (ls as u64).wrapping_add(rs as u64),
+        OverflowOp::Sub => (ls as u64).wrapping_sub(rs as u64),
+        OverflowOp::Mul => (ls as u64).wrapping_mul(rs as u64),
+    };
+
+    (Type::from_int(ty, wrapped as i64), !res.is_some_and(|res| min <= res && res <= max))
+}
+
 impl Pass for PreComputeValue {
     fn run(&self, block: &mut crate::prelude::Block) {
-        for node in block.nodes.iter_mut() {
-            if let Some(or) = node.as_any().downcast_ref::<Add<Type, Type, Var>>() {
-                let res = or.inner1.val() + or.inner2.val();
-                node.replace(Assign::new(or.inner3.clone(), {
-                    match or.inner3.ty {
-                        TypeMetadata::u16 => Type::u16(res as u16),
-                        TypeMetadata::u32 => Type::u32(res as u32),
-                        TypeMetadata::u64 => Type::u64(res as u64),
-                        TypeMetadata::i16 => Type::i16(res as i16),
-                        TypeMetadata::i32 => Type::i32(res as i32),
-                        TypeMetadata::i64 => Type::i64(res as i64),
-                        TypeMetadata::ptr => Type::ptr(res as i64),
-                        TypeMetadata::Void => Type::Void,
-                    }
-                }))
-            }
-            if let Some(or) = node.as_any().downcast_ref::<Sub<Type, Type, Var>>() {
-                let res = or.inner1.val() - or.inner2.val();
-                node.replace(Assign::new(or.inner3.clone(), {
-                    match or.inner3.ty {
-                        TypeMetadata::u16 => Type::u16(res as u16),
-                        TypeMetadata::u32 => Type::u32(res as u32),
-                        TypeMetadata::u64 => Type::u64(res as u64),
-                        TypeMetadata::i16 => Type::i16(res as i16),
-                        TypeMetadata::i32 => Type::i32(res as i32),
-                        TypeMetadata::i64 => Type::i64(res as i64),
-                        TypeMetadata::ptr => Type::ptr(res as i64),
-                        TypeMetadata::Void => Type::Void,
-                    }
-                }))
+        // the constants which were assigned to variables in this block
+        let mut consts: HashMap<String, Type> = HashMap::new();
+
+        // the assignments of the overflow flags of folded overflow checked nodes (inserted after the nodes)
+        let mut flags: Vec<(usize, Box<dyn Ir>)> = vec![];
+
+        for (index, node) in block.nodes.iter_mut().enumerate() {
+            let mut res = eval_node(node.as_ref()).map(|val| (node.result().expect("folded nodes have a result").clone(), val));
+
+            let select = match node.opcode() {
+                Opcode::Select => eval_select(&consts, node.as_ref()),
+                _ => None,
+            };
+
+            if let Some((val, overflow)) = eval_overflow_node(&consts, node.as_ref()) {
+                let [out, flag] = node.results()[..] else { unreachable!("overflow checked nodes have a result and an overflow flag") };
+
+                consts.insert(flag.name.to_owned(), Type::bool(overflow));
+                flags.push((index + 1, Assign::new(flag.clone(), Type::bool(overflow))));
+
+                res = Some((out.clone(), val));
             }
-            if let Some(or) = node.as_any().downcast_ref::<And<Type, Type, Var>>() {
-                let res = or.inner1.val() & or.inner2.val();
-                node.replace(Assign::new(or.inner3.clone(), {
-                    match or.inner3.ty {
-                        TypeMetadata::u16 => Type::u16(res as u16),
-                        TypeMetadata::u32 => Type::u32(res as u32),
-                        TypeMetadata::u64 => Type::u64(res as u64),
-                        TypeMetadata::i16 => Type::i16(res as i16),
-                        TypeMetadata::i32 => Type::i32(res as i32),
-                        TypeMetadata::i64 => Type::i64(res as i64),
-                        TypeMetadata::ptr => Type::ptr(res as i64),
-                        TypeMetadata::Void => Type::Void,
-                    }
-                }))
+
+            if let Some((out, val)) = res {
+                node.replace(Assign::new(out, val));
             }
-            if let Some(or) = node.as_any().downcast_ref::<Xor<Type, Type, Var>>() {
-                let res = or.inner1.val() ^ or.inner2.val();
-                node.replace(Assign::new(or.inner3.clone(), {
-                    match or.inner3.ty {
-                        TypeMetadata::u16 => Type::u16(res as u16),
-                        TypeMetadata::u32 => Type::u32(res as u32),
-                        TypeMetadata::u64 => Type::u64(res as u64),
-                        TypeMetadata::i16 => Type::i16(res as i16),
-                        TypeMetadata::i32 => Type::i32(res as i32),
-                        TypeMetadata::i64 => Type::i64(res as i64),
-                        TypeMetadata::ptr => Type::ptr(res as i64),
-                        TypeMetadata::Void => Type::Void,
-                    }
-                }))
+            if let Some(select) = select {
+                node.replace(select);
             }
-            if let Some(or) = node.as_any().downcast_ref::<Or<Type, Type, Var>>() {
-                let res = or.inner1.val() | or.inner2.val();
-                node.replace(Assign::new(or.inner3.clone(), {
-                    match or.inner3.ty {
-                        TypeMetadata::u16 => Type::u16(res as u16),
-                        TypeMetadata::u32 => Type::u32(res as u32),
-                        TypeMetadata::u64 => Type::u64(res as u64),
-                        TypeMetadata::i16 => Type::i16(res as i16),
-                        TypeMetadata::i32 => Type::i32(res as i32),
-                        TypeMetadata::i64 => Type::i64(res as i64),
-                        TypeMetadata::ptr => Type::ptr(res as i64),
-                        TypeMetadata::Void => Type::Void,
-                    }
-                }))
+
+            if node.opcode() == Opcode::Assign {
+                if let (Some(out), [Operand::Const(val)]) = (node.result(), &node.operands()[..]) {
+                    consts.insert(out.name.to_owned(), **val);
+                }
             }
         }
+
+        for (index, flag) in flags.into_iter().rev() {
+            block.nodes.insert(index, flag);
+        }
     }
-}
\ No newline at end of file
+}
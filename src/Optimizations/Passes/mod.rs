@@ -1,5 +1,7 @@
 //mod MathInline;
 mod MathEval;
+mod DeadPaths;
 
 pub use MathEval::PreComputeValue;
+pub use DeadPaths::PruneDeadPaths;
 //pub use MathInline::InlineConstValue;
\ No newline at end of file
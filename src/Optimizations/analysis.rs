@@ -0,0 +1,75 @@
+use std::{any::{Any, TypeId}, collections::HashMap, rc::Rc};
+
+use crate::IR::{Cfg, DefUse, DomTree, DominanceFrontiers, Function};
+
+/// An analysis of a function which passes can request from the analysis manager
+pub trait Analysis: Any {
+    /// Computes the analysis of the function (other analyses it is built on can be requested from the manager)
+    fn compute(func: &Function, analyses: &mut AnalysisManager) -> Self where Self: Sized;
+}
+
+/// Caches the analyses of the function which the passes are currently run on
+///
+/// The analyses are computed the first time they are requested and are kept
+/// until a pass invalidates them (because it changed the function)
+#[derive(Default)]
+pub struct AnalysisManager {
+    cache: HashMap<TypeId, Rc<dyn Any>>,
+}
+
+impl AnalysisManager {
+    /// Creates a new analysis manager without any cached analyses
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the analysis of the function (it gets computed if it isn't cached)
+    pub fn get<A: Analysis>(&mut self, func: &Function) -> Rc<A> {
+        if let Some(cached) = self.cache.get(&TypeId::of::<A>()) {
+            return cached.clone().downcast::<A>().expect("the analyses are cached by their type");
+        }
+
+        let analysis = Rc::new(A::compute(func, self));
+
+        self.cache.insert(TypeId::of::<A>(), analysis.clone());
+
+        analysis
+    }
+
+    /// Returns if the analysis is cached
+    pub fn is_cached<A: Analysis>(&self) -> bool {
+        self.cache.contains_key(&TypeId::of::<A>())
+    }
+
+    /// Drops all cached analyses (needs to be called after the function was changed)
+    pub fn invalidate(&mut self) {
+        self.cache.clear();
+    }
+}
+
+impl Analysis for DefUse {
+    fn compute(func: &Function, _: &mut AnalysisManager) -> Self {
+        DefUse::new(func)
+    }
+}
+
+impl Analysis for Cfg {
+    fn compute(func: &Function, _: &mut AnalysisManager) -> Self {
+        Cfg::new(func)
+    }
+}
+
+impl Analysis for DomTree {
+    fn compute(func: &Function, analyses: &mut AnalysisManager) -> Self {
+        DomTree::new(&analyses.get::<Cfg>(func))
+    }
+}
+
+impl Analysis for DominanceFrontiers {
+    fn compute(func: &Function, analyses: &mut AnalysisManager) -> Self {
+        let cfg = analyses.get::<Cfg>(func);
+        let dom = analyses.get::<DomTree>(func);
+
+        DominanceFrontiers::new(&cfg, &dom)
+    }
+}
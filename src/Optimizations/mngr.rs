@@ -1,6 +1,6 @@
 use std::collections::VecDeque;
-use crate::IR::Block;
-use super::Pass;
+use crate::IR::{Block, Function};
+use super::{AnalysisManager, Pass};
 
 /// The manager of all passes (PassManager)
 pub struct PassManager {
@@ -31,4 +31,14 @@ impl PassManager {
             pass.run(block);
         }
     }
+
+    /// Executes the entire pass queue on the function
+    /// (the analyses of the function are shared between the passes till one of them changes it)
+    pub fn run_func(&self, func: &mut Function) {
+        let mut analyses = AnalysisManager::new();
+
+        for pass in &self.passes {
+            pass.run_func_with_analyses(func, &mut analyses);
+        }
+    }
 }
\ No newline at end of file
@@ -1,10 +1,12 @@
 mod mngr;
 mod template;
+mod analysis;
 /// all passes
 pub mod Passes;
 
 pub use mngr::PassManager;
 pub use template::Pass;
+pub use analysis::{Analysis, AnalysisManager};
 /// used for optimizing
 pub trait Optimize<T> {
     /// optimizes self
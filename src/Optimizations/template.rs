@@ -1,7 +1,23 @@
-use crate::IR::Block;
+use crate::IR::{Block, Function};
+
+use super::AnalysisManager;
 
 /// The trait all Passes need to implement
 pub trait Pass {
     /// Returns the pass
     fn run(&self, block: &mut Block);
-}
\ No newline at end of file
+
+    /// Runs the pass on the entire function (by default it runs on every block)
+    fn run_func(&self, func: &mut Function) {
+        for block in &mut func.blocks {
+            self.run(block);
+        }
+    }
+
+    /// Runs the pass on the function with access to the cached analyses of it
+    /// (by default it runs `run_func` and invalidates the analyses, because the function could have changed)
+    fn run_func_with_analyses(&self, func: &mut Function, analyses: &mut AnalysisManager) {
+        self.run_func(func);
+        analyses.invalidate();
+    }
+}
@@ -128,6 +128,35 @@ pub enum Arch {
     Renderscript64,
 }
 
+impl Arch {
+    /// Returns the size of a pointer in bytes
+    pub fn ptrSize(&self) -> usize {
+        match self {
+            Arch::Avr | Arch::Msp420 => 2,
+
+            Arch::Arm | Arch::ArmEB | Arch::Arc | Arch::Hexagon |
+            Arch::Mips | Arch::Mipsel | Arch::Ppc | Arch::R600 |
+            Arch::Riscv32 | Arch::Sparc | Arch::Sparcel | Arch::Tce |
+            Arch::TceLe | Arch::Thumb | Arch::Thumbeb | Arch::X86 |
+            Arch::Xcore | Arch::Nvptx | Arch::Le32 | Arch::AmdIL |
+            Arch::Hsail | Arch::Spir | Arch::Kalimba | Arch::Shave |
+            Arch::Lanai | Arch::Wasm32 | Arch::Renderscript32 => 4,
+
+            _ => 8,
+        }
+    }
+
+    /// Returns the biggest alignment (in bytes) which a scalar type gets in memory
+    pub fn maxScalarAlign(&self) -> usize {
+        match self {
+            Arch::Avr => 1,
+            Arch::Msp420 => 2,
+            Arch::X86 => 4,
+            _ => 8,
+        }
+    }
+}
+
 /// Target calling convention
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CallConv {
@@ -141,6 +170,30 @@ pub enum CallConv {
     WasmBasicCAbi,
 }
 
+impl CallConv {
+    /// Parses the calling convention from the name which is used for it in the ir
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "win64" => Some(CallConv::WindowsFastCall),
+            "sysv" => Some(CallConv::SystemV),
+            "apple_aarch64" => Some(CallConv::AppleAarch64),
+            "wasm_c" => Some(CallConv::WasmBasicCAbi),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for CallConv {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            CallConv::WindowsFastCall => "win64",
+            CallConv::SystemV => "sysv",
+            CallConv::AppleAarch64 => "apple_aarch64",
+            CallConv::WasmBasicCAbi => "wasm_c",
+        })
+    }
+}
+
 /// Vendor
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Vendor {
@@ -26,6 +26,7 @@ impl TargetRegistry {
 
     /// Sets the calling convention to use for the specified architecture
     /// If it isn't found the function does noting
+    /// (functions which specify their own calling convention in their type still use it)
     pub fn setCallingConventionForTarget(&mut self, arch: Arch, call: CallConv) {
         if let Some(target) = self.targets.get_mut(&arch) {
             target.call = call;
@@ -50,7 +51,7 @@ impl TargetRegistry {
         let org = self.getBasedOnArch(arch)?;
 
         org.block = Some(block.clone());
-        let instrs = org.build_instrs(&funct, &triple);
+        let instrs = org.build_instrs(&funct, &triple)?;
 
         org.reset();
 
@@ -64,7 +65,7 @@ impl TargetRegistry {
         let org = self.getBasedOnArch(arch)?;
         org.block = Some(block.clone());
 
-        let instrs = org.build_instrs(&funct, &triple);
+        let instrs = org.build_instrs(&funct, &triple)?;
         let instrs = org.lower(instrs)?;
 
         let mut asm = vec![];
@@ -88,7 +89,7 @@ impl TargetRegistry {
 
         org.block = Some(block.clone());
 
-        let instrs = org.build_instrs(&funct, &triple);
+        let instrs = org.build_instrs(&funct, &triple)?;
         let instrs = org.lower(instrs)?;
 
         let mut res = vec![];
@@ -101,10 +102,13 @@ impl TargetRegistry {
             if let Some(link) = link {
                 let mut link = link.clone();
 
-                if link.special {
-                    link.from = block.name.to_owned();
-                } else {
-                    link.from = funct.name.to_string();
+                // some links already know their source (e.g: jump table entries)
+                if link.from.is_empty() {
+                    if link.special {
+                        link.from = block.name.to_owned();
+                    } else {
+                        link.from = funct.name.to_string();
+                    }
                 }
                 link.at = res.len();
 
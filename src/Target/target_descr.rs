@@ -1,9 +1,9 @@
 use std::error::Error;
 
 use crate::prelude::{ir::*, Block, Var};
-use crate::CodeGen::MCInstr;
+use crate::CodeGen::{AsmMCInstr, MCInstr, MachineMnemonic};
 use crate::CodeGen::{compilation::CompilationHelper, MachineInstr};
-use crate::IR::{Const, Function, Type, TypeMetadata};
+use crate::IR::{AggregateType, Const, Function, FunctionAttr, Type, TypeMetadata, VerifyError};
 
 use super::{Triple, WhiteList};
 use super::{CallConv, Compiler, Lexer};
@@ -71,7 +71,7 @@ impl TargetBackendDescr {
     }
 
     /// builds all ir nodes of the current block into a vector of MachineInstr
-    pub fn build_instrs(&mut self, func: &Function, triple: &Triple) -> Vec<MachineInstr> {
+    pub fn build_instrs(&mut self, func: &Function, triple: &Triple) -> Result<Vec<MachineInstr>, Box<dyn Error>> {
         let helper = if let Some(helper) = &mut self.helper { helper }
         else { panic!("no current compilation helper"); };
 
@@ -79,13 +79,36 @@ impl TargetBackendDescr {
             panic!("the architecture of the triple {:?} isn't the same as the one of the compilation helper {:?}", triple.arch, helper.arch)
         }
 
+        // the parts of the nodes which depend on the target can only be checked here
+        if let Some(block) = &self.block {
+            for node in &block.nodes {
+                if let Some(asm) = node.as_any().downcast_ref::<InlineAsm>() {
+                    asm.verify_regs(triple.arch)?
+                } else if let Some(call) = node.as_any().downcast_ref::<TailCall<Function, Vec<Var>>>() {
+                    let tys = call.inner2.iter().map(|arg| arg.ty).collect::<Vec<_>>();
+
+                    // our caller only reserved stack space for our own arguments
+                    if helper.call_conv(&call.inner1.ty).arg_regs(triple.arch, &tys).contains(&None) {
+                        Err(VerifyError::TailCallStackArgs)?
+                    }
+                }
+            }
+        }
+
         let block = if let Some(block) = &self.block {
-            block.clone()
+            helper.build_phi_moves(func, block)
         } else {
             panic!("no current block");
         };
 
+        helper.func = func.name.to_owned();
+        helper.naked = func.has_attr(FunctionAttr::Naked);
+        helper.func_call = helper.call_conv(&func.ty);
+
         helper.build_argument_preprocessing(func);
+        helper.build_stack_preprocessing(func, &block, &mut self.sink);
+
+        self.block = Some(block.clone());
 
         for node in block.nodes {
             node.compile(self);
@@ -93,7 +116,7 @@ impl TargetBackendDescr {
 
         let out = self.sink.clone();
 
-        out
+        Ok(out)
     }
 
     /// Resets all values to "factory standart"
@@ -112,13 +135,46 @@ impl TargetBackendDescr {
         }
     }
 
+    /// assembles the assembly string (the instructions are seperated by new lines or semicolons)
+    pub fn assemble(&self, asm: &str) -> Result<Box<dyn MCInstr>, Box<dyn Error>> {
+        let mut out = AsmMCInstr { asm: vec![], code: vec![] };
+
+        for line in asm.split(['\n', ';']).map(|line| line.trim()).filter(|line| !line.is_empty()) {
+            let tokens = self.lexer().lex(line.to_string())?;
+
+            let mut compiler = self.compiler().new(tokens);
+            compiler.parse()?;
+
+            out.asm.push(compiler.printOut());
+            out.code.extend(compiler.out()?);
+        }
+
+        Ok(Box::new(out))
+    }
+
     /// Used for lowering machine instructions into dyn MCInstr
     pub fn lower(&self, instrs: Vec<MachineInstr>) -> Result<Vec<Box<dyn MCInstr>>, Box<dyn Error>> {
         if let Some(helper) = &self.helper {
             self.whitelist.check_for_forbidden_mnemonics(&instrs)?;
 
             if let Some(lower) = helper.lower {
-                Ok(lower(self.call, instrs))
+                let mut out = vec![];
+                let mut lowerable = vec![];
+
+                // inline assembly is assembled by the assembler of the target,
+                // so only the instructions around it are lowered
+                for instr in instrs {
+                    if let MachineMnemonic::InlineAsm(asm) = &instr.mnemonic {
+                        out.extend(lower(self.call, std::mem::take(&mut lowerable)));
+                        out.push(self.assemble(asm)?);
+                    } else {
+                        lowerable.push(instr);
+                    }
+                }
+
+                out.extend(lower(self.call, lowerable));
+
+                Ok(out)
             } else {
                 todo!("the target architecture {:?} doesn't support instruction lowering", helper.arch)
             }
@@ -135,6 +191,10 @@ compile_func!(compile_mul_var_var, compile_mul_var_var, Mul<Var, Var, Var>);
 compile_func!(compile_or_var_var,  compile_or_var_var,  Or<Var, Var, Var>);
 compile_func!(compile_sub_var_var, compile_sub_var_var, Sub<Var, Var, Var>);
 compile_func!(compile_xor_var_var, compile_xor_var_var, Xor<Var, Var, Var>);
+compile_func!(compile_rem_var_var, compile_rem_var_var, Rem<Var, Var, Var>);
+compile_func!(compile_shl_var_var, compile_shl_var_var, Shl<Var, Var, Var>);
+compile_func!(compile_lshr_var_var, compile_lshr_var_var, Lshr<Var, Var, Var>);
+compile_func!(compile_ashr_var_var, compile_ashr_var_var, Ashr<Var, Var, Var>);
 
 compile_func!(compile_add_var_type, compile_add_var_type, Add<Var, Type, Var>);
 compile_func!(compile_and_var_type, compile_and_var_type, And<Var, Type, Var>);
@@ -143,6 +203,10 @@ compile_func!(compile_mul_var_type, compile_mul_var_type, Mul<Var, Type, Var>);
 compile_func!(compile_or_var_type,  compile_or_var_type,  Or<Var, Type, Var>);
 compile_func!(compile_sub_var_type,  compile_sub_var_type,  Sub<Var, Type, Var>);
 compile_func!(compile_xor_var_type, compile_xor_var_type, Xor<Var, Type, Var>);
+compile_func!(compile_rem_var_type, compile_rem_var_type, Rem<Var, Type, Var>);
+compile_func!(compile_shl_var_type, compile_shl_var_type, Shl<Var, Type, Var>);
+compile_func!(compile_lshr_var_type, compile_lshr_var_type, Lshr<Var, Type, Var>);
+compile_func!(compile_ashr_var_type, compile_ashr_var_type, Ashr<Var, Type, Var>);
 
 compile_func!(compile_add_type_type, compile_add_type_type, Add<Type, Type, Var>);
 compile_func!(compile_and_type_type, compile_and_type_type, And<Type, Type, Var>);
@@ -151,6 +215,23 @@ compile_func!(compile_mul_type_type, compile_mul_type_type, Mul<Type, Type, Var>
 compile_func!(compile_or_type_type,  compile_or_type_type,  Or<Type, Type, Var>);
 compile_func!(compile_sub_type_type, compile_sub_type_type, Sub<Type, Type, Var>);
 compile_func!(compile_xor_type_type, compile_xor_type_type, Xor<Type, Type, Var>);
+compile_func!(compile_rem_type_type, compile_rem_type_type, Rem<Type, Type, Var>);
+compile_func!(compile_shl_type_type, compile_shl_type_type, Shl<Type, Type, Var>);
+compile_func!(compile_lshr_type_type, compile_lshr_type_type, Lshr<Type, Type, Var>);
+compile_func!(compile_ashr_type_type, compile_ashr_type_type, Ashr<Type, Type, Var>);
+
+compile_func!(compile_neg_var, compile_neg_var, Neg<Var, Var>);
+compile_func!(compile_neg_type, compile_neg_type, Neg<Type, Var>);
+compile_func!(compile_bswap_var, compile_bswap_var, Bswap<Var, Var>);
+compile_func!(compile_bswap_type, compile_bswap_type, Bswap<Type, Var>);
+compile_func!(compile_ctpop_var, compile_ctpop_var, Ctpop<Var, Var>);
+compile_func!(compile_ctpop_type, compile_ctpop_type, Ctpop<Type, Var>);
+compile_func!(compile_ctlz_var, compile_ctlz_var, Ctlz<Var, Var>);
+compile_func!(compile_ctlz_type, compile_ctlz_type, Ctlz<Type, Var>);
+compile_func!(compile_cttz_var, compile_cttz_var, Cttz<Var, Var>);
+compile_func!(compile_cttz_type, compile_cttz_type, Cttz<Type, Var>);
+compile_func!(compile_not_var, compile_not_var, Not<Var, Var>);
+compile_func!(compile_not_type, compile_not_type, Not<Type, Var>);
 
 compile_func!(compile_ret_ty, compile_ret_ty, Return<Type>);
 compile_func!(compile_ret_var, compile_ret_var, Return<Var>);
@@ -166,4 +247,38 @@ compile_func!(compile_assign_var_const, compile_assign_var_const, Assign<Var, Co
 compile_func!(compile_br, compile_br, Br<Box<Block>>);
 compile_func!(compile_br_cond, compile_br_cond, BrCond<Var, Block, Block>);
 
-compile_func!(compile_cmp, compile_cmp, Cmp);
\ No newline at end of file
+compile_func!(compile_cmp, compile_cmp, Cmp);
+
+compile_func!(compile_alloca, compile_alloca, Alloca<Var, TypeMetadata>);
+compile_func!(compile_alloca_aggregate, compile_alloca, Alloca<Var, AggregateType>);
+compile_func!(compile_load, compile_load, Load<Var, Var, TypeMetadata>);
+compile_func!(compile_store_var, compile_store_var, Store<Var, Var>);
+compile_func!(compile_store_ty, compile_store_ty, Store<Var, Type>);
+
+compile_func!(compile_phi, compile_phi, Phi);
+compile_func!(compile_switch, compile_switch, Switch);
+
+compile_func!(compile_select_var_var, compile_select, Select<Var, Var>);
+compile_func!(compile_select_var_type, compile_select, Select<Var, Type>);
+compile_func!(compile_select_type_var, compile_select, Select<Type, Var>);
+compile_func!(compile_select_type_type, compile_select, Select<Type, Type>);
+compile_func!(compile_gep_var, compile_gep_var, Gep<Var>);
+compile_func!(compile_gep_type, compile_gep_type, Gep<Type>);
+compile_func!(compile_memcpy_var, compile_memcpy_var, MemCpy<Var>);
+compile_func!(compile_memcpy_type, compile_memcpy_type, MemCpy<Type>);
+compile_func!(compile_memset_var, compile_memset_var, MemSet<Var>);
+compile_func!(compile_memset_type, compile_memset_type, MemSet<Type>);
+compile_func!(compile_atomic_load, compile_atomic_load, AtomicLoad);
+compile_func!(compile_atomic_store, compile_atomic_store, AtomicStore);
+compile_func!(compile_atomic_rmw, compile_atomic_rmw, AtomicRmw);
+compile_func!(compile_atomic_cmpxchg, compile_atomic_cmpxchg, AtomicCmpXchg);
+compile_func!(compile_fence, compile_fence, Fence);
+compile_func!(compile_with_overflow, compile_with_overflow, WithOverflow);
+compile_func!(compile_va_start, compile_va_start, VaStart);
+compile_func!(compile_va_arg, compile_va_arg, VaArg);
+compile_func!(compile_va_end, compile_va_end, VaEnd);
+compile_func!(compile_inline_asm, compile_inline_asm, InlineAsm);
+compile_func!(compile_call_indirect, compile_call_indirect, CallIndirect);
+compile_func!(compile_tail_call, compile_tail_call, TailCall<Function, Vec<Var>>);
+compile_func!(compile_unreachable, compile_unreachable, Unreachable);
+compile_func!(compile_assign_var_func, compile_assign_var_func, Assign<Var, Function>);
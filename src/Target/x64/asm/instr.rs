@@ -52,10 +52,10 @@ impl X64MCInstr {
         
         Ok(match self.mnemonic {
             Mnemonic::Add | Mnemonic::Adc | Mnemonic::And | Mnemonic::Or | Mnemonic::Sub | Mnemonic::Xor | Mnemonic::Mov | Mnemonic::Cmp => {
-                let mandatory = if let Some(Operand::Reg(reg)) = &self.op1 {
-                    if reg.is_gr16() { Some(MandatoryPrefix::t16BitOps)}
-                    else { None }
-                } else { None };
+                let mandatory = match (&self.op1, &self.op2) {
+                    (Some(Operand::Reg(reg)), _) | (Some(Operand::Mem(_)), Some(Operand::Reg(reg))) if reg.is_gr16() => Some(MandatoryPrefix::t16BitOps),
+                    _ => None,
+                };
 
                 let (mut r, mut m, i, ibase, ibase8) = match self.mnemonic {
                     Mnemonic::Add => (0x01, 0x03, 0, 0x81, 0x80),
@@ -66,11 +66,11 @@ impl X64MCInstr {
                     Mnemonic::Xor => (0x31, 0x33, 6, 0x81, 0x80),
                     Mnemonic::Mov => (0x89, 0x8B, 0, 0xC7, 0xC6),
 
-                    Mnemonic::Cmp => (0x83, 0x3B, 7, 0x81, 0x80),
+                    Mnemonic::Cmp => (0x39, 0x3B, 7, 0x81, 0x80),
                     _ => unreachable!(),
                 };
 
-                if let Some(Operand::Reg(reg)) = &self.op1 {
+                if let (Some(Operand::Reg(reg)), _) | (Some(Operand::Mem(_)), Some(Operand::Reg(reg))) = (&self.op1, &self.op2) {
                     if reg.is_gr8() { 
                         r -= 1; m -= 1;
                     }
@@ -86,6 +86,8 @@ impl X64MCInstr {
 
                         let mut op = vec![];
 
+                        let mut force_rex = reg.needs_rex();
+
                         if let Some(Operand::Reg(op0)) = &self.op1 {
                             let op0 = op0.as_any().downcast_ref::<x64Reg>().expect("expected x64 registers and not the ones from other archs");
                             
@@ -93,6 +95,8 @@ impl X64MCInstr {
                                 rex.b = true;
                             }
 
+                            force_rex |= op0.needs_rex();
+
                             if reg.extended() || op0.extended() { 
                                 op.push(r);
                                 op.extend_from_slice(&ModRm::reg2(
@@ -110,12 +114,11 @@ impl X64MCInstr {
 
                         } else if let Some(Operand::Mem(mem)) = &self.op1 {
                             op.push(r);
-                            rex.sync(mem.rex(true));
-                            let enc = &mem.encode(Some(*reg));
-                            op.extend_from_slice(&enc.1);
+                            rex = rex.sync(mem.rex());
+                            op.extend_from_slice(&ModRm::memR(mem.clone(), *reg));
                         } else { todo!() }
 
-                        buildOpcode(mandatory, rex.option(), op)
+                        buildOpcode(mandatory, if force_rex { Some(rex) } else { rex.option() }, op)
                     },
                     Operand::Mem(mem) => {
                         let mut op = vec![];
@@ -129,14 +132,13 @@ impl X64MCInstr {
                             }
                             op.push(m);
 
-                            if !mem.rex(false).empty() {
+                            if !mem.rex().empty() || op0.needs_rex() {
                                 if let Some(rext) = rex {
-                                    rex = Some(rext.sync(mem.rex(false)));
-                                } else {rex = Some(mem.rex(false))}
+                                    rex = Some(rext.sync(mem.rex()));
+                                } else {rex = Some(mem.rex())}
                             }
 
-                            let enc = &mem.encode(Some(*op0));
-                            op.extend_from_slice(&enc.1);
+                            op.extend_from_slice(&ModRm::regM(*op0, mem.clone()));
 
                         } else { todo!() }
 
@@ -150,7 +152,15 @@ impl X64MCInstr {
                         if let Some(Operand::Reg(op0)) = &self.op1 {
                             let op0 = op0.as_any().downcast_ref::<x64Reg>().expect("expected x64 registers and not the ones from other archs");
 
-                            if op0.is_gr64() || op0.extended() {
+                            if self.mnemonic == Mnemonic::Mov && op0.is_gr64() && (*num > i32::MAX as i64 || *num < i32::MIN as i64) {
+                                // mov r64, imm64
+                                op.push(0xB8 + op0.enc());
+                                op.extend_from_slice(&(*num).to_le_bytes());
+
+                                return Ok((buildOpcode(None, Some(RexPrefix { w: true, r: false, x: false, b: op0.extended() }), op), None));
+                            }
+
+                            if op0.is_gr64() || op0.extended() || op0.needs_rex() {
                                 rex = Some(RexPrefix { w: op0.is_gr64(), r: false, x: false, b: op0.extended() });
                             }
 
@@ -200,10 +210,10 @@ impl X64MCInstr {
                 let mut op = vec![];
 
                 if let Some(Operand::Mem(mem)) = &self.op2 {
-                    if !mem.rex(false).empty() {
+                    if !mem.rex().empty() {
                         if let Some(rext) = rex {
-                            rex = Some(rext.sync(mem.rex(false)));
-                        } else {rex = Some(mem.rex(false))}
+                            rex = Some(rext.sync(mem.rex()));
+                        } else {rex = Some(mem.rex())}
                     }
 
                     op.push(0x8D);
@@ -239,10 +249,9 @@ impl X64MCInstr {
                     op.push(r + op0.enc());
 
                 } else if let Some(Operand::Mem(mem)) = &self.op1 {
+                    rex = mem.rex().option();
                     op.push(m);
-                    let enc = mem.encode(None);
-                    op.push(enc.0 | i << 3 | 0b100);
-                    op.extend_from_slice(&enc.1);
+                    op.extend_from_slice(&ModRm::imMem(i, mem.clone()));
                 } else if self.mnemonic == Mnemonic::Push {
                     if let Some(Operand::Imm(num)) = &self.op1 {
                         let bytes = (*num).to_be_bytes();
@@ -266,16 +275,169 @@ impl X64MCInstr {
                 (buildOpcode(mandatory, rex, op), None)
             },
             Mnemonic::Ret => (vec![0xC3], None),
+            Mnemonic::Cdq => (vec![0x99], None),
+            Mnemonic::Cqo => (vec![0x48, 0x99], None),
+            Mnemonic::Ud2 => (vec![0x0F, 0x0B], None),
+            // the lock prefix is emitted as its own instruction in front of the locked one
+            Mnemonic::Lock => (vec![0xF0], None),
+            // the fs segment prefix is emitted as its own instruction in front of the instruction which accesses the memory
+            Mnemonic::Fs => (vec![0x64], None),
+            Mnemonic::Mfence => (vec![0x0F, 0xAE, 0xF0], None),
+            Mnemonic::Xchg | Mnemonic::Cmpxchg | Mnemonic::Xadd => {
+                let (mem, reg) = if let (Some(Operand::Mem(mem)), Some(Operand::Reg(reg))) = (&self.op1, &self.op2) {
+                    (mem.clone(), *reg)
+                } else { todo!() };
+
+                let mut op = match self.mnemonic {
+                    Mnemonic::Xchg => vec![0x87],
+                    Mnemonic::Cmpxchg => vec![0x0F, 0xB1],
+                    Mnemonic::Xadd => vec![0x0F, 0xC1],
+                    _ => unreachable!(),
+                };
+
+                if reg.is_gr8() {
+                    *op.last_mut().expect("opcodes aren't empty") -= 1;
+                }
+
+                let mandatory = if reg.is_gr16() { Some(MandatoryPrefix::t16BitOps) } else { None };
+
+                let mut rex = RexPrefix::none();
+                rex.w = reg.is_gr64();
+                rex.r = reg.extended();
+                rex = rex.sync(mem.rex());
+
+                op.extend_from_slice(&ModRm::memR(mem, reg));
+
+                (buildOpcode(mandatory, if reg.needs_rex() { Some(rex) } else { rex.option() }, op), None)
+            },
+            Mnemonic::Bswap => {
+                let reg = if let Some(Operand::Reg(reg)) = self.op1 { reg } else { todo!() };
+
+                let rex = RexPrefix { w: reg.is_gr64(), r: false, x: false, b: reg.extended() };
+
+                (buildOpcode(None, rex.option(), vec![0x0F, 0xC8 + reg.enc()]), None)
+            },
             Mnemonic::Endbr64 => (vec![0xF3, 0x0F, 0x1E, 0xFA], None),
-            Mnemonic::Movzx => todo!(),
+            Mnemonic::Movzx | Mnemonic::Movsx | Mnemonic::Movsxd => {
+                let (dst, src) = if let (Some(Operand::Reg(dst)), Some(Operand::Reg(src))) = (&self.op1, &self.op2) {
+                    (*dst, *src)
+                } else { todo!() };
+
+                let mandatory = if dst.is_gr16() { Some(MandatoryPrefix::t16BitOps) } else { None };
+
+                let op = match self.mnemonic {
+                    Mnemonic::Movzx if src.is_gr8() => vec![0x0F, 0xB6],
+                    Mnemonic::Movzx => vec![0x0F, 0xB7],
+                    Mnemonic::Movsx if src.is_gr8() => vec![0x0F, 0xBE],
+                    Mnemonic::Movsx => vec![0x0F, 0xBF],
+                    Mnemonic::Movsxd => vec![0x63],
+                    _ => unreachable!(),
+                };
+
+                let rex = RexPrefix { 
+                    w: dst.is_gr64() || self.mnemonic == Mnemonic::Movsxd, 
+                    r: dst.extended(), 
+                    x: false, 
+                    b: src.extended() 
+                };
+
+                // without a rex prefix spl/bpl/sil/dil would be ah/ch/dh/bh
+                let rex = if matches!(src, x64Reg::Spl | x64Reg::Bpl | x64Reg::Sil | x64Reg::Dil) {
+                    Some(rex)
+                } else { rex.option() };
+
+                let mut op = op;
+                op.extend_from_slice(&ModRm::reg2(src, dst));
+
+                (buildOpcode(mandatory, rex, op), None)
+            },
+            Mnemonic::Movss | Mnemonic::Movsd => {
+                let mandatory = if self.mnemonic == Mnemonic::Movss {
+                    Some(MandatoryPrefix::tScalarSingle)
+                } else { Some(MandatoryPrefix::tScalarDouble) };
+
+                let (rex, op) = match (self.op1.as_ref().expect("verify failed"), self.op2.as_ref().expect("verify failed")) {
+                    (Operand::Reg(op1), Operand::Reg(op2)) => {
+                        let mut op = vec![0x0F, 0x10];
+                        op.extend_from_slice(&ModRm::reg2(*op2, *op1));
+                        (RexPrefix { w: false, r: op1.extended(), x: false, b: op2.extended() }, op)
+                    },
+                    (Operand::Reg(op1), Operand::Mem(mem)) => {
+                        let mut op = vec![0x0F, 0x10];
+                        op.extend_from_slice(&ModRm::regM(*op1, mem.clone()));
+                        (RexPrefix { w: false, r: op1.extended(), x: false, b: false }.sync(mem.rex()), op)
+                    },
+                    (Operand::Mem(mem), Operand::Reg(op2)) => {
+                        let mut op = vec![0x0F, 0x11];
+                        op.extend_from_slice(&ModRm::memR(mem.clone(), *op2));
+                        (RexPrefix { w: false, r: op2.extended(), x: false, b: false }.sync(mem.rex()), op)
+                    },
+                    _ => unreachable!(),
+                };
+
+                (buildOpcode(mandatory, rex.option(), op), None)
+            },
+            Mnemonic::Movaps | Mnemonic::Addss | Mnemonic::Addsd | Mnemonic::Subss | Mnemonic::Subsd |
+            Mnemonic::Mulss | Mnemonic::Mulsd | Mnemonic::Divss | Mnemonic::Divsd | Mnemonic::Ucomiss | 
+            Mnemonic::Ucomisd | Mnemonic::Cvtss2sd | Mnemonic::Cvtsd2ss | Mnemonic::Movd | Mnemonic::Movq |
+            Mnemonic::Cvtsi2ss | Mnemonic::Cvtsi2sd | Mnemonic::Cvttss2si | Mnemonic::Cvttsd2si => {
+                let (op1, op2) = if let (Some(Operand::Reg(op1)), Some(Operand::Reg(op2))) = (&self.op1, &self.op2) {
+                    (*op1, *op2)
+                } else { todo!() };
+
+                let ss = Some(MandatoryPrefix::tScalarSingle);
+                let sd = Some(MandatoryPrefix::tScalarDouble);
+                let sse = Some(MandatoryPrefix::tSse);
+
+                // (mandatory prefix, opcode, reg of modrm, rm of modrm)
+                let (mandatory, op, reg, rm) = match self.mnemonic {
+                    Mnemonic::Movaps => (None, 0x28, op1, op2),
+                    Mnemonic::Addss => (ss, 0x58, op1, op2),
+                    Mnemonic::Addsd => (sd, 0x58, op1, op2),
+                    Mnemonic::Mulss => (ss, 0x59, op1, op2),
+                    Mnemonic::Mulsd => (sd, 0x59, op1, op2),
+                    Mnemonic::Subss => (ss, 0x5C, op1, op2),
+                    Mnemonic::Subsd => (sd, 0x5C, op1, op2),
+                    Mnemonic::Divss => (ss, 0x5E, op1, op2),
+                    Mnemonic::Divsd => (sd, 0x5E, op1, op2),
+                    Mnemonic::Ucomiss => (None, 0x2E, op1, op2),
+                    Mnemonic::Ucomisd => (sse, 0x2E, op1, op2),
+                    Mnemonic::Cvtss2sd => (ss, 0x5A, op1, op2),
+                    Mnemonic::Cvtsd2ss => (sd, 0x5A, op1, op2),
+                    Mnemonic::Cvtsi2ss => (ss, 0x2A, op1, op2),
+                    Mnemonic::Cvtsi2sd => (sd, 0x2A, op1, op2),
+                    Mnemonic::Cvttss2si => (ss, 0x2C, op1, op2),
+                    Mnemonic::Cvttsd2si => (sd, 0x2C, op1, op2),
+                    Mnemonic::Movd | Mnemonic::Movq if op1.is_xmm() => (sse, 0x6E, op1, op2),
+                    Mnemonic::Movd | Mnemonic::Movq => (sse, 0x7E, op2, op1),
+                    _ => unreachable!(),
+                };
+
+                let rex = RexPrefix { 
+                    w: self.mnemonic == Mnemonic::Movq || op1.is_gr64() || op2.is_gr64(), 
+                    r: reg.extended(), 
+                    x: false, 
+                    b: rm.extended() 
+                };
+
+                let mut op = vec![0x0F, op];
+                op.extend_from_slice(&ModRm::reg2(rm, reg));
+
+                (buildOpcode(mandatory, rex.option(), op), None)
+            },
             Mnemonic::Call => {
                 let (i, m, r) = (0xE8, 0xFF, 2);
 
+                let mut rex = None;
+
                 let mut op = vec![];
                 if let Some(Operand::Reg(reg)) = &self.op1 {
+                    let reg = *reg.as_any().downcast_ref::<x64Reg>().unwrap();
+                    rex = RexPrefix { w: false, r: false, x: false, b: reg.extended() }.option();
                     op.push(m);
-                    op.extend_from_slice(&ModRm::regWimm(r, *reg.as_any().downcast_ref::<x64Reg>().unwrap()));
+                    op.extend_from_slice(&ModRm::regWimm(r, reg));
                 } else if let Some(Operand::Mem(mem)) = &self.op1 {
+                    rex = mem.rex().option();
                     op.push(m);
                     op.extend_from_slice(&ModRm::imMem(r, mem.clone()));
                 } else if let Some(Operand::Imm(imm)) = self.op1 {
@@ -287,7 +449,7 @@ impl X64MCInstr {
                     op.push(bytes[4]);
                 } else { todo!() }
 
-                (buildOpcode(None, None, op), None)
+                (buildOpcode(None, rex, op), None)
             }
             Mnemonic::Jmp => {
                 let (m, r) = (0xFF, 4);
@@ -315,8 +477,16 @@ impl X64MCInstr {
 
                 (buildOpcode(None, None, op), None)
             }
-            Mnemonic::Jne => {
-                let mut op = vec![0x0F, 0x85];
+            Mnemonic::Jne | Mnemonic::Je | Mnemonic::Jg | Mnemonic::Ja | Mnemonic::Jb | Mnemonic::Jo => {
+                let mut op = vec![0x0F, match self.mnemonic {
+                    Mnemonic::Jne => 0x85,
+                    Mnemonic::Je => 0x84,
+                    Mnemonic::Jg => 0x8F,
+                    Mnemonic::Ja => 0x87,
+                    Mnemonic::Jb => 0x82,
+                    Mnemonic::Jo => 0x80,
+                    _ => unreachable!(),
+                }];
 
                 if let Some(Operand::Imm(num)) = self.op1 {
                     let bytes = num.to_be_bytes();
@@ -333,15 +503,21 @@ impl X64MCInstr {
                     (vec![], Some(Link { from: "".into(), to: dst.to_string(), at: 0, addend: *addend, special: false }))
                 } else if let Some(Operand::BlockLinkDestination(dst, addend)) = &self.op1 {
                     (vec![], Some(Link { from: "".into(), to: dst.to_string(), at: 0, addend: *addend, special: true }))
+                } else if let Some(Operand::JumpTableEntry(table, dst, offset)) = &self.op1 {
+                    (vec![], Some(Link { from: table.to_string(), to: dst.to_string(), at: 0, addend: *offset, special: true }))
                 } else {
                     (vec![], None)
                 }
             }
             Mnemonic::Debug | Mnemonic::StartOptimization | Mnemonic::EndOptimization => (vec![], None),
-            Mnemonic::Imul | Mnemonic::Mul => {
+            Mnemonic::Imul | Mnemonic::Mul | Mnemonic::Idiv | Mnemonic::Div | Mnemonic::Neg | Mnemonic::Not => {
                 let i = match self.mnemonic {
                     Mnemonic::Imul => 5,
                     Mnemonic::Mul => 4,
+                    Mnemonic::Idiv => 7,
+                    Mnemonic::Div => 6,
+                    Mnemonic::Neg => 3,
+                    Mnemonic::Not => 2,
                     _ => unreachable!(),
                 };
 
@@ -362,19 +538,110 @@ impl X64MCInstr {
                         mandatory = Some(MandatoryPrefix::t16BitOps);
                     }
 
-                    if reg.is_gr64() || reg.extended() {
-                        rex.w = reg.is_gr64();
-                        rex.r = reg.is_gr8();
-                    }
+                    rex.w = reg.is_gr64();
+                    rex.b = reg.extended();
 
                     op.push(r);
 
                     op.extend_from_slice(&ModRm::regWimm(i, reg));
+
+                    if reg.needs_rex() {
+                        return Ok((buildOpcode(mandatory, Some(rex), op), None));
+                    }
                 } else { todo!() }
 
                 (buildOpcode(mandatory, rex.option(), op), None)
             }
-            Mnemonic::Setg | Mnemonic::Setge | Mnemonic::Setl | Mnemonic::Setle | Mnemonic::Sete | Mnemonic::Setne => {
+            Mnemonic::Cmove | Mnemonic::Cmovne | Mnemonic::Cmovg | Mnemonic::Cmovge | Mnemonic::Cmovl | Mnemonic::Cmovle |
+            Mnemonic::Cmova | Mnemonic::Cmovae | Mnemonic::Cmovb | Mnemonic::Cmovbe |
+            Mnemonic::Bsf | Mnemonic::Bsr | Mnemonic::Popcnt => {
+                let (dst, src) = if let (Some(Operand::Reg(dst)), Some(Operand::Reg(src))) = (&self.op1, &self.op2) {
+                    (*dst, *src)
+                } else { todo!() };
+
+                let instr = match self.mnemonic {
+                    Mnemonic::Cmove => 0x44,
+                    Mnemonic::Cmovne => 0x45,
+                    Mnemonic::Cmovg => 0x4F,
+                    Mnemonic::Cmovge => 0x4D,
+                    Mnemonic::Cmovl => 0x4C,
+                    Mnemonic::Cmovle => 0x4E,
+                    Mnemonic::Cmova => 0x47,
+                    Mnemonic::Cmovae => 0x43,
+                    Mnemonic::Cmovb => 0x42,
+                    Mnemonic::Cmovbe => 0x46,
+                    Mnemonic::Bsf => 0xBC,
+                    Mnemonic::Bsr => 0xBD,
+                    Mnemonic::Popcnt => 0xB8,
+                    _ => unreachable!(),
+                };
+
+                let mandatory = if self.mnemonic == Mnemonic::Popcnt {
+                    // popcnt is encoded with the F3 prefix
+                    Some(MandatoryPrefix::tScalarSingle)
+                } else if dst.is_gr16() { 
+                    Some(MandatoryPrefix::t16BitOps) 
+                } else { None };
+
+                let rex = RexPrefix { 
+                    w: dst.is_gr64(), 
+                    r: dst.extended(), 
+                    x: false, 
+                    b: src.extended() 
+                };
+
+                let mut op = vec![0x0F, instr];
+                op.extend_from_slice(&ModRm::reg2(src, dst));
+
+                (buildOpcode(mandatory, rex.option(), op), None)
+            }
+            Mnemonic::Shl | Mnemonic::Shr | Mnemonic::Sar => {
+                let i = match self.mnemonic {
+                    Mnemonic::Shl => 4,
+                    Mnemonic::Shr => 5,
+                    Mnemonic::Sar => 7,
+                    _ => unreachable!(),
+                };
+
+                let reg = if let Some(Operand::Reg(reg)) = self.op1 { reg } else { todo!() };
+
+                let mut mandatory = None;
+
+                let mut rex = RexPrefix::none();
+                rex.w = reg.is_gr64();
+                rex.b = reg.extended();
+
+                if reg.is_gr16() {
+                    mandatory = Some(MandatoryPrefix::t16BitOps);
+                }
+
+                let mut op = vec![];
+
+                // shifts by cl use D3 /n, shifts by an imm8 use C1 /n ib (D2/C0 for 8 bit registers)
+                let imm = if let Some(Operand::Imm(imm)) = self.op2 { Some(imm) } else { None };
+
+                let mut r = if imm.is_some() { 0xC1 } else { 0xD3 };
+
+                if reg.is_gr8() {
+                    r -= 1;
+                }
+
+                op.push(r);
+                op.extend_from_slice(&ModRm::regWimm(i, reg));
+
+                if let Some(imm) = imm {
+                    op.push(imm as u8);
+                }
+
+                if reg.needs_rex() {
+                    return Ok((buildOpcode(mandatory, Some(rex), op), None));
+                }
+
+                (buildOpcode(mandatory, rex.option(), op), None)
+            }
+            Mnemonic::Setg | Mnemonic::Setge | Mnemonic::Setl | Mnemonic::Setle | Mnemonic::Sete | Mnemonic::Setne |
+            Mnemonic::Seta | Mnemonic::Setae | Mnemonic::Setb | Mnemonic::Setbe | Mnemonic::Seto |
+            Mnemonic::Setp | Mnemonic::Setnp => {
                 let mut op = vec![];
                 let rex;
 
@@ -385,6 +652,13 @@ impl X64MCInstr {
                     Mnemonic::Setle => 0x9E,
                     Mnemonic::Sete => 0x94,
                     Mnemonic::Setne => 0x95,
+                    Mnemonic::Seta => 0x97,
+                    Mnemonic::Setae => 0x93,
+                    Mnemonic::Setb => 0x92,
+                    Mnemonic::Setbe => 0x96,
+                    Mnemonic::Seto => 0x90,
+                    Mnemonic::Setp => 0x9A,
+                    Mnemonic::Setnp => 0x9B,
                     _ => unreachable!(),
                 };
 
@@ -395,7 +669,7 @@ impl X64MCInstr {
 
                     rex = {
                         let mut rex = RexPrefix::none();
-                        rex.r = reg.extended();
+                        rex.b = reg.extended();
                         if reg.sub64() != x64Reg::Rax &&
                            reg.sub64() != x64Reg::Rbx &&
                            reg.sub64() != x64Reg::Rcx &&
@@ -404,9 +678,9 @@ impl X64MCInstr {
                         } else { None }
                     };
 
-                    op.extend_from_slice( &ModRm::regWimm(0, reg) );
+                    op.extend_from_slice( &ModRm::regWimm(0, reg.sub8()) );
                 } else if let Some(Operand::Mem(mem)) = &self.op1 {
-                    rex = mem.rex(true).option();
+                    rex = mem.rex().option();
                     op.extend_from_slice( &ModRm::imMem(0, mem.to_owned()) );
                 } else { unreachable!() }
 
@@ -479,7 +753,49 @@ impl X64MCInstr {
                     Err(InstrEncodingError::InvalidVariant(self.clone(), "ret isn't allowed to have operands".into()))?
                 }
             },
-            Mnemonic::Movzx => todo!(),
+            Mnemonic::Movzx | Mnemonic::Movsx | Mnemonic::Movsxd => {
+                if let (Some(Operand::Reg(dst)), Some(Operand::Reg(src))) = (&self.op1, &self.op2) {
+                    if dst.is_xmm() || src.is_xmm() || src.is_gr64() {
+                        Err(InstrEncodingError::InvalidVariant(self.clone(), "movzx/movsx/movsxd need a general purpose register as their source and destination".into()))?
+                    }
+                } else {
+                    Err(InstrEncodingError::InvalidVariant(self.clone(), "movzx/movsx/movsxd need two register operands".into()))?
+                }
+            },
+            Mnemonic::Movss | Mnemonic::Movsd => {
+                match (&self.op1, &self.op2) {
+                    (Some(Operand::Reg(op1)), Some(Operand::Reg(op2))) if op1.is_xmm() && op2.is_xmm() => {},
+                    (Some(Operand::Reg(reg)), Some(Operand::Mem(_))) | 
+                    (Some(Operand::Mem(_)), Some(Operand::Reg(reg))) if reg.is_xmm() => {},
+                    _ => Err(InstrEncodingError::InvalidVariant(self.clone(), format!("{} needs an xmm register and either an xmm register or a memory operand", self.mnemonic)))?,
+                }
+            },
+            Mnemonic::Movaps | Mnemonic::Addss | Mnemonic::Addsd | Mnemonic::Subss | Mnemonic::Subsd |
+            Mnemonic::Mulss | Mnemonic::Mulsd | Mnemonic::Divss | Mnemonic::Divsd | Mnemonic::Ucomiss | 
+            Mnemonic::Ucomisd | Mnemonic::Cvtss2sd | Mnemonic::Cvtsd2ss => {
+                if let (Some(Operand::Reg(op1)), Some(Operand::Reg(op2))) = (&self.op1, &self.op2) {
+                    if !op1.is_xmm() || !op2.is_xmm() {
+                        Err(InstrEncodingError::InvalidVariant(self.clone(), format!("{} needs two xmm registers as its operands", self.mnemonic)))?
+                    }
+                } else {
+                    Err(InstrEncodingError::InvalidVariant(self.clone(), format!("{} needs two register operands", self.mnemonic)))?
+                }
+            },
+            Mnemonic::Movd | Mnemonic::Movq | Mnemonic::Cvtsi2ss | Mnemonic::Cvtsi2sd | Mnemonic::Cvttss2si | Mnemonic::Cvttsd2si => {
+                if let (Some(Operand::Reg(op1)), Some(Operand::Reg(op2))) = (&self.op1, &self.op2) {
+                    let valid = match self.mnemonic {
+                        Mnemonic::Movd | Mnemonic::Movq => op1.is_xmm() != op2.is_xmm(),
+                        Mnemonic::Cvtsi2ss | Mnemonic::Cvtsi2sd => op1.is_xmm() && !op2.is_xmm(),
+                        _ => !op1.is_xmm() && op2.is_xmm(),
+                    };
+
+                    if !valid {
+                        Err(InstrEncodingError::InvalidVariant(self.clone(), format!("{} needs one xmm and one general purpose register", self.mnemonic)))?
+                    }
+                } else {
+                    Err(InstrEncodingError::InvalidVariant(self.clone(), format!("{} needs two register operands", self.mnemonic)))?
+                }
+            },
             Mnemonic::Call | Mnemonic::Jmp => {
                 if self.op2 != None {
                     Err(InstrEncodingError::InvalidVariant(self.clone(), "call/jmp only needs one operand".into()))?
@@ -487,7 +803,9 @@ impl X64MCInstr {
 
                 if let Some(Operand::Imm(_)) = self.op1 {} else {
                     if let Some(Operand::Mem(_)) = self.op1 {} else {
-                        Err(InstrEncodingError::InvalidVariant(self.clone(), "call/jmp can needs to have num/mem operand".into()))?
+                        if let Some(Operand::Reg(_)) = self.op1 {} else {
+                            Err(InstrEncodingError::InvalidVariant(self.clone(), "call/jmp can needs to have num/mem/reg operand".into()))?
+                        }
                     }
                 }
             }
@@ -497,23 +815,78 @@ impl X64MCInstr {
                     Err(InstrEncodingError::InvalidVariant(self.clone(), "endbr64 can't have operands".to_string()))?
                 }
             }
-            Mnemonic::Mul | Mnemonic::Imul => {
+            Mnemonic::Mul | Mnemonic::Imul | Mnemonic::Div | Mnemonic::Idiv | Mnemonic::Neg | Mnemonic::Not => {
                 if !(self.op1 != None && self.op2 == None) {
-                    Err(InstrEncodingError::InvalidVariant(self.clone(), "mul/imul need on operand of type register".into()))?
+                    Err(InstrEncodingError::InvalidVariant(self.clone(), format!("{} needs one operand of type register", self.mnemonic)))?
                 }
 
                 if let Some(Operand::Reg(_)) = self.op1 {} else {
                     Err(InstrEncodingError::InvalidVariant(self.clone(), 
-                        "mul/imul need one operand of type registry".into()
+                        format!("{} needs one operand of type register", self.mnemonic)
                     ))?
                 }
             }
-            Mnemonic::Jne => {
+            Mnemonic::Popcnt => {
+                if let (Some(Operand::Reg(dst)), Some(Operand::Reg(src))) = (&self.op1, &self.op2) {
+                    if !(dst.is_gr32() || dst.is_gr64()) || !(src.is_gr32() || src.is_gr64()) {
+                        Err(InstrEncodingError::InvalidVariant(self.clone(), "popcnt needs two 32 or 64 bit general purpose registers".into()))?
+                    }
+                } else {
+                    Err(InstrEncodingError::InvalidVariant(self.clone(), "popcnt needs two register operands".into()))?
+                }
+            }
+            Mnemonic::Bswap => {
+                if self.op2.is_some() {
+                    Err(InstrEncodingError::InvalidVariant(self.clone(), "bswap only needs one operand".into()))?
+                }
+
+                if let Some(Operand::Reg(reg)) = &self.op1 {
+                    if !(reg.is_gr32() || reg.is_gr64()) {
+                        Err(InstrEncodingError::InvalidVariant(self.clone(), "bswap needs a 32 or 64 bit general purpose register".into()))?
+                    }
+                } else {
+                    Err(InstrEncodingError::InvalidVariant(self.clone(), "bswap needs one register operand".into()))?
+                }
+            }
+            Mnemonic::Xchg | Mnemonic::Cmpxchg | Mnemonic::Xadd => {
+                if let (Some(Operand::Mem(_)), Some(Operand::Reg(reg))) = (&self.op1, &self.op2) {
+                    if reg.is_xmm() {
+                        Err(InstrEncodingError::InvalidVariant(self.clone(), format!("{} needs a general purpose register", self.mnemonic)))?
+                    }
+                } else {
+                    Err(InstrEncodingError::InvalidVariant(self.clone(), format!("{} needs a memory and a register operand", self.mnemonic)))?
+                }
+            }
+            Mnemonic::Cdq | Mnemonic::Cqo | Mnemonic::Ud2 | Mnemonic::Lock | Mnemonic::Fs | Mnemonic::Mfence => {
+                if self.op1.is_some() || self.op2.is_some() {
+                    Err(InstrEncodingError::InvalidVariant(self.clone(), format!("{} can't have operands", self.mnemonic)))?
+                }
+            }
+            Mnemonic::Cmove | Mnemonic::Cmovne | Mnemonic::Cmovg | Mnemonic::Cmovge | Mnemonic::Cmovl | Mnemonic::Cmovle |
+            Mnemonic::Cmova | Mnemonic::Cmovae | Mnemonic::Cmovb | Mnemonic::Cmovbe | Mnemonic::Bsf | Mnemonic::Bsr => {
+                if let (Some(Operand::Reg(dst)), Some(Operand::Reg(src))) = (&self.op1, &self.op2) {
+                    if dst.is_xmm() || src.is_xmm() || dst.is_gr8() || src.is_gr8() {
+                        Err(InstrEncodingError::InvalidVariant(self.clone(), format!("{} needs two 16, 32 or 64 bit general purpose registers", self.mnemonic)))?
+                    }
+                } else {
+                    Err(InstrEncodingError::InvalidVariant(self.clone(), format!("{} needs two register operands", self.mnemonic)))?
+                }
+            }
+            Mnemonic::Shl | Mnemonic::Shr | Mnemonic::Sar => {
+                match (&self.op1, &self.op2) {
+                    (Some(Operand::Reg(reg)), Some(Operand::Reg(x64Reg::Cl))) |
+                    (Some(Operand::Reg(reg)), Some(Operand::Imm(0..=255))) if !reg.is_xmm() => {},
+                    _ => Err(InstrEncodingError::InvalidVariant(self.clone(), format!("{} needs a register and either cl or an imm8 as its operands", self.mnemonic)))?,
+                }
+            }
+            Mnemonic::Jne | Mnemonic::Je | Mnemonic::Jg | Mnemonic::Ja | Mnemonic::Jb | Mnemonic::Jo => {
                 if let Some(Operand::Imm(_)) = self.op1 {} else {
-                    Err(InstrEncodingError::InvalidVariant(self.to_owned(), "jne expects one imm as its ops".to_owned()))?
+                    Err(InstrEncodingError::InvalidVariant(self.to_owned(), format!("{} expects one imm as its ops", self.mnemonic)))?
                 }
             }
-            Mnemonic::Setg | Mnemonic::Setge | Mnemonic::Setl | Mnemonic::Setle | Mnemonic::Sete | Mnemonic::Setne => {
+            Mnemonic::Setg | Mnemonic::Setge | Mnemonic::Setl | Mnemonic::Setle | Mnemonic::Sete | Mnemonic::Setne |
+            Mnemonic::Seta | Mnemonic::Setae | Mnemonic::Setb | Mnemonic::Setbe | Mnemonic::Seto |
+            Mnemonic::Setp | Mnemonic::Setnp => {
                 if self.op2.is_some() || self.op1.is_none() {
                     Err(InstrEncodingError::InvalidVariant(self.to_owned(), "set.. expects one operand".to_owned()))?
                 }
@@ -548,6 +921,7 @@ impl X64MCInstr {
                 Operand::Mem(mem) => profile.markup(&format!("{}", mem), ColorClass::Var),
                 Operand::LinkDestination(_, _) => "".to_string(),
                 Operand::BlockLinkDestination(_, _) => "".to_string(),
+                Operand::JumpTableEntry(_, _, _) => "".to_string(),
                 Operand::Debug(s) => s.to_string(),
             }));
             if let Some(op2) = &self.op2 {
@@ -557,6 +931,7 @@ impl X64MCInstr {
                     Operand::Mem(mem) => profile.markup(&format!("{}", mem), ColorClass::Var),
                     Operand::LinkDestination(_, _) => "".to_string(),
                     Operand::BlockLinkDestination(_, _) => "".to_string(),
+                    Operand::JumpTableEntry(_, _, _) => "".to_string(),
                     Operand::Debug(s) => s.to_string(),
                 }));
             }
@@ -607,7 +982,8 @@ impl X64MCInstr {
         match self.mnemonic {
             Mnemonic::Add | Mnemonic::Adc | Mnemonic::And | 
             Mnemonic::Or | Mnemonic::Xor |Mnemonic::Sub | 
-            Mnemonic::Mov | Mnemonic::Movzx | Mnemonic::Lea => {
+            Mnemonic::Mov | Mnemonic::Movzx | Mnemonic::Movsx | Mnemonic::Movsxd | Mnemonic::Lea |
+            Mnemonic::Movaps | Mnemonic::Movd | Mnemonic::Movq | Mnemonic::Movss | Mnemonic::Movsd => {
               if self.op1 == *op {
                 true
               } else { false }
@@ -697,10 +1073,38 @@ pub enum Mnemonic {
 
     Imul,
     Mul,
+    Idiv,
+    Div,
+    Neg,
+    Not,
+    Cdq,
+    Cqo,
+    Ud2,
+
+    Bsf,
+    Bsr,
+    Popcnt,
+    Bswap,
+
+    Lock,
+    Fs,
+    Xchg,
+    Cmpxchg,
+    Xadd,
+    Mfence,
+
+    Shl,
+    Shr,
+    Sar,
 
     Call,
     Jmp,
     Jne,
+    Je,
+    Jg,
+    Ja,
+    Jb,
+    Jo,
 
     Endbr64,
 
@@ -720,6 +1124,49 @@ pub enum Mnemonic {
     Setl,
     Setge,
     Setle,
+    Seta,
+    Setae,
+    Setb,
+    Setbe,
+    Seto,
+    Setp,
+    Setnp,
+
+    Cmove,
+    Cmovne,
+    Cmovg,
+    Cmovge,
+    Cmovl,
+    Cmovle,
+    Cmova,
+    Cmovae,
+    Cmovb,
+    Cmovbe,
+
+    Movsx,
+    Movsxd,
+
+    Movaps,
+    Movd,
+    Movq,
+    Movss,
+    Movsd,
+    Addss,
+    Addsd,
+    Subss,
+    Subsd,
+    Mulss,
+    Mulsd,
+    Divss,
+    Divsd,
+    Ucomiss,
+    Ucomisd,
+    Cvtsi2ss,
+    Cvtsi2sd,
+    Cvttss2si,
+    Cvttsd2si,
+    Cvtss2sd,
+    Cvtsd2ss,
 }
 
 impl FromStr for Mnemonic {
@@ -744,7 +1191,32 @@ impl FromStr for Mnemonic {
             "endbr64" => Ok(Mnemonic::Endbr64),
             "imul" => Ok(Mnemonic::Imul),
             "mul" => Ok(Mnemonic::Mul),
+            "idiv" => Ok(Mnemonic::Idiv),
+            "div" => Ok(Mnemonic::Div),
+            "neg" => Ok(Mnemonic::Neg),
+            "not" => Ok(Mnemonic::Not),
+            "cdq" => Ok(Mnemonic::Cdq),
+            "cqo" => Ok(Mnemonic::Cqo),
+            "ud2" => Ok(Mnemonic::Ud2),
+            "lock" => Ok(Mnemonic::Lock),
+            "fs" => Ok(Mnemonic::Fs),
+            "xchg" => Ok(Mnemonic::Xchg),
+            "cmpxchg" => Ok(Mnemonic::Cmpxchg),
+            "xadd" => Ok(Mnemonic::Xadd),
+            "mfence" => Ok(Mnemonic::Mfence),
+            "bsf" => Ok(Mnemonic::Bsf),
+            "bsr" => Ok(Mnemonic::Bsr),
+            "popcnt" => Ok(Mnemonic::Popcnt),
+            "bswap" => Ok(Mnemonic::Bswap),
+            "shl" => Ok(Mnemonic::Shl),
+            "shr" => Ok(Mnemonic::Shr),
+            "sar" => Ok(Mnemonic::Sar),
             "jne" => Ok(Mnemonic::Jne),
+            "je" => Ok(Mnemonic::Je),
+            "jg" => Ok(Mnemonic::Jg),
+            "ja" => Ok(Mnemonic::Ja),
+            "jb" => Ok(Mnemonic::Jb),
+            "jo" => Ok(Mnemonic::Jo),
             "cmp" => Ok(Mnemonic::Cmp),
             "sete" => Ok(Mnemonic::Sete),
             "setne" => Ok(Mnemonic::Setne),
@@ -752,6 +1224,46 @@ impl FromStr for Mnemonic {
             "setl" => Ok(Mnemonic::Setl),
             "setge" => Ok(Mnemonic::Setge),
             "setle" => Ok(Mnemonic::Setle),
+            "seta" => Ok(Mnemonic::Seta),
+            "setae" => Ok(Mnemonic::Setae),
+            "setb" => Ok(Mnemonic::Setb),
+            "setbe" => Ok(Mnemonic::Setbe),
+            "seto" => Ok(Mnemonic::Seto),
+            "setp" => Ok(Mnemonic::Setp),
+            "setnp" => Ok(Mnemonic::Setnp),
+            "cmove" => Ok(Mnemonic::Cmove),
+            "cmovne" => Ok(Mnemonic::Cmovne),
+            "cmovg" => Ok(Mnemonic::Cmovg),
+            "cmovge" => Ok(Mnemonic::Cmovge),
+            "cmovl" => Ok(Mnemonic::Cmovl),
+            "cmovle" => Ok(Mnemonic::Cmovle),
+            "cmova" => Ok(Mnemonic::Cmova),
+            "cmovae" => Ok(Mnemonic::Cmovae),
+            "cmovb" => Ok(Mnemonic::Cmovb),
+            "cmovbe" => Ok(Mnemonic::Cmovbe),
+            "movsx" => Ok(Mnemonic::Movsx),
+            "movsxd" => Ok(Mnemonic::Movsxd),
+            "movaps" => Ok(Mnemonic::Movaps),
+            "movss" => Ok(Mnemonic::Movss),
+            "movsd" => Ok(Mnemonic::Movsd),
+            "movd" => Ok(Mnemonic::Movd),
+            "movq" => Ok(Mnemonic::Movq),
+            "addss" => Ok(Mnemonic::Addss),
+            "addsd" => Ok(Mnemonic::Addsd),
+            "subss" => Ok(Mnemonic::Subss),
+            "subsd" => Ok(Mnemonic::Subsd),
+            "mulss" => Ok(Mnemonic::Mulss),
+            "mulsd" => Ok(Mnemonic::Mulsd),
+            "divss" => Ok(Mnemonic::Divss),
+            "divsd" => Ok(Mnemonic::Divsd),
+            "ucomiss" => Ok(Mnemonic::Ucomiss),
+            "ucomisd" => Ok(Mnemonic::Ucomisd),
+            "cvtsi2ss" => Ok(Mnemonic::Cvtsi2ss),
+            "cvtsi2sd" => Ok(Mnemonic::Cvtsi2sd),
+            "cvttss2si" => Ok(Mnemonic::Cvttss2si),
+            "cvttsd2si" => Ok(Mnemonic::Cvttsd2si),
+            "cvtss2sd" => Ok(Mnemonic::Cvtss2sd),
+            "cvtsd2ss" => Ok(Mnemonic::Cvtsd2ss),
             _ => Err(()),
         }
     }
@@ -777,11 +1289,36 @@ impl Display for Mnemonic {
             Mnemonic::Endbr64 => "endbr64",
             Mnemonic::Mul => "mul",
             Mnemonic::Imul => "imul",
+            Mnemonic::Idiv => "idiv",
+            Mnemonic::Div => "div",
+            Mnemonic::Neg => "neg",
+            Mnemonic::Not => "not",
+            Mnemonic::Cdq => "cdq",
+            Mnemonic::Cqo => "cqo",
+            Mnemonic::Ud2 => "ud2",
+            Mnemonic::Lock => "lock",
+            Mnemonic::Fs => "fs",
+            Mnemonic::Xchg => "xchg",
+            Mnemonic::Cmpxchg => "cmpxchg",
+            Mnemonic::Xadd => "xadd",
+            Mnemonic::Mfence => "mfence",
+            Mnemonic::Bsf => "bsf",
+            Mnemonic::Bsr => "bsr",
+            Mnemonic::Popcnt => "popcnt",
+            Mnemonic::Bswap => "bswap",
+            Mnemonic::Shl => "shl",
+            Mnemonic::Shr => "shr",
+            Mnemonic::Sar => "sar",
             Mnemonic::Link => "",
             Mnemonic::StartOptimization => "",
             Mnemonic::EndOptimization => "",
             Mnemonic::Debug => "#",
             Mnemonic::Jne => "jne",
+            Mnemonic::Je => "je",
+            Mnemonic::Jg => "jg",
+            Mnemonic::Ja => "ja",
+            Mnemonic::Jb => "jb",
+            Mnemonic::Jo => "jo",
             Mnemonic::Cmp => "cmp",
             Mnemonic::Sete => "sete",
             Mnemonic::Setg => "setg",
@@ -789,6 +1326,46 @@ impl Display for Mnemonic {
             Mnemonic::Setge => "setge",
             Mnemonic::Setle => "setle",
             Mnemonic::Setne => "setne",
+            Mnemonic::Seta => "seta",
+            Mnemonic::Setae => "setae",
+            Mnemonic::Setb => "setb",
+            Mnemonic::Setbe => "setbe",
+            Mnemonic::Seto => "seto",
+            Mnemonic::Setp => "setp",
+            Mnemonic::Setnp => "setnp",
+            Mnemonic::Cmove => "cmove",
+            Mnemonic::Cmovne => "cmovne",
+            Mnemonic::Cmovg => "cmovg",
+            Mnemonic::Cmovge => "cmovge",
+            Mnemonic::Cmovl => "cmovl",
+            Mnemonic::Cmovle => "cmovle",
+            Mnemonic::Cmova => "cmova",
+            Mnemonic::Cmovae => "cmovae",
+            Mnemonic::Cmovb => "cmovb",
+            Mnemonic::Cmovbe => "cmovbe",
+            Mnemonic::Movsx => "movsx",
+            Mnemonic::Movsxd => "movsxd",
+            Mnemonic::Movaps => "movaps",
+            Mnemonic::Movss => "movss",
+            Mnemonic::Movsd => "movsd",
+            Mnemonic::Movd => "movd",
+            Mnemonic::Movq => "movq",
+            Mnemonic::Addss => "addss",
+            Mnemonic::Addsd => "addsd",
+            Mnemonic::Subss => "subss",
+            Mnemonic::Subsd => "subsd",
+            Mnemonic::Mulss => "mulss",
+            Mnemonic::Mulsd => "mulsd",
+            Mnemonic::Divss => "divss",
+            Mnemonic::Divsd => "divsd",
+            Mnemonic::Ucomiss => "ucomiss",
+            Mnemonic::Ucomisd => "ucomisd",
+            Mnemonic::Cvtsi2ss => "cvtsi2ss",
+            Mnemonic::Cvtsi2sd => "cvtsi2sd",
+            Mnemonic::Cvttss2si => "cvttss2si",
+            Mnemonic::Cvttsd2si => "cvttsd2si",
+            Mnemonic::Cvtss2sd => "cvtss2sd",
+            Mnemonic::Cvtsd2ss => "cvtsd2ss",
         })
     }
 }
@@ -806,6 +1383,8 @@ pub enum Operand {
     LinkDestination(String, i64),
     /// A link destination to a block
     BlockLinkDestination(String, i64),
+    /// An entry of a jump table (the table, the block, the offset of the entry in the table)
+    JumpTableEntry(String, String, i64),
     /// For debugging
     Debug(String),
 }
@@ -831,6 +1410,7 @@ impl Display for Operand {
             Operand::Mem(mem) => format!("{}", mem),
             Operand::LinkDestination(_, _) => "".to_string(),
             Operand::BlockLinkDestination(_, _) => "".to_string(),
+            Operand::JumpTableEntry(_, _, _) => "".to_string(),
             Operand::Debug(s) => s.to_string(),
         })
     }
@@ -852,77 +1432,66 @@ pub struct MemOp {
 }
 
 impl MemOp {
-    #[doc(hidden)]
-    pub fn encode(&self, basis: Option<x64Reg>) -> (/*modrm mod*/u8, Vec<u8>) {
-        let mut scale = match self.scale {
-            0 => 0,
-            1 => 0,
+    /// Encodes the modrm byte (with `field` as the reg field), the optional sib byte and the displacement
+    pub fn encode(&self, field: u8) -> Vec<u8> {
+        let field = (field & 0b111) << 3;
+
+        if self.rip {
+            let mut out = vec![field | 0b101];
+            out.extend_from_slice(&(self.displ as i32).to_le_bytes());
+            return out;
+        }
+
+        let scale = match self.scale {
+            0 | 1 => 0,
             2 => 1,
             4 => 2,
             8 => 3,
             _ => todo!("scale needs to be either 1/2/4/8")
         };
 
-        let mut displ = vec![];
+        let index = if let Some(index) = &self.index { index.enc() } else { 0b100 };
+
+        let base = if let Some(base) = &self.base { *base } else {
+            // [index * scale + disp32]
+            let mut out = vec![field | 0b100, scale << 6 | index << 3 | 0b101];
+            out.extend_from_slice(&(self.displ as i32).to_le_bytes());
+            return out;
+        };
 
-        if self.displ == 0 {
-        } else if self.displ >= -128 && self.displ <= 127 {
-            scale = 0b01;
-            displ.push(self.displ as u8);
+        // rbp/r13 can't be encoded without a displacement
+        let (md, displ) = if self.displ == 0 && base.enc() != 0b101 {
+            (0b00, vec![])
+        } else if self.displ >= i8::MIN as isize && self.displ <= i8::MAX as isize {
+            (0b01, vec![self.displ as u8])
         } else {
-            scale = 0b10;
-            displ.extend_from_slice(&(self.displ as i32).to_le_bytes());
-        }
+            (0b10, (self.displ as i32).to_le_bytes().to_vec())
+        };
 
-        let mut sib = 0;
+        let mut out = vec![];
 
-        if let Some(index) = &self.index {
-            if let Some(base) = &self.base {
-                sib |= (scale << 6) | (index.enc() << 3) | base.enc();
-            } else {
-                if let Some(base) = &basis {
-                    sib |= (scale << 6) | (index.enc() << 3) | base.enc();
-                } else {
-                    sib |= (scale << 6) | (index.enc() << 3);
-                }
-            }
+        // rsp/r12 as the base always need a sib byte
+        if self.index.is_some() || base.enc() == 0b100 {
+            out.push(md << 6 | field | 0b100);
+            out.push(scale << 6 | index << 3 | base.enc());
         } else {
-            if let Some(base) = &self.base {
-                if let Some(basis) = &basis {
-                    sib |= (scale << 6) | basis.enc() << 3 | base.enc();
-                } else {
-                    sib |= (scale << 6) | 0b100 << 3 | base.enc();
-                }
-            } else {
-                if let Some(base) = &basis {
-                    sib |= (scale << 6) | 0b100 << 3 | base.enc();
-                } else {
-                    sib |= (scale << 6) | 0b100 << 3 | 0b100;
-                }
-            }
+            out.push(md << 6 | field | base.enc());
         }
 
-        
-        let mut encoding = vec![sib];
-        encoding.extend_from_slice(&displ);
+        out.extend_from_slice(&displ);
 
-        (0, encoding)
+        out
     }
 
     /// Returns the used rex prefix for the memory displacment
-    pub fn rex(&self, front: bool) -> RexPrefix {
+    pub fn rex(&self) -> RexPrefix {
         let mut rex = RexPrefix::none();
         if let Some(base) = &self.base {
-            let base = base.as_any().downcast_ref::<x64Reg>().unwrap();
-            if front {
-                rex.r = base.extended();
-            } else {
-                rex.b = base.extended();
-            }
+            rex.b = base.extended();
         }
         
         if let Some(index) = &self.index {
-            rex.x = index.as_any().downcast_ref::<x64Reg>().unwrap().extended();
+            rex.x = index.extended();
         }
 
         rex
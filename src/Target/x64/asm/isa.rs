@@ -70,6 +70,9 @@ fn add_rex_to_option(option: &mut Option<RexPrefix>, w: bool, r: bool, x: bool,
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MandatoryPrefix {
     t16BitOps,
+    tScalarSingle,
+    tScalarDouble,
+    tSse,
 }
 
 pub(crate) fn buildOpcode(mandatory: Option<MandatoryPrefix>, rex: Option<RexPrefix>, op: Vec<u8>) -> Vec<u8> {
@@ -78,6 +81,9 @@ pub(crate) fn buildOpcode(mandatory: Option<MandatoryPrefix>, rex: Option<RexPre
     if let Some(man) = mandatory {
         out.extend_from_slice(&match man {
             MandatoryPrefix::t16BitOps => vec![0x66],
+            MandatoryPrefix::tScalarSingle => vec![0xF3],
+            MandatoryPrefix::tScalarDouble => vec![0xF2],
+            MandatoryPrefix::tSse => vec![0x66],
         })
     }
 
@@ -103,7 +109,7 @@ pub struct ModRm {}
 
 impl ModRm {
     pub fn regRipImm(reg: x64Reg, imm: i32) -> Vec<u8> {
-        let mut out = vec![0 | reg.enc() | 0b101];
+        let mut out = vec![reg.enc() << 3 | 0b101];
 
         let bytes = imm.to_be_bytes();
 
@@ -119,21 +125,11 @@ impl ModRm {
     }
 
     pub fn regM(reg: x64Reg, mem: MemOp) -> Vec<u8> {
-        let enc = mem.encode(Some(reg));
-        let mut out = vec![];
-
-        if let Some(_) = mem.index {
-            out = vec![enc.0 << 6 | reg.enc() << 3 | 0b100]
-        }
-
-        out.extend_from_slice(&enc.1);
-        out
+        mem.encode(reg.enc())
     }
 
     pub fn memR(mem: MemOp, reg: x64Reg) -> Vec<u8> {
-        let mut out = vec![mem.encode(Some(reg)).0 << 6 | reg.enc() << 3 | 0b100];
-        out.extend_from_slice(&mem.encode(Some(reg)).1);
-        out
+        mem.encode(reg.enc())
     }
 
     pub fn regWimm(i: u8, reg: x64Reg) -> Vec<u8> {
@@ -141,8 +137,6 @@ impl ModRm {
     }
 
     pub fn imMem(i: u8, mem: MemOp) -> Vec<u8> {
-        let mut out = vec![mem.encode(None).0 << 6 | i << 3 | 0b100];
-        out.extend_from_slice(&mem.encode(None).1);
-        out
+        mem.encode(i)
     }
 }
\ No newline at end of file
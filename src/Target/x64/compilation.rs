@@ -25,6 +25,17 @@ pub(crate) fn construct_compilation_helper(call_conv: CallConv) -> CompilationHe
 
     helper.regs.reverse(Arch::X86_64);
 
+    // xmm14 and xmm15 are used as temporarys while lowering
+    // and xmm6->xmm15 are callee saved on windows, so only xmm0->xmm5 hold variables
+    helper.fp_regs.push(Arch::X86_64, Reg::x64(x64Reg::Xmm0));
+    helper.fp_regs.push(Arch::X86_64, Reg::x64(x64Reg::Xmm1));
+    helper.fp_regs.push(Arch::X86_64, Reg::x64(x64Reg::Xmm2));
+    helper.fp_regs.push(Arch::X86_64, Reg::x64(x64Reg::Xmm3));
+    helper.fp_regs.push(Arch::X86_64, Reg::x64(x64Reg::Xmm4));
+    helper.fp_regs.push(Arch::X86_64, Reg::x64(x64Reg::Xmm5));
+
+    helper.fp_regs.reverse(Arch::X86_64);
+
     helper.lower = Some(super::lower::x64_lower);
 
     helper
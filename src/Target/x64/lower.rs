@@ -1,39 +1,85 @@
-use crate::prelude::CmpMode;
-use crate::CodeGen::{MCInstr, MachineInstr, MachineMnemonic};
+use crate::prelude::{AtomicOrdering, AtomicRmwOp, CmpMode, OverflowOp};
+use crate::CodeGen::{MCInstr, MachineInstr, MachineMnemonic, MachineOperand};
 use crate::Optimizations::Optimize;
+use crate::Obj::TlsModel;
 use crate::Target::CallConv;
+use crate::IR::TypeMetadata;
 
 use super::{instr::{MemOp, Mnemonic, Operand, X64MCInstr}, x64Reg};
 
-fn x64_lower_instr(conv: CallConv, sink: &mut Vec<X64MCInstr>, instr: MachineInstr) {
+fn x64_lower_instr(sink: &mut Vec<X64MCInstr>, instr: MachineInstr) {
     match &instr.mnemonic {
+        MachineMnemonic::Add if instr.meta.float() => x64_lower_fmath(sink, &instr, Mnemonic::Addss, Mnemonic::Addsd),
+        MachineMnemonic::Sub if instr.meta.float() => x64_lower_fmath(sink, &instr, Mnemonic::Subss, Mnemonic::Subsd),
+        MachineMnemonic::Mul if instr.meta.float() => x64_lower_fmath(sink, &instr, Mnemonic::Mulss, Mnemonic::Mulsd),
+        MachineMnemonic::Div if instr.meta.float() => x64_lower_fmath(sink, &instr, Mnemonic::Divss, Mnemonic::Divsd),
+        MachineMnemonic::Compare(mode) if instr.meta.float() => x64_lower_fcmp(sink, &instr, mode),
+
         MachineMnemonic::Move => x64_lower_move(sink, &instr),
         MachineMnemonic::Add => x64_lower_add(sink, &instr),
         MachineMnemonic::And => x64_lower_and(sink, &instr),
-        MachineMnemonic::Div => x64_lower_div(sink, &instr),
+        MachineMnemonic::Div => x64_lower_div(sink, &instr, false),
+        MachineMnemonic::Rem => x64_lower_div(sink, &instr, true),
         MachineMnemonic::Mul => x64_lower_mul(sink, &instr),
         MachineMnemonic::Or => x64_lower_or(sink, &instr),
         MachineMnemonic::Sub => x64_lower_sub(sink, &instr),
         MachineMnemonic::Xor => x64_lower_xor(sink, &instr),
+        MachineMnemonic::Shl => x64_lower_shift(sink, &instr, Mnemonic::Shl),
+        MachineMnemonic::Lshr => x64_lower_shift(sink, &instr, Mnemonic::Shr),
+        MachineMnemonic::Ashr => x64_lower_shift(sink, &instr, Mnemonic::Sar),
+        MachineMnemonic::Neg => x64_lower_neg(sink, &instr),
+        MachineMnemonic::Not => x64_lower_not(sink, &instr),
+        MachineMnemonic::Bswap => x64_lower_bswap(sink, &instr),
+        MachineMnemonic::Ctpop => x64_lower_ctpop(sink, &instr),
+        MachineMnemonic::Ctlz => x64_lower_ctlz(sink, &instr),
+        MachineMnemonic::Cttz => x64_lower_cttz(sink, &instr),
         MachineMnemonic::Zext => x64_lower_zext(sink, &instr),
+        MachineMnemonic::Sext => x64_lower_sext(sink, &instr),
         MachineMnemonic::Downcast => x64_lower_downcast(sink, &instr),
-        MachineMnemonic::Call(to) => x64_lower_call(conv, sink, &instr, to),
+        MachineMnemonic::Call(to) => x64_lower_call(sink, &instr, to),
+        MachineMnemonic::CallIndirect => x64_lower_call_indirect(sink, &instr),
+        MachineMnemonic::TailCall(to) => x64_lower_tail_call(sink, &instr, to),
         MachineMnemonic::Return => x64_lower_return(sink, &instr),
+        MachineMnemonic::Unreachable => x64_lower_unreachable(sink, &instr),
         MachineMnemonic::AdressLoad(to) => x64_lower_adr_load(sink, &instr, to),
+        MachineMnemonic::TlsAdressLoad(to, model) => x64_lower_tls_adr_load(sink, &instr, to, *model),
         MachineMnemonic::Br(to) => x64_lower_br(sink, &instr, to),
         MachineMnemonic::BrCond(iftrue, iffalse) => x64_lower_cond_br(sink, &instr, iftrue, iffalse),
+        MachineMnemonic::Switch(table, default, cases) => x64_lower_switch(sink, &instr, table, default, cases),
         MachineMnemonic::Compare(mode) => x64_lower_cmp(sink, &instr, mode),
+        MachineMnemonic::Select => x64_lower_select(sink, &instr),
+        MachineMnemonic::FCast(from) => x64_lower_fcast(sink, &instr, *from),
+        MachineMnemonic::Prolog => x64_lower_prolog(sink, &instr),
+        MachineMnemonic::Epilog => x64_lower_epilog(sink, &instr),
+        MachineMnemonic::Alloca => x64_lower_alloca(sink, &instr),
+        MachineMnemonic::Load => x64_lower_load(sink, &instr),
+        MachineMnemonic::Store => x64_lower_store(sink, &instr),
+        MachineMnemonic::Gep => x64_lower_gep(sink, &instr),
+        MachineMnemonic::MemCpy => x64_lower_memcpy(sink, &instr),
+        MachineMnemonic::MemSet => x64_lower_memset(sink, &instr),
+        MachineMnemonic::AtomicLoad(_) => x64_lower_load(sink, &instr),
+        MachineMnemonic::AtomicStore(order) => x64_lower_atomic_store(sink, &instr, *order),
+        MachineMnemonic::AtomicRmw(op, _) => x64_lower_atomic_rmw(sink, &instr, *op),
+        MachineMnemonic::AtomicCmpXchg(_) => x64_lower_atomic_cmpxchg(sink, &instr),
+        MachineMnemonic::Fence(order) => x64_lower_fence(sink, &instr, *order),
+        MachineMnemonic::Overflow(op) => x64_lower_overflow(sink, &instr, *op),
+        MachineMnemonic::SetOverflow => x64_lower_set_overflow(sink, &instr),
+        MachineMnemonic::BrOverflow(iftrue, iffalse) => x64_lower_overflow_br(sink, &instr, iftrue, iffalse),
+        MachineMnemonic::VaStart(conv) => x64_lower_va_start(sink, &instr, *conv),
+        MachineMnemonic::VaArg(conv) => x64_lower_va_arg(sink, &instr, *conv),
+        MachineMnemonic::InlineAsm(_) => unreachable!("inline assembly is assembled by the target and not lowered"),
     }
 }
 
 /// The function used for lowering general `MachineInstr` into `MCInstr`
-pub(crate) fn x64_lower(conv: CallConv, instrs: Vec<MachineInstr>) -> Vec<Box<dyn MCInstr>> {
+/// (the calling conventions of the calls were already applied while compiling them)
+pub(crate) fn x64_lower(_: CallConv, instrs: Vec<MachineInstr>) -> Vec<Box<dyn MCInstr>> {
     let mut out = vec![
         X64MCInstr::with0(Mnemonic::StartOptimization)
     ];
 
     for instr in instrs {
-        x64_lower_instr(conv, &mut out, instr);
+        x64_lower_instr(&mut out, instr);
     }
 
     out.optimize();
@@ -47,27 +93,234 @@ pub(crate) fn x64_lower(conv: CallConv, instrs: Vec<MachineInstr>) -> Vec<Box<dy
     mc_instrs
 }
 
-fn x64_lower_div(_sink: &mut Vec<X64MCInstr>, _instr: &MachineInstr) {
-    todo!()
+/// moves the operand into the register (extended to at least 32 bits) and returns the used sub register
+fn x64_lower_extend(sink: &mut Vec<X64MCInstr>, reg: x64Reg, op: Operand, ty: TypeMetadata) -> x64Reg {
+    let wide = if ty.bitSize() > 32 { reg.sub64() } else { reg.sub32() };
+
+    match op {
+        Operand::Reg(src) if src.is_gr8() || src.is_gr16() => {
+            let mne = if ty.signed() { Mnemonic::Movsx } else { Mnemonic::Movzx };
+            sink.push(X64MCInstr::with2(mne, Operand::Reg(wide), Operand::Reg(src)));
+        },
+        op => sink.push(X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(wide), op)),
+    }
+
+    wide
+}
+fn x64_lower_div(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, rem: bool) {
+    let op1 = x64_lower_op(instr.operands.first().expect("expected a first operand"));
+    let op2 = x64_lower_op(instr.operands.get(1).expect("expected a second operand"));
+    let out = x64_lower_op(&instr.out.expect("expected a output operand"));
+
+    if instr.meta.float() {
+        unreachable!("remainders of floating point values are rejected by the verifier and the semantic analysis");
+    }
+
+    // the dividend is rdx:rax, so rdx needs to be saved (except it's the output)
+    let save_rdx = !matches!(out, Operand::Reg(reg) if reg.sub64() == x64Reg::Rdx);
+
+    if save_rdx {
+        sink.push( X64MCInstr::with1(Mnemonic::Push, Operand::Reg(x64Reg::Rdx)) );
+    }
+    sink.push( X64MCInstr::with1(Mnemonic::Push, Operand::Reg(x64Reg::Rbx)) );
+
+    // the divisor is moved first, because it could be in rdx
+    let divisor = x64_lower_extend(sink, x64Reg::Rbx, op2, instr.meta);
+    let dividend = x64_lower_extend(sink, x64Reg::Rax, op1, instr.meta);
+
+    if instr.meta.signed() {
+        sink.push( X64MCInstr::with0(if dividend.is_gr64() { Mnemonic::Cqo } else { Mnemonic::Cdq }) );
+        sink.push( X64MCInstr::with1(Mnemonic::Idiv, Operand::Reg(divisor)) );
+    } else {
+        sink.push( X64MCInstr::with2(Mnemonic::Xor, Operand::Reg(x64Reg::Edx), Operand::Reg(x64Reg::Edx)) );
+        sink.push( X64MCInstr::with1(Mnemonic::Div, Operand::Reg(divisor)) );
+    }
+
+    // the quotient is in rax, the remainder in rdx
+    let result = if rem { x64Reg::Rdx } else { x64Reg::Rax };
+
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, out, Operand::Reg(result.sub_ty(instr.meta))) );
+
+    sink.push( X64MCInstr::with1(Mnemonic::Pop, Operand::Reg(x64Reg::Rbx)) );
+    if save_rdx {
+        sink.push( X64MCInstr::with1(Mnemonic::Pop, Operand::Reg(x64Reg::Rdx)) );
+    }
+}
+fn x64_lower_shift(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, mnemonic: Mnemonic) {
+    let op1 = x64_lower_op(instr.operands.first().expect("expected a first operand"));
+    let op2 = x64_lower_op(instr.operands.get(1).expect("expected a second operand"));
+    let out = x64_lower_op(&instr.out.expect("expected a output operand"));
+
+    if instr.meta.float() {
+        unreachable!("shifts of floating point values are rejected by the verifier and the semantic analysis");
+    }
+
+    let tmp = Operand::Reg(x64Reg::Rax.sub_ty(instr.meta));
+
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, tmp.clone(), op1) );
+
+    if let Operand::Imm(amount) = op2 {
+        // the cpu masks the shift amount the same way
+        let mask = if instr.meta.bitSize() > 32 { 63 } else { 31 };
+
+        sink.push( X64MCInstr::with2(mnemonic, tmp.clone(), Operand::Imm(amount & mask)) );
+    } else {
+        // the shift amount needs to be in cl
+        let save_rcx = !matches!(out, Operand::Reg(reg) if reg.sub64() == x64Reg::Rcx);
+
+        if save_rcx {
+            sink.push( X64MCInstr::with1(Mnemonic::Push, Operand::Reg(x64Reg::Rcx)) );
+        }
+
+        sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(x64Reg::Rcx.sub_ty(instr.meta)), op2) );
+        sink.push( X64MCInstr::with2(mnemonic, tmp.clone(), Operand::Reg(x64Reg::Cl)) );
+
+        if save_rcx {
+            sink.push( X64MCInstr::with1(Mnemonic::Pop, Operand::Reg(x64Reg::Rcx)) );
+        }
+    }
+
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, out, tmp) );
+}
+fn x64_lower_neg(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let op1 = x64_lower_op(instr.operands.first().expect("expected a first operand"));
+    let out = x64_lower_op(&instr.out.expect("expected a output operand"));
+
+    let tmp = x64Reg::Rax.sub_ty(instr.meta);
+
+    if instr.meta.float() {
+        // floats are negated by flipping the sign bit
+        if x64_is_xmm(&op1) {
+            x64_lower_fmove(sink, Operand::Reg(tmp), op1, instr.meta);
+        } else {
+            sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(tmp), op1) );
+        }
+
+        let sign = Operand::Reg(x64Reg::Rbx.sub_ty(instr.meta));
+
+        sink.push( X64MCInstr::with1(Mnemonic::Push, Operand::Reg(x64Reg::Rbx)) );
+        sink.push( X64MCInstr::with2(Mnemonic::Mov, sign.clone(), Operand::Imm(1 << (instr.meta.bitSize() - 1))) );
+        sink.push( X64MCInstr::with2(Mnemonic::Xor, Operand::Reg(tmp), sign) );
+        sink.push( X64MCInstr::with1(Mnemonic::Pop, Operand::Reg(x64Reg::Rbx)) );
+
+        x64_lower_fmove(sink, out, Operand::Reg(tmp), instr.meta);
+        return;
+    }
+
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(tmp), op1) );
+    sink.push( X64MCInstr::with1(Mnemonic::Neg, Operand::Reg(tmp)) );
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, out, Operand::Reg(tmp)) );
+}
+fn x64_lower_not(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let op1 = x64_lower_op(instr.operands.first().expect("expected a first operand"));
+    let out = x64_lower_op(&instr.out.expect("expected a output operand"));
+
+    if instr.meta.float() {
+        unreachable!("not of floating point values is rejected by the verifier and the semantic analysis");
+    }
+
+    let tmp = Operand::Reg(x64Reg::Rax.sub_ty(instr.meta));
+
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, tmp.clone(), op1) );
+
+    if instr.meta == TypeMetadata::bool {
+        // a bool only keeps the lowest bit
+        sink.push( X64MCInstr::with2(Mnemonic::Xor, tmp.clone(), Operand::Imm(1)) );
+    } else {
+        sink.push( X64MCInstr::with1(Mnemonic::Not, tmp.clone()) );
+    }
+
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, out, tmp) );
+}
+/// moves the operand zero extended into rax and returns the used sub register (eax or rax)
+fn x64_lower_bit_operand(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) -> x64Reg {
+    let op1 = x64_lower_op(instr.operands.first().expect("expected a first operand"));
+
+    if instr.meta.float() {
+        unreachable!("bit manipulation of floating point values is rejected by the verifier and the semantic analysis");
+    }
+
+    let bits = instr.meta.bitSize();
+    let wide = if bits > 32 { x64Reg::Rax } else { x64Reg::Eax };
+
+    match op1 {
+        Operand::Imm(imm) if bits < 64 => sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(wide), Operand::Imm(imm & ((1 << bits) - 1))) ),
+        Operand::Reg(reg) if reg.is_gr8() || reg.is_gr16() => sink.push( X64MCInstr::with2(Mnemonic::Movzx, Operand::Reg(wide), Operand::Reg(reg)) ),
+        op => sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(wide), op) ),
+    }
+
+    wide
+}
+fn x64_lower_bit_result(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let out = x64_lower_op(&instr.out.expect("expected a output operand"));
+
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, out, Operand::Reg(x64Reg::Rax.sub_ty(instr.meta))) );
+}
+fn x64_lower_bswap(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let tmp = x64_lower_bit_operand(sink, instr);
+
+    match instr.meta.bitSize() {
+        8 => {},
+        16 => {
+            // the swapped bytes end up in the upper half
+            sink.push( X64MCInstr::with1(Mnemonic::Bswap, Operand::Reg(tmp)) );
+            sink.push( X64MCInstr::with2(Mnemonic::Shr, Operand::Reg(tmp), Operand::Imm(16)) );
+        },
+        _ => sink.push( X64MCInstr::with1(Mnemonic::Bswap, Operand::Reg(tmp)) ),
+    }
+
+    x64_lower_bit_result(sink, instr);
+}
+fn x64_lower_ctpop(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let tmp = x64_lower_bit_operand(sink, instr);
+
+    sink.push( X64MCInstr::with2(Mnemonic::Popcnt, Operand::Reg(tmp), Operand::Reg(tmp)) );
+
+    x64_lower_bit_result(sink, instr);
+}
+fn x64_lower_ctlz(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let tmp = x64_lower_bit_operand(sink, instr);
+    let zero = if tmp.is_gr64() { x64Reg::Rbx } else { x64Reg::Ebx };
+
+    // bsr returns the index of the highest set bit, so a zero needs to become -1
+    sink.push( X64MCInstr::with1(Mnemonic::Push, Operand::Reg(x64Reg::Rbx)) );
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(zero), Operand::Imm(-1)) );
+    sink.push( X64MCInstr::with2(Mnemonic::Bsr, Operand::Reg(tmp), Operand::Reg(tmp)) );
+    sink.push( X64MCInstr::with2(Mnemonic::Cmove, Operand::Reg(tmp), Operand::Reg(zero)) );
+    sink.push( X64MCInstr::with1(Mnemonic::Pop, Operand::Reg(x64Reg::Rbx)) );
+
+    // leading zeros = bits - 1 - index
+    sink.push( X64MCInstr::with1(Mnemonic::Neg, Operand::Reg(tmp)) );
+    sink.push( X64MCInstr::with2(Mnemonic::Add, Operand::Reg(tmp), Operand::Imm(instr.meta.bitSize() as i64 - 1)) );
+
+    x64_lower_bit_result(sink, instr);
+}
+fn x64_lower_cttz(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let tmp = x64_lower_bit_operand(sink, instr);
+    let zero = if tmp.is_gr64() { x64Reg::Rbx } else { x64Reg::Ebx };
+
+    // bsf leaves the destination undefined for a zero
+    sink.push( X64MCInstr::with1(Mnemonic::Push, Operand::Reg(x64Reg::Rbx)) );
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(zero), Operand::Imm(instr.meta.bitSize() as i64)) );
+    sink.push( X64MCInstr::with2(Mnemonic::Bsf, Operand::Reg(tmp), Operand::Reg(tmp)) );
+    sink.push( X64MCInstr::with2(Mnemonic::Cmove, Operand::Reg(tmp), Operand::Reg(zero)) );
+    sink.push( X64MCInstr::with1(Mnemonic::Pop, Operand::Reg(x64Reg::Rbx)) );
+
+    x64_lower_bit_result(sink, instr);
 }
 fn x64_lower_move(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
     
     let op1 = instr.operands.get(0).expect("expected a first operand");
     let out = instr.out.expect("expected a output operand");
 
-    let op1 = match op1 {
-        crate::CodeGen::MachineOperand::Imm(i) => Operand::Imm(*i),
-        crate::CodeGen::MachineOperand::Reg(reg) => match reg {
-            crate::CodeGen::Reg::x64(x64) => Operand::Reg(*x64),
-        },
-    };
+    let op1 = x64_lower_op(op1);
     
-    let out = match out {
-        crate::CodeGen::MachineOperand::Imm(i) => Operand::Imm(i),
-        crate::CodeGen::MachineOperand::Reg(reg) => match reg {
-            crate::CodeGen::Reg::x64(x64) => Operand::Reg(x64),
-        },
-    };
+    let out = x64_lower_op(&out);
+
+    if x64_is_xmm(&out) || x64_is_xmm(&op1) {
+        x64_lower_fmove(sink, out, op1, instr.meta);
+        return;
+    }
 
     sink.push( X64MCInstr::with2(Mnemonic::Mov, out, op1).into() );
 }
@@ -76,27 +329,12 @@ fn x64_lower_mul(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
     let op2 = instr.operands.get(1).expect("expected a second operand");
     let out = instr.out.expect("expected a output operand");
 
-    let op1 = match op1 {
-        crate::CodeGen::MachineOperand::Imm(i) => Operand::Imm(*i),
-        crate::CodeGen::MachineOperand::Reg(reg) => match reg {
-            crate::CodeGen::Reg::x64(x64) => Operand::Reg(*x64),
-        },
-    };
+    let op1 = x64_lower_op(op1);
 
     
-    let op2 = match op2 {
-        crate::CodeGen::MachineOperand::Imm(i) => Operand::Imm(*i),
-        crate::CodeGen::MachineOperand::Reg(reg) => match reg {
-            crate::CodeGen::Reg::x64(x64) => Operand::Reg(*x64),
-        },
-    };
+    let op2 = x64_lower_op(op2);
     
-    let out = match out {
-        crate::CodeGen::MachineOperand::Imm(i) => Operand::Imm(i),
-        crate::CodeGen::MachineOperand::Reg(reg) => match reg {
-            crate::CodeGen::Reg::x64(x64) => Operand::Reg(x64),
-        },
-    };
+    let out = x64_lower_op(&out);
 
     let mnemonic = if instr.meta.signed() {
         Mnemonic::Imul
@@ -125,86 +363,99 @@ fn x64_lower_mul(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
     }
 }
 fn x64_lower_zext(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
-    
-    let op1 = instr.operands.get(0).expect("expected a first operand");
-    let op2 = instr.operands.get(0).expect("expected a secound operand");
-    let out = instr.out.expect("expected a output operand");
-
-    let mut movxz = false;
-
-    let op1 = match op1 {
-        crate::CodeGen::MachineOperand::Imm(i) => Operand::Imm(*i),
-        crate::CodeGen::MachineOperand::Reg(reg) => match reg {
-            crate::CodeGen::Reg::x64(x64) => Operand::Reg(*x64),
-        },
-    };
+    let op1 = x64_lower_op(instr.operands.first().expect("expected a first operand"));
+    let out = x64_lower_op(&instr.out.expect("expected a output operand"));
 
-    let op2 = match op2 {
-        crate::CodeGen::MachineOperand::Imm(i) => Operand::Imm(*i),
-        crate::CodeGen::MachineOperand::Reg(reg) => match reg {
-            crate::CodeGen::Reg::x64(x64) => Operand::Reg(*x64),
-        },
-    };
-    
-    let out = match out {
-        crate::CodeGen::MachineOperand::Imm(i) => Operand::Imm(i),
-        crate::CodeGen::MachineOperand::Reg(reg) => match reg {
-            crate::CodeGen::Reg::x64(x64) => Operand::Reg(x64),
-        },
-    };
+    let tmp = x64Reg::Rax.sub_ty(instr.meta);
 
     if let Operand::Reg(op1) = op1 {
-        if let Operand::Reg(op2) = op2 {
-            if (op1.is_gr16() | op1.is_gr8()) && (op2.is_gr32() | op2.is_gr64()) { // movxz allowes a gr8/16 zext into gr32/64
-                movxz = true;
-            }
+        if op1.is_gr32() {
+            // 32bit movs already zero the upper half of the register
+            sink.push(X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(x64Reg::Eax), Operand::Reg(op1)));
+        } else {
+            sink.push(X64MCInstr::with2(Mnemonic::Movzx, Operand::Reg(tmp), Operand::Reg(op1)));
         }
+    } else {
+        sink.push(X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(tmp), op1));
     }
 
-    if movxz {
-        let tmp = Operand::Reg(x64Reg::Rax.sub_ty(instr.meta));
+    sink.push(X64MCInstr::with2(Mnemonic::Mov, out, Operand::Reg(tmp)));
+}
+fn x64_lower_sext(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let op1 = x64_lower_op(instr.operands.first().expect("expected a first operand"));
+    let out = x64_lower_op(&instr.out.expect("expected a output operand"));
 
-        sink.push(X64MCInstr::with2(Mnemonic::Mov, tmp.clone(), op1));
-        sink.push(X64MCInstr::with2(Mnemonic::Movzx, tmp.clone(), op2));
-        sink.push(X64MCInstr::with2(Mnemonic::Mov, out, tmp));
-    } else {
-        let tmp = Operand::Reg(x64Reg::Rax.sub_ty(instr.meta));
+    let tmp = x64Reg::Rax.sub_ty(instr.meta);
 
-        if op1 == out {
-            sink.push(X64MCInstr::with2(Mnemonic::Mov, op1,  op2));
-        } else {
-            sink.push(X64MCInstr::with2(Mnemonic::Mov, tmp.clone(), op1));
-            sink.push(X64MCInstr::with2(Mnemonic::Mov, tmp.clone(), op2));
-            sink.push(X64MCInstr::with2(Mnemonic::Mov, out, tmp));
-        }
+    if let Operand::Reg(op1) = op1 {
+        let mne = if op1.is_gr32() { Mnemonic::Movsxd } else { Mnemonic::Movsx };
+
+        sink.push(X64MCInstr::with2(mne, Operand::Reg(tmp), Operand::Reg(op1)));
+    } else {
+        sink.push(X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(tmp), op1));
     }
 
+    sink.push(X64MCInstr::with2(Mnemonic::Mov, out, Operand::Reg(tmp)));
 }
-fn x64_lower_downcast(_sink: &mut Vec<X64MCInstr>, _instr: &MachineInstr) {
-    todo!()
+fn x64_lower_downcast(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let op1 = x64_lower_op(instr.operands.first().expect("expected a first operand"));
+    let out = x64_lower_op(&instr.out.expect("expected a output operand"));
+
+    let op1 = match op1 {
+        Operand::Reg(reg) => Operand::Reg(reg.sub_ty(instr.meta)),
+        op => op,
+    };
+
+    sink.push(X64MCInstr::with2(Mnemonic::Mov, out.clone(), op1));
+
+    if instr.meta == TypeMetadata::bool {
+        // a bool only keeps the lowest bit
+        sink.push(X64MCInstr::with2(Mnemonic::And, out, Operand::Imm(1)));
+    }
 }
-fn x64_lower_call(conv: CallConv, sink: &mut Vec<X64MCInstr>, _: &MachineInstr, target: &String) {   
+fn x64_lower_call(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, target: &String) {   
     let func = target;
 
-    if conv.reset_eax() {
-        sink.push( X64MCInstr::with2(Mnemonic::Xor, Operand::Reg(x64Reg::Eax), Operand::Reg(x64Reg::Eax)) );
-    }
+    x64_lower_call_setup(sink, instr);
 
     sink.push( X64MCInstr::with1(Mnemonic::Call, Operand::Imm(0)).into() );
     sink.push( X64MCInstr::with1(Mnemonic::Link, Operand::LinkDestination(func.to_string(), -4)).into() );
 }
+fn x64_lower_call_indirect(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let ptr = x64_lower_op(instr.operands.first().expect("expected the stack slot of the pointer"));
+
+    // r11 isn't used for arguments and every variable was saved before the call
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(x64Reg::R11), ptr) );
+
+    x64_lower_call_setup(sink, instr);
+
+    sink.push( X64MCInstr::with1(Mnemonic::Call, Operand::Reg(x64Reg::R11)) );
+}
+fn x64_lower_tail_call(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, target: &String) {
+    x64_lower_call_setup(sink, instr);
+
+    sink.push( X64MCInstr::with1(Mnemonic::Jmp, Operand::Imm(0)) );
+    sink.push( X64MCInstr::with1(Mnemonic::Link, Operand::LinkDestination(target.to_string(), -4)) );
+}
+fn x64_lower_call_setup(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    // al holds the number of used vector registers (for variadic functions)
+    // the count is only given if the calling convention of the callee needs it
+    match instr.operands.last() {
+        Some(MachineOperand::Imm(fp_args @ 1..)) => sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(x64Reg::Eax), Operand::Imm(*fp_args)) ),
+        Some(MachineOperand::Imm(_)) => sink.push( X64MCInstr::with2(Mnemonic::Xor, Operand::Reg(x64Reg::Eax), Operand::Reg(x64Reg::Eax)) ),
+        _ => {},
+    }
+}
 fn x64_lower_return(sink: &mut Vec<X64MCInstr>, _: &MachineInstr) {
     sink.push( X64MCInstr::with0(Mnemonic::Ret).into() )
 }
+fn x64_lower_unreachable(sink: &mut Vec<X64MCInstr>, _: &MachineInstr) {
+    sink.push( X64MCInstr::with0(Mnemonic::Ud2) )
+}
 fn x64_lower_adr_load(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, symbol: &String) {
     let out = instr.out.expect("expected a output operand");
 
-    let out = match out {
-        crate::CodeGen::MachineOperand::Imm(i) => Operand::Imm(i),
-        crate::CodeGen::MachineOperand::Reg(reg) => match reg {
-            crate::CodeGen::Reg::x64(x64) => Operand::Reg(x64),
-        },
-    };
+    let out = x64_lower_op(&out);
 
     sink.push(
         X64MCInstr::with2(Mnemonic::Lea, Operand::Reg(x64Reg::Rax), Operand::Mem(MemOp { base: None, index: None, scale: 1, displ: 1, rip: true })).into()
@@ -216,6 +467,41 @@ fn x64_lower_adr_load(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, symbol:
         X64MCInstr::with2(Mnemonic::Mov, out, Operand::Reg(x64Reg::Rax)).into()
     );
 }
+fn x64_lower_tls_adr_load(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, symbol: &String, model: TlsModel) {
+    let out = instr.out.expect("expected a output operand");
+
+    let out = x64_lower_op(&out);
+
+    // fs:[0] holds the thread pointer
+    let thread_pointer = Operand::Mem(MemOp { base: None, index: None, scale: 1, displ: 0, rip: false });
+
+    // the fs prefix mustn't be separated from its instruction
+    sink.push( X64MCInstr::with0(Mnemonic::EndOptimization) );
+
+    match model {
+        TlsModel::LocalExec => {
+            // mov rax, fs:[0]
+            // lea rax, [rax + symbol@tpoff]
+            sink.push( X64MCInstr::with0(Mnemonic::Fs) );
+            sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(x64Reg::Rax), thread_pointer) );
+            // the displacement is replaced by the linker (it is big enough to be encoded with 32 bits)
+            sink.push( X64MCInstr::with2(Mnemonic::Lea, Operand::Reg(x64Reg::Rax), Operand::Mem(MemOp { base: Some(x64Reg::Rax), index: None, scale: 1, displ: i32::MAX as isize, rip: false })) );
+            sink.push( X64MCInstr::with1(Mnemonic::Link, Operand::LinkDestination(symbol.to_string(), 0)) );
+        },
+        TlsModel::InitialExec => {
+            // mov rax, [rip + symbol@gottpoff]
+            // add rax, fs:[0]
+            sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(x64Reg::Rax), Operand::Mem(MemOp { base: None, index: None, scale: 1, displ: 1, rip: true })) );
+            sink.push( X64MCInstr::with1(Mnemonic::Link, Operand::LinkDestination(symbol.to_string(), -4)) );
+            sink.push( X64MCInstr::with0(Mnemonic::Fs) );
+            sink.push( X64MCInstr::with2(Mnemonic::Add, Operand::Reg(x64Reg::Rax), thread_pointer) );
+        },
+    }
+
+    sink.push( X64MCInstr::with0(Mnemonic::StartOptimization) );
+
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, out, Operand::Reg(x64Reg::Rax)) );
+}
 fn x64_lower_br(sink: &mut Vec<X64MCInstr>, _: &MachineInstr, symbol: &String) {
     let target = Operand::BlockLinkDestination(symbol.to_owned(), -4);
 
@@ -233,18 +519,13 @@ fn x64_lower_cond_br(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, iftrue: &
     let value = instr.operands.get(1).expect("expected valid value to compare at 1. place");
 
     let src = match src {
-        crate::CodeGen::MachineOperand::Imm(_) => unreachable!(),
         crate::CodeGen::MachineOperand::Reg(reg) => match *reg {
             crate::CodeGen::Reg::x64(x64) => Operand::Reg(x64),
         },
+        _ => unreachable!(),
     };
 
-    let value = match value {
-        crate::CodeGen::MachineOperand::Imm(imm) => Operand::Imm(*imm),
-        crate::CodeGen::MachineOperand::Reg(reg) => match *reg {
-            crate::CodeGen::Reg::x64(x64) => Operand::Reg(x64),
-        },
-    };
+    let value = x64_lower_op(value);
 
     sink.push(X64MCInstr::with2(Mnemonic::Cmp, src, value));
     sink.push(X64MCInstr::with1(Mnemonic::Jne, Operand::Imm(0)));
@@ -252,6 +533,171 @@ fn x64_lower_cond_br(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, iftrue: &
     sink.push(X64MCInstr::with1(Mnemonic::Jmp, Operand::Imm(0)));
     sink.push(X64MCInstr::with1(Mnemonic::Link, Operand::BlockLinkDestination(iftrue.to_owned(), -4)));
 }
+fn x64_lower_switch(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, table: &String, default: &String, cases: &Vec<(i64, String)>) {
+    let src = match instr.operands.first().expect("expected valid value to switch on at 1. place") {
+        crate::CodeGen::MachineOperand::Reg(reg) => match *reg {
+            crate::CodeGen::Reg::x64(x64) => x64,
+        },
+        _ => unreachable!(),
+    };
+
+    let signed = instr.meta.signed();
+
+    // the code contains jumps over other instructions, so it mustn't be optimized
+    sink.push(X64MCInstr::with0(Mnemonic::EndOptimization));
+
+    // the value gets extended to 64 bits (like the cases are), so everything is compared as 64 bit values
+    let value = Operand::Reg(x64Reg::Rax);
+
+    if src.is_gr64() {
+        sink.push(X64MCInstr::with2(Mnemonic::Mov, value.clone(), Operand::Reg(src)));
+    } else if src.is_gr32() && signed {
+        sink.push(X64MCInstr::with2(Mnemonic::Movsxd, value.clone(), Operand::Reg(src)));
+    } else if src.is_gr32() {
+        sink.push(X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(x64Reg::Eax), Operand::Reg(src)));
+    } else if signed {
+        sink.push(X64MCInstr::with2(Mnemonic::Movsx, value.clone(), Operand::Reg(src)));
+    } else {
+        sink.push(X64MCInstr::with2(Mnemonic::Movzx, value.clone(), Operand::Reg(src)));
+    }
+
+    let mut cases = cases.to_owned();
+
+    if signed {
+        cases.sort_by_key(|(case, _)| *case);
+    } else {
+        cases.sort_by_key(|(case, _)| *case as u64);
+    }
+
+    // dense cases use a jump table, else a balanced compare tree is used
+    let range = match (cases.first(), cases.last()) {
+        (Some((min, _)), Some((max, _))) => (*max as u64).wrapping_sub(*min as u64).saturating_add(1),
+        _ => 0,
+    };
+
+    if cases.len() >= 4 && range <= 3 * cases.len() as u64 {
+        x64_lower_switch_table(sink, table, default, &cases, range);
+    } else {
+        sink.extend(x64_lower_switch_tree(default, &cases, signed));
+    }
+
+    sink.push(X64MCInstr::with0(Mnemonic::StartOptimization));
+}
+fn x64_lower_select(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let cond = x64_lower_op(instr.operands.first().expect("expected a condition"));
+    let yes = x64_lower_op(instr.operands.get(1).expect("expected a value for a true condition"));
+    let no = x64_lower_op(instr.operands.get(2).expect("expected a value for a false condition"));
+    let out = x64_lower_op(&instr.out.expect("expected a output operand"));
+
+    // cmov doesn't support imms, 8 bit registers or xmm registers, so the values are moved into general purpose registers first
+    sink.push( X64MCInstr::with1(Mnemonic::Push, Operand::Reg(x64Reg::Rbx)) );
+
+    let yes = x64_lower_select_value(sink, x64Reg::Rbx, yes, instr.meta);
+    let no = x64_lower_select_value(sink, x64Reg::Rax, no, instr.meta);
+
+    sink.push( X64MCInstr::with2(Mnemonic::Cmp, cond, Operand::Imm(0)) );
+    sink.push( X64MCInstr::with2(Mnemonic::Cmovne, Operand::Reg(no), Operand::Reg(yes)) );
+
+    sink.push( X64MCInstr::with1(Mnemonic::Pop, Operand::Reg(x64Reg::Rbx)) );
+
+    let result = Operand::Reg(x64Reg::Rax.sub_ty(instr.meta));
+
+    if instr.meta.float() {
+        x64_lower_fmove(sink, out, result, instr.meta);
+    } else {
+        sink.push( X64MCInstr::with2(Mnemonic::Mov, out, result) );
+    }
+}
+/// moves a value of the select into the general purpose register
+fn x64_lower_select_value(sink: &mut Vec<X64MCInstr>, reg: x64Reg, value: Operand, ty: TypeMetadata) -> x64Reg {
+    if x64_is_xmm(&value) {
+        let reg = reg.sub_ty(ty);
+        x64_lower_fmove(sink, Operand::Reg(reg), value, ty);
+        reg
+    } else {
+        x64_lower_extend(sink, reg, value, ty)
+    }
+}
+/// compares rax with the case value
+fn x64_lower_switch_case_cmp(sink: &mut Vec<X64MCInstr>, case: i64) {
+    if case > i32::MAX as i64 || case < i32::MIN as i64 {
+        sink.push(X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(x64Reg::R11), Operand::Imm(case)));
+        sink.push(X64MCInstr::with2(Mnemonic::Cmp, Operand::Reg(x64Reg::Rax), Operand::Reg(x64Reg::R11)));
+    } else {
+        sink.push(X64MCInstr::with2(Mnemonic::Cmp, Operand::Reg(x64Reg::Rax), Operand::Imm(case)));
+    }
+}
+fn x64_lower_switch_tree(default: &String, cases: &[(i64, String)], signed: bool) -> Vec<X64MCInstr> {
+    let mut sink = vec![];
+
+    if cases.len() <= 3 {
+        for (case, block) in cases {
+            x64_lower_switch_case_cmp(&mut sink, *case);
+            sink.push(X64MCInstr::with1(Mnemonic::Je, Operand::Imm(0)));
+            sink.push(X64MCInstr::with1(Mnemonic::Link, Operand::BlockLinkDestination(block.to_owned(), -4)));
+        }
+
+        sink.push(X64MCInstr::with1(Mnemonic::Jmp, Operand::Imm(0)));
+        sink.push(X64MCInstr::with1(Mnemonic::Link, Operand::BlockLinkDestination(default.to_owned(), -4)));
+
+        return sink;
+    }
+
+    let mid = cases.len() / 2;
+    let (case, block) = &cases[mid];
+
+    let smaller = x64_lower_switch_tree(default, &cases[..mid], signed);
+    let bigger = x64_lower_switch_tree(default, &cases[mid + 1..], signed);
+
+    let smaller_size = smaller.iter().map(|instr| {
+        instr.encode().expect("switch instructions should be encodable").0.len()
+    }).sum::<usize>();
+
+    x64_lower_switch_case_cmp(&mut sink, *case);
+    sink.push(X64MCInstr::with1(Mnemonic::Je, Operand::Imm(0)));
+    sink.push(X64MCInstr::with1(Mnemonic::Link, Operand::BlockLinkDestination(block.to_owned(), -4)));
+
+    // bigger values jump over the code which handles the smaller cases
+    sink.push(X64MCInstr::with1(if signed { Mnemonic::Jg } else { Mnemonic::Ja }, Operand::Imm(smaller_size as i64)));
+
+    sink.extend(smaller);
+    sink.extend(bigger);
+
+    sink
+}
+fn x64_lower_switch_table(sink: &mut Vec<X64MCInstr>, table: &String, default: &String, cases: &[(i64, String)], range: u64) {
+    let min = cases.first().expect("jump tables need cases").0;
+
+    // rax = value - min (values under min wrap around, so they are also above the range)
+    if min > i32::MAX as i64 || min < i32::MIN as i64 {
+        sink.push(X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(x64Reg::R11), Operand::Imm(min)));
+        sink.push(X64MCInstr::with2(Mnemonic::Sub, Operand::Reg(x64Reg::Rax), Operand::Reg(x64Reg::R11)));
+    } else if min != 0 {
+        sink.push(X64MCInstr::with2(Mnemonic::Sub, Operand::Reg(x64Reg::Rax), Operand::Imm(min)));
+    }
+
+    sink.push(X64MCInstr::with2(Mnemonic::Cmp, Operand::Reg(x64Reg::Rax), Operand::Imm(range as i64 - 1)));
+    sink.push(X64MCInstr::with1(Mnemonic::Ja, Operand::Imm(0)));
+    sink.push(X64MCInstr::with1(Mnemonic::Link, Operand::BlockLinkDestination(default.to_owned(), -4)));
+
+    // the entries of the table are the offsets of the blocks relative to the table
+    sink.push(X64MCInstr::with2(Mnemonic::Lea, Operand::Reg(x64Reg::R11), Operand::Mem(MemOp { base: None, index: None, scale: 1, displ: 1, rip: true })));
+    sink.push(X64MCInstr::with1(Mnemonic::Link, Operand::LinkDestination(table.to_owned(), -4)));
+
+    sink.push(X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(x64Reg::Eax), Operand::Mem(MemOp { base: Some(x64Reg::R11), index: Some(x64Reg::Rax), scale: 4, displ: 0, rip: false })));
+    sink.push(X64MCInstr::with2(Mnemonic::Movsxd, Operand::Reg(x64Reg::Rax), Operand::Reg(x64Reg::Eax)));
+    sink.push(X64MCInstr::with2(Mnemonic::Add, Operand::Reg(x64Reg::Rax), Operand::Reg(x64Reg::R11)));
+    sink.push(X64MCInstr::with1(Mnemonic::Jmp, Operand::Reg(x64Reg::Rax)));
+
+    for index in 0..range as i64 {
+        let block = cases.iter()
+            .find(|(case, _)| case.wrapping_sub(min) == index)
+            .map(|(_, block)| block)
+            .unwrap_or(default);
+
+        sink.push(X64MCInstr::with1(Mnemonic::Link, Operand::JumpTableEntry(table.to_owned(), block.to_owned(), index * 4)));
+    }
+}
 fn x64_lower_cmp(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, mode: &CmpMode) {
     let ls = instr.operands.get(0).expect("expected valid src operand at 1. place");
     let rs = instr.operands.get(1).expect("expected valid value to compare at 2. place");
@@ -266,18 +712,13 @@ fn x64_lower_cmp(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, mode: &CmpMod
     };
 
     let mut ls = match ls {
-        crate::CodeGen::MachineOperand::Imm(_) => unreachable!(),
         crate::CodeGen::MachineOperand::Reg(reg) => match *reg {
             crate::CodeGen::Reg::x64(x64) => Operand::Reg(x64),
         },
+        _ => unreachable!(),
     };
 
-    let mut rs = match rs {
-        crate::CodeGen::MachineOperand::Imm(imm) => Operand::Imm(*imm),
-        crate::CodeGen::MachineOperand::Reg(reg) => match *reg {
-            crate::CodeGen::Reg::x64(x64) => Operand::Reg(x64),
-        },
-    };
+    let mut rs = x64_lower_op(rs);
 
     if let Operand::Imm(_) = ls {
         let tmp = ls;
@@ -306,27 +747,12 @@ macro_rules! LowerSimpleMath {
             let op2 = instr.operands.get(1).expect("expected a second operand");
             let out = instr.out.expect("expected a output operand");
 
-            let op1 = match op1 {
-                crate::CodeGen::MachineOperand::Imm(i) => Operand::Imm(*i),
-                crate::CodeGen::MachineOperand::Reg(reg) => match reg {
-                    crate::CodeGen::Reg::x64(x64) => Operand::Reg(*x64),
-                },
-            };
+            let op1 = x64_lower_op(op1);
 
             
-            let op2 = match op2 {
-                crate::CodeGen::MachineOperand::Imm(i) => Operand::Imm(*i),
-                crate::CodeGen::MachineOperand::Reg(reg) => match reg {
-                    crate::CodeGen::Reg::x64(x64) => Operand::Reg(*x64),
-                },
-            };
+            let op2 = x64_lower_op(op2);
             
-            let out = match out {
-                crate::CodeGen::MachineOperand::Imm(i) => Operand::Imm(i),
-                crate::CodeGen::MachineOperand::Reg(reg) => match reg {
-                    crate::CodeGen::Reg::x64(x64) => Operand::Reg(x64),
-                },
-            };
+            let out = x64_lower_op(&out);
 
             let tmp = || Operand::Reg(x64Reg::Rax.sub_ty(instr.meta));
 
@@ -341,4 +767,617 @@ LowerSimpleMath!(x64_lower_add, Mnemonic::Add);
 LowerSimpleMath!(x64_lower_and, Mnemonic::And);
 LowerSimpleMath!(x64_lower_or, Mnemonic::Or);
 LowerSimpleMath!(x64_lower_sub, Mnemonic::Sub);
-LowerSimpleMath!(x64_lower_xor, Mnemonic::Xor);
\ No newline at end of file
+LowerSimpleMath!(x64_lower_xor, Mnemonic::Xor);
+
+fn x64_lower_op(op: &MachineOperand) -> Operand {
+    match op {
+        MachineOperand::Imm(i) => Operand::Imm(*i),
+        MachineOperand::Reg(reg) => match reg {
+            crate::CodeGen::Reg::x64(x64) => Operand::Reg(*x64),
+        },
+        MachineOperand::Stack(off) => Operand::Mem(MemOp { base: Some(x64Reg::Rbp), index: None, scale: 1, displ: -*off as isize, rip: false }),
+    }
+}
+
+fn x64_is_xmm(op: &Operand) -> bool {
+    matches!(op, Operand::Reg(reg) if reg.is_xmm())
+}
+
+/// moves between xmm registers, general purpose registers and immediates
+fn x64_lower_fmove(sink: &mut Vec<X64MCInstr>, out: Operand, op: Operand, ty: TypeMetadata) {
+    let movd = |wide: bool| if wide { Mnemonic::Movq } else { Mnemonic::Movd };
+
+    match (&out, &op) {
+        (Operand::Reg(out_reg), Operand::Reg(op_reg)) if out_reg.is_xmm() && op_reg.is_xmm() => {
+            if out_reg != op_reg {
+                sink.push( X64MCInstr::with2(Mnemonic::Movaps, out, op) );
+            }
+        },
+        (Operand::Reg(out_reg), Operand::Imm(_)) if out_reg.is_xmm() => {
+            let tmp = x64Reg::Rax.sub_ty(ty);
+
+            sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(tmp), op) );
+            sink.push( X64MCInstr::with2(movd(tmp.is_gr64()), out, Operand::Reg(tmp)) );
+        },
+        (Operand::Reg(out_reg), Operand::Reg(op_reg)) if out_reg.is_xmm() => {
+            sink.push( X64MCInstr::with2(movd(op_reg.is_gr64()), out, op) );
+        },
+        (Operand::Reg(out_reg), Operand::Reg(_)) => {
+            sink.push( X64MCInstr::with2(movd(out_reg.is_gr64()), out, op) );
+        },
+        // variables are held in registers and immediates are only moved into xmm registers
+        _ => unreachable!("floating point move between {} <- {}", out, op),
+    }
+}
+
+fn x64_lower_fmath(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, single: Mnemonic, double: Mnemonic) {
+    let op1 = x64_lower_op(instr.operands.first().expect("expected a first operand"));
+    let op2 = x64_lower_op(instr.operands.get(1).expect("expected a second operand"));
+    let out = x64_lower_op(&instr.out.expect("expected a output operand"));
+
+    let mnemonic = if instr.meta == TypeMetadata::f32 { single } else { double };
+
+    let tmp = Operand::Reg(x64Reg::Xmm15);
+
+    x64_lower_fmove(sink, tmp.clone(), op1, instr.meta);
+
+    let op2 = if let Operand::Imm(_) = op2 {
+        x64_lower_fmove(sink, Operand::Reg(x64Reg::Xmm14), op2, instr.meta);
+        Operand::Reg(x64Reg::Xmm14)
+    } else { op2 };
+
+    sink.push( X64MCInstr::with2(mnemonic, tmp.clone(), op2) );
+    x64_lower_fmove(sink, out, tmp, instr.meta);
+}
+
+fn x64_lower_fcmp(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, mode: &CmpMode) {
+    let ls = x64_lower_op(instr.operands.first().expect("expected valid src operand at 1. place"));
+    let rs = x64_lower_op(instr.operands.get(1).expect("expected valid value to compare at 2. place"));
+    let out = x64_lower_op(&instr.out.expect("expected output"));
+
+    let rs = if let Operand::Imm(_) = rs {
+        x64_lower_fmove(sink, Operand::Reg(x64Reg::Xmm14), rs, instr.meta);
+        Operand::Reg(x64Reg::Xmm14)
+    } else { rs };
+
+    let cmp = if instr.meta == TypeMetadata::f32 { Mnemonic::Ucomiss } else { Mnemonic::Ucomisd };
+
+    // ucomiss/ucomisd set the flags like an unsigned compare, but an unordered
+    // result (a NaN operand) sets zf, pf and cf. seta/setae are false for unordered results,
+    // so less than (or equal) is lowered as greater than (or equal) with swapped operands
+    match mode {
+        CmpMode::LessThan | CmpMode::LessThanOrEqual => {
+            let rs = if let Operand::Reg(_) = rs { rs } else {
+                x64_lower_fmove(sink, Operand::Reg(x64Reg::Xmm14), rs, instr.meta);
+                Operand::Reg(x64Reg::Xmm14)
+            };
+
+            sink.push( X64MCInstr::with2(cmp, rs, ls) );
+        },
+        _ => sink.push( X64MCInstr::with2(cmp, ls, rs) ),
+    }
+
+    let (mne, parity) = match mode {
+        CmpMode::Eqal => (Mnemonic::Sete, Some((Mnemonic::Setnp, Mnemonic::And))),
+        CmpMode::NotEqal => (Mnemonic::Setne, Some((Mnemonic::Setp, Mnemonic::Or))),
+        CmpMode::GreaterThan | CmpMode::LessThan => (Mnemonic::Seta, None),
+        CmpMode::GreaterThanOrEqual | CmpMode::LessThanOrEqual => (Mnemonic::Setae, None),
+    };
+
+    sink.push( X64MCInstr::with1(mne, out.to_owned()) );
+
+    // eq needs to be ordered, ne is true for unordered results
+    if let Some((set, combine)) = parity {
+        sink.push( X64MCInstr::with1(set, Operand::Reg(x64Reg::Al)) );
+        sink.push( X64MCInstr::with2(combine, out, Operand::Reg(x64Reg::Al)) );
+    }
+}
+
+fn x64_lower_fcast(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, from: TypeMetadata) {
+    let op1 = x64_lower_op(instr.operands.first().expect("expected a first operand"));
+    let out = x64_lower_op(&instr.out.expect("expected a output operand"));
+
+    let to = instr.meta;
+
+    if from.float() && to.float() {
+        let mne = match (from, to) {
+            (TypeMetadata::f32, TypeMetadata::f64) => Mnemonic::Cvtss2sd,
+            (TypeMetadata::f64, TypeMetadata::f32) => Mnemonic::Cvtsd2ss,
+            _ => {
+                x64_lower_fmove(sink, out, op1, to);
+                return;
+            }
+        };
+
+        sink.push( X64MCInstr::with2(mne, out, op1) );
+    } else if to.float() {
+        let tmp = Operand::Reg(x64Reg::Rax);
+
+        // the source gets extended into rax, so it can be converted as a 64bit intenger
+        match from {
+            TypeMetadata::i32 => sink.push( X64MCInstr::with2(Mnemonic::Movsxd, tmp.clone(), op1) ),
+            TypeMetadata::i16 | TypeMetadata::i8 => sink.push( X64MCInstr::with2(Mnemonic::Movsx, tmp.clone(), op1) ),
+            TypeMetadata::u16 | TypeMetadata::u8 | TypeMetadata::bool => sink.push( X64MCInstr::with2(Mnemonic::Movzx, Operand::Reg(x64Reg::Eax), op1) ),
+            TypeMetadata::u32 => sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(x64Reg::Eax), op1) ),
+            _ => sink.push( X64MCInstr::with2(Mnemonic::Mov, tmp.clone(), op1) ),
+        }
+
+        let mne = if to == TypeMetadata::f32 { Mnemonic::Cvtsi2ss } else { Mnemonic::Cvtsi2sd };
+
+        if from != TypeMetadata::u64 {
+            sink.push( X64MCInstr::with2(mne, out, tmp) );
+            return;
+        }
+
+        // cvtsi2ss/cvtsi2sd only convert signed intengers, so u64 values with the sign bit set
+        // are halved (keeping the lowest bit for the rounding), converted and then doubled
+        let result = Operand::Reg(x64Reg::Xmm15);
+        let add = if to == TypeMetadata::f32 { Mnemonic::Addss } else { Mnemonic::Addsd };
+
+        let signed = vec![X64MCInstr::with2(mne, result.clone(), tmp.clone())];
+
+        let mut unsigned = vec![
+            X64MCInstr::with1(Mnemonic::Push, Operand::Reg(x64Reg::Rbx)),
+            X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(x64Reg::Rbx), tmp.clone()),
+            X64MCInstr::with2(Mnemonic::And, Operand::Reg(x64Reg::Rbx), Operand::Imm(1)),
+            X64MCInstr::with2(Mnemonic::Shr, tmp.clone(), Operand::Imm(1)),
+            X64MCInstr::with2(Mnemonic::Or, tmp.clone(), Operand::Reg(x64Reg::Rbx)),
+            X64MCInstr::with1(Mnemonic::Pop, Operand::Reg(x64Reg::Rbx)),
+            X64MCInstr::with2(mne, result.clone(), tmp.clone()),
+            X64MCInstr::with2(add, result.clone(), result.clone()),
+        ];
+
+        let size = |instrs: &[X64MCInstr]| instrs.iter().map(|instr| {
+            instr.encode().expect("the conversion instructions should be encodable").0.len()
+        }).sum::<usize>() as i64;
+
+        unsigned.push( X64MCInstr::with1(Mnemonic::Jmp, Operand::Imm(size(&signed))) );
+
+        // values which are bigger than -1 don't have the sign bit set
+        sink.push( X64MCInstr::with2(Mnemonic::Cmp, tmp, Operand::Imm(-1)) );
+        sink.push( X64MCInstr::with1(Mnemonic::Jg, Operand::Imm(size(&unsigned))) );
+        sink.extend(unsigned);
+        sink.extend(signed);
+
+        x64_lower_fmove(sink, out, result, to);
+    } else {
+        let mne = if from == TypeMetadata::f32 { Mnemonic::Cvttss2si } else { Mnemonic::Cvttsd2si };
+
+        sink.push( X64MCInstr::with2(mne, Operand::Reg(x64Reg::Rax), op1) );
+        sink.push( X64MCInstr::with2(Mnemonic::Mov, out, Operand::Reg(x64Reg::Rax.sub_ty(to))) );
+    }
+}
+
+fn x64_lower_prolog(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let size = if let Some(MachineOperand::Imm(size)) = instr.operands.first() { *size } else { 0 };
+
+    sink.push( X64MCInstr::with1(Mnemonic::Push, Operand::Reg(x64Reg::Rbp)) );
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(x64Reg::Rbp), Operand::Reg(x64Reg::Rsp)) );
+
+    if size > 0 {
+        sink.push( X64MCInstr::with2(Mnemonic::Sub, Operand::Reg(x64Reg::Rsp), Operand::Imm(size)) );
+    }
+}
+
+fn x64_lower_epilog(sink: &mut Vec<X64MCInstr>, _: &MachineInstr) {
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(x64Reg::Rsp), Operand::Reg(x64Reg::Rbp)) );
+    sink.push( X64MCInstr::with1(Mnemonic::Pop, Operand::Reg(x64Reg::Rbp)) );
+}
+
+/// returns the memory operand which the pointer operand points to
+fn x64_lower_ptr(op: &MachineOperand) -> Operand {
+    match x64_lower_op(op) {
+        Operand::Reg(ptr) => Operand::Mem(MemOp { base: Some(ptr), index: None, scale: 1, displ: 0, rip: false }),
+        Operand::Mem(mem) => Operand::Mem(mem),
+        op => unreachable!("pointers are held in registers or stack slots, but got {}", op),
+    }
+}
+
+fn x64_lower_alloca(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let slot = x64_lower_op(instr.operands.first().expect("expected a stack slot"));
+    let out = x64_lower_op(&instr.out.expect("expected a output operand"));
+
+    sink.push( X64MCInstr::with2(Mnemonic::Lea, out, slot) );
+}
+
+fn x64_lower_load(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let ptr = x64_lower_ptr(instr.operands.first().expect("expected a pointer operand"));
+    let out = x64_lower_op(&instr.out.expect("expected a output operand"));
+
+    let mne = match instr.meta {
+        TypeMetadata::f32 => Mnemonic::Movss,
+        TypeMetadata::f64 => Mnemonic::Movsd,
+        _ => Mnemonic::Mov,
+    };
+
+    sink.push( X64MCInstr::with2(mne, out, ptr) );
+}
+
+fn x64_lower_gep(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let ptr = x64_lower_op(instr.operands.first().expect("expected a pointer operand"));
+    let index = x64_lower_op(instr.operands.get(1).expect("expected an index operand"));
+    let out = x64_lower_op(&instr.out.expect("expected a output operand"));
+
+    let scale = if let Some(MachineOperand::Imm(scale)) = instr.operands.get(2) { *scale } else { 1 };
+
+    let base = if let Operand::Reg(base) = ptr { base.sub64() } else {
+        unreachable!("pointers are only held in registers, but the gep got {}", ptr)
+    };
+
+    let (index, scale, displ) = match index {
+        Operand::Imm(index) => (None, 1, (index * scale) as isize),
+        Operand::Reg(reg) => {
+            // the index needs to be extended to 64 bits
+            let ty = instr.meta;
+
+            if ty.signed() && ty.bitSize() == 32 {
+                sink.push( X64MCInstr::with2(Mnemonic::Movsxd, Operand::Reg(x64Reg::Rax), Operand::Reg(reg)) );
+            } else if ty.signed() && ty.bitSize() < 32 {
+                sink.push( X64MCInstr::with2(Mnemonic::Movsx, Operand::Reg(x64Reg::Rax), Operand::Reg(reg)) );
+            } else {
+                x64_lower_extend(sink, x64Reg::Rax, Operand::Reg(reg), ty);
+            }
+
+            if matches!(scale, 1 | 2 | 4 | 8) {
+                (Some(x64Reg::Rax), scale as isize, 0)
+            } else {
+                // the scale can't be encoded in the memory operand
+                sink.push( X64MCInstr::with1(Mnemonic::Push, Operand::Reg(x64Reg::Rdx)) );
+                sink.push( X64MCInstr::with1(Mnemonic::Push, Operand::Reg(x64Reg::Rbx)) );
+                sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(x64Reg::Rbx), Operand::Imm(scale)) );
+                sink.push( X64MCInstr::with1(Mnemonic::Mul, Operand::Reg(x64Reg::Rbx)) );
+                sink.push( X64MCInstr::with1(Mnemonic::Pop, Operand::Reg(x64Reg::Rbx)) );
+                sink.push( X64MCInstr::with1(Mnemonic::Pop, Operand::Reg(x64Reg::Rdx)) );
+
+                (Some(x64Reg::Rax), 1, 0)
+            }
+        },
+        op => unreachable!("the index of a gep is either a constant or a variable in a register, but got {}", op),
+    };
+
+    sink.push( X64MCInstr::with2(Mnemonic::Lea, out, Operand::Mem(MemOp { base: Some(base), index: index, scale: scale, displ: displ, rip: false })) );
+}
+
+fn x64_lower_store(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let ptr = x64_lower_ptr(instr.operands.first().expect("expected a pointer operand"));
+    let value = x64_lower_op(instr.operands.get(1).expect("expected a value to store"));
+
+    match value {
+        Operand::Reg(reg) if reg.is_xmm() => {
+            let mne = if instr.meta == TypeMetadata::f32 { Mnemonic::Movss } else { Mnemonic::Movsd };
+            sink.push( X64MCInstr::with2(mne, ptr, value) );
+        },
+        Operand::Reg(_) => sink.push( X64MCInstr::with2(Mnemonic::Mov, ptr, value) ),
+        _ => {
+            // the memory operand doesn't carry a size, so the immediate goes through rax
+            let tmp = Operand::Reg(x64Reg::Rax.sub_ty(instr.meta));
+
+            sink.push( X64MCInstr::with2(Mnemonic::Mov, tmp.clone(), value) );
+            sink.push( X64MCInstr::with2(Mnemonic::Mov, ptr, tmp) );
+        },
+    }
+}
+
+/// returns the memory operand `[base + displ]`
+fn x64_lower_mem_at(base: x64Reg, displ: isize) -> Operand {
+    Operand::Mem(MemOp { base: Some(base.sub64()), index: None, scale: 1, displ: displ, rip: false })
+}
+
+/// returns the chunks (offset and type) in which `size` bytes are moved
+fn x64_lower_mem_chunks(size: i64) -> Vec<(isize, TypeMetadata)> {
+    let mut chunks = vec![];
+    let mut offset = 0;
+
+    while offset < size {
+        let ty = match size - offset {
+            8.. => TypeMetadata::u64,
+            4..=7 => TypeMetadata::u32,
+            2..=3 => TypeMetadata::u16,
+            _ => TypeMetadata::u8,
+        };
+
+        chunks.push((offset as isize, ty));
+        offset += ty.byteSize() as i64;
+    }
+
+    chunks
+}
+
+fn x64_lower_memcpy(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let Operand::Reg(dst) = x64_lower_op(instr.operands.first().expect("expected a destination operand")) else {
+        unreachable!("pointers are only held in registers")
+    };
+    let Operand::Reg(src) = x64_lower_op(instr.operands.get(1).expect("expected a source operand")) else {
+        unreachable!("pointers are only held in registers")
+    };
+    let Some(MachineOperand::Imm(size)) = instr.operands.get(2) else {
+        unreachable!("only constant sizes are inlined (the other ones call the runtime)")
+    };
+
+    for (offset, ty) in x64_lower_mem_chunks(*size) {
+        let tmp = Operand::Reg(x64Reg::Rax.sub_ty(ty));
+
+        sink.push( X64MCInstr::with2(Mnemonic::Mov, tmp.clone(), x64_lower_mem_at(src, offset)) );
+        sink.push( X64MCInstr::with2(Mnemonic::Mov, x64_lower_mem_at(dst, offset), tmp) );
+    }
+}
+
+fn x64_lower_memset(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let Operand::Reg(dst) = x64_lower_op(instr.operands.first().expect("expected a destination operand")) else {
+        unreachable!("pointers are only held in registers")
+    };
+    let val = x64_lower_op(instr.operands.get(1).expect("expected a value operand"));
+    let Some(MachineOperand::Imm(size)) = instr.operands.get(2) else {
+        unreachable!("only constant sizes are inlined (the other ones call the runtime)")
+    };
+
+    sink.push( X64MCInstr::with2(Mnemonic::Movzx, Operand::Reg(x64Reg::Eax), val) );
+
+    if *size > 1 {
+        // the byte gets copied into every byte of rax
+        sink.push( X64MCInstr::with1(Mnemonic::Push, Operand::Reg(x64Reg::Rbx)) );
+
+        for shift in [8, 16, 32] {
+            sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(x64Reg::Rbx), Operand::Reg(x64Reg::Rax)) );
+            sink.push( X64MCInstr::with2(Mnemonic::Shl, Operand::Reg(x64Reg::Rbx), Operand::Imm(shift)) );
+            sink.push( X64MCInstr::with2(Mnemonic::Or, Operand::Reg(x64Reg::Rax), Operand::Reg(x64Reg::Rbx)) );
+        }
+
+        sink.push( X64MCInstr::with1(Mnemonic::Pop, Operand::Reg(x64Reg::Rbx)) );
+    }
+
+    for (offset, ty) in x64_lower_mem_chunks(*size) {
+        sink.push( X64MCInstr::with2(Mnemonic::Mov, x64_lower_mem_at(dst, offset), Operand::Reg(x64Reg::Rax.sub_ty(ty))) );
+    }
+}
+
+// x64 doesn't reorder loads with other loads and stores with other stores,
+// so only seq_cst stores and fences need extra instructions (locked instructions are full barriers)
+
+fn x64_lower_atomic_store(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, order: AtomicOrdering) {
+    if order != AtomicOrdering::SeqCst {
+        return x64_lower_store(sink, instr);
+    }
+
+    let ptr = x64_lower_ptr(instr.operands.first().expect("expected a pointer operand"));
+    let value = x64_lower_op(instr.operands.get(1).expect("expected a value to store"));
+
+    let tmp = Operand::Reg(x64Reg::Rax.sub_ty(instr.meta));
+
+    // xchg with memory is implicitly locked
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, tmp.clone(), value) );
+    sink.push( X64MCInstr::with2(Mnemonic::Xchg, ptr, tmp) );
+}
+
+fn x64_lower_atomic_rmw(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, op: AtomicRmwOp) {
+    let ptr = x64_lower_ptr(instr.operands.first().expect("expected a pointer operand"));
+    let value = x64_lower_op(instr.operands.get(1).expect("expected a value operand"));
+
+    let tmp = Operand::Reg(x64Reg::Rax.sub_ty(instr.meta));
+
+    // the code contains a loop, so it mustn't be optimized
+    sink.push( X64MCInstr::with0(Mnemonic::EndOptimization) );
+
+    match op {
+        AtomicRmwOp::Add => {
+            sink.push( X64MCInstr::with2(Mnemonic::Mov, tmp.clone(), value) );
+            sink.push( X64MCInstr::with0(Mnemonic::Lock) );
+            sink.push( X64MCInstr::with2(Mnemonic::Xadd, ptr, tmp.clone()) );
+        },
+        AtomicRmwOp::Xchg => {
+            sink.push( X64MCInstr::with2(Mnemonic::Mov, tmp.clone(), value) );
+            sink.push( X64MCInstr::with2(Mnemonic::Xchg, ptr, tmp.clone()) );
+        },
+        AtomicRmwOp::And | AtomicRmwOp::Or => {
+            // there is no instruction which returns the old value, so a compare exchange loop is used
+            let mne = if op == AtomicRmwOp::And { Mnemonic::And } else { Mnemonic::Or };
+            let new = Operand::Reg(x64Reg::Rbx.sub_ty(instr.meta));
+
+            sink.push( X64MCInstr::with1(Mnemonic::Push, Operand::Reg(x64Reg::Rbx)) );
+            sink.push( X64MCInstr::with2(Mnemonic::Mov, tmp.clone(), ptr.clone()) );
+
+            // cmpxchg loads the current value into rax if it fails
+            let body = vec![
+                X64MCInstr::with2(Mnemonic::Mov, new.clone(), tmp.clone()),
+                X64MCInstr::with2(mne, new.clone(), value),
+                X64MCInstr::with0(Mnemonic::Lock),
+                X64MCInstr::with2(Mnemonic::Cmpxchg, ptr, new),
+            ];
+
+            let size = body.iter().map(|instr| {
+                instr.encode().expect("atomic instructions should be encodable").0.len()
+            }).sum::<usize>();
+
+            sink.extend(body);
+
+            // the jump is relative to its end (jne rel32 is 6 bytes long)
+            sink.push( X64MCInstr::with1(Mnemonic::Jne, Operand::Imm(-(size as i64 + 6))) );
+            sink.push( X64MCInstr::with1(Mnemonic::Pop, Operand::Reg(x64Reg::Rbx)) );
+        },
+    }
+
+    sink.push( X64MCInstr::with0(Mnemonic::StartOptimization) );
+
+    if let Some(out) = &instr.out {
+        sink.push( X64MCInstr::with2(Mnemonic::Mov, x64_lower_op(out), tmp) );
+    }
+}
+
+fn x64_lower_atomic_cmpxchg(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    let ptr = x64_lower_ptr(instr.operands.first().expect("expected a pointer operand"));
+    let expected = x64_lower_op(instr.operands.get(1).expect("expected an expected value"));
+    let new = x64_lower_op(instr.operands.get(2).expect("expected a new value"));
+
+    let tmp = Operand::Reg(x64Reg::Rax.sub_ty(instr.meta));
+
+    // cmpxchg compares with rax and leaves the old value in it
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, tmp.clone(), expected) );
+    sink.push( X64MCInstr::with0(Mnemonic::Lock) );
+    sink.push( X64MCInstr::with2(Mnemonic::Cmpxchg, ptr, new) );
+
+    if let Some(out) = &instr.out {
+        sink.push( X64MCInstr::with2(Mnemonic::Mov, x64_lower_op(out), tmp) );
+    }
+}
+
+fn x64_lower_fence(sink: &mut Vec<X64MCInstr>, _: &MachineInstr, order: AtomicOrdering) {
+    // only stores followed by loads can be reordered
+    if order == AtomicOrdering::SeqCst {
+        sink.push( X64MCInstr::with0(Mnemonic::Mfence) );
+    }
+}
+
+fn x64_lower_overflow(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, op: OverflowOp) {
+    let ls = x64_lower_op(instr.operands.first().expect("expected a first operand"));
+    let rs = x64_lower_op(instr.operands.get(1).expect("expected a second operand"));
+
+    let tmp = Operand::Reg(x64Reg::Rax.sub_ty(instr.meta));
+    let rdx = Operand::Reg(x64Reg::Rdx.sub_ty(instr.meta));
+
+    // the optimizer could replace the arithmetic with an lea (which doesn't set the flags),
+    // so it's stopped until the flags are read by the next instruction
+    sink.push( X64MCInstr::with0(Mnemonic::EndOptimization) );
+
+    let out = instr.out.as_ref().map(x64_lower_op);
+
+    // the one operand mul writes the upper half into rdx
+    let save_rdx = op == OverflowOp::Mul && out.as_ref() != Some(&rdx);
+
+    if save_rdx {
+        sink.push( X64MCInstr::with1(Mnemonic::Push, Operand::Reg(x64Reg::Rdx)) );
+    }
+
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, tmp.clone(), ls) );
+
+    match op {
+        OverflowOp::Add => sink.push( X64MCInstr::with2(Mnemonic::Add, tmp.clone(), rs) ),
+        OverflowOp::Sub => sink.push( X64MCInstr::with2(Mnemonic::Sub, tmp.clone(), rs) ),
+        // both set the carry and the overflow flag if the upper half isn't just the extension of the lower half
+        OverflowOp::Mul => sink.push( X64MCInstr::with1(if instr.meta.signed() { Mnemonic::Imul } else { Mnemonic::Mul }, rs) ),
+    }
+
+    // mov and pop don't change the flags
+    if let Some(out) = out {
+        sink.push( X64MCInstr::with2(Mnemonic::Mov, out, tmp) );
+    }
+
+    if save_rdx {
+        sink.push( X64MCInstr::with1(Mnemonic::Pop, Operand::Reg(x64Reg::Rdx)) );
+    }
+}
+
+fn x64_lower_set_overflow(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr) {
+    if let Some(out) = &instr.out {
+        let mne = if instr.meta.signed() { Mnemonic::Seto } else { Mnemonic::Setb };
+
+        sink.push( X64MCInstr::with1(mne, x64_lower_op(out)) );
+    }
+
+    sink.push( X64MCInstr::with0(Mnemonic::StartOptimization) );
+}
+
+fn x64_lower_overflow_br(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, iftrue: &String, iffalse: &String) {
+    let mne = if instr.meta.signed() { Mnemonic::Jo } else { Mnemonic::Jb };
+
+    sink.push(X64MCInstr::with1(mne, Operand::Imm(0)));
+    sink.push(X64MCInstr::with1(Mnemonic::Link, Operand::BlockLinkDestination(iftrue.to_owned(), -4)));
+    sink.push(X64MCInstr::with1(Mnemonic::Jmp, Operand::Imm(0)));
+    sink.push(X64MCInstr::with1(Mnemonic::Link, Operand::BlockLinkDestination(iffalse.to_owned(), -4)));
+
+    sink.push( X64MCInstr::with0(Mnemonic::StartOptimization) );
+}
+
+/// returns the memory operand which lies `offset` bytes behind the memory the pointer operand points to
+fn x64_lower_ptr_offset(op: &MachineOperand, offset: isize) -> Operand {
+    match x64_lower_ptr(op) {
+        Operand::Mem(mut mem) => {
+            mem.displ += offset;
+            Operand::Mem(mem)
+        },
+        op => unreachable!("x64_lower_ptr always returns a memory operand, but got {}", op),
+    }
+}
+
+fn x64_lower_va_start(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, conv: CallConv) {
+    let list = instr.operands.first().expect("expected a va_list operand");
+    let stack_args = x64_lower_op(instr.operands.get(1).expect("expected the area of the stack arguments"));
+
+    sink.push( X64MCInstr::with2(Mnemonic::Lea, Operand::Reg(x64Reg::Rax), stack_args) );
+
+    if conv == CallConv::WindowsFastCall {
+        // the va_list is only a pointer to the next argument
+        sink.push( X64MCInstr::with2(Mnemonic::Mov, x64_lower_ptr_offset(list, 0), Operand::Reg(x64Reg::Rax)) );
+        return;
+    }
+
+    let gp_offset = x64_lower_op(instr.operands.get(2).expect("expected the general purpose offset"));
+    let fp_offset = x64_lower_op(instr.operands.get(3).expect("expected the floating point offset"));
+    let save_area = x64_lower_op(instr.operands.get(4).expect("expected the register save area"));
+
+    // struct { u32 gp_offset, u32 fp_offset, ptr overflow_arg_area, ptr reg_save_area }
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, x64_lower_ptr_offset(list, 8), Operand::Reg(x64Reg::Rax)) );
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(x64Reg::Eax), gp_offset) );
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, x64_lower_ptr_offset(list, 0), Operand::Reg(x64Reg::Eax)) );
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(x64Reg::Eax), fp_offset) );
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, x64_lower_ptr_offset(list, 4), Operand::Reg(x64Reg::Eax)) );
+    sink.push( X64MCInstr::with2(Mnemonic::Lea, Operand::Reg(x64Reg::Rax), save_area) );
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, x64_lower_ptr_offset(list, 16), Operand::Reg(x64Reg::Rax)) );
+}
+
+fn x64_lower_va_arg(sink: &mut Vec<X64MCInstr>, instr: &MachineInstr, conv: CallConv) {
+    let list = instr.operands.first().expect("expected a va_list operand");
+
+    let mne = match instr.meta {
+        TypeMetadata::f32 => Mnemonic::Movss,
+        TypeMetadata::f64 => Mnemonic::Movsd,
+        _ => Mnemonic::Mov,
+    };
+
+    // rax points behind the argument after the list was advanced
+    let load = |displ: isize| -> Vec<X64MCInstr> {
+        instr.out.iter().map(|out| X64MCInstr::with2(mne, x64_lower_op(out), Operand::Mem(MemOp { 
+            base: Some(x64Reg::Rax), index: None, scale: 1, displ, rip: false 
+        }))).collect()
+    };
+
+    // every argument on the stack (and in the home slots of windows) takes 8 bytes
+    let mut stack = vec![
+        X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(x64Reg::Rax), x64_lower_ptr_offset(list, if conv == CallConv::WindowsFastCall { 0 } else { 8 })),
+        X64MCInstr::with2(Mnemonic::Add, Operand::Reg(x64Reg::Rax), Operand::Imm(8)),
+        X64MCInstr::with2(Mnemonic::Mov, x64_lower_ptr_offset(list, if conv == CallConv::WindowsFastCall { 0 } else { 8 }), Operand::Reg(x64Reg::Rax)),
+    ];
+    stack.extend(load(-8));
+
+    if conv == CallConv::WindowsFastCall {
+        sink.extend(stack);
+        return;
+    }
+
+    // system v reads the argument from the register save area until the registers of its class are used up
+    let (offset, limit, step) = if instr.meta.float() { (4, 6 * 8 + 8 * 16, 16) } else { (0, 6 * 8, 8) };
+
+    let mut regs = vec![
+        X64MCInstr::with2(Mnemonic::Add, Operand::Reg(x64Reg::Eax), Operand::Imm(step)),
+        X64MCInstr::with2(Mnemonic::Mov, x64_lower_ptr_offset(list, offset), Operand::Reg(x64Reg::Eax)),
+        X64MCInstr::with2(Mnemonic::Add, Operand::Reg(x64Reg::Rax), x64_lower_ptr_offset(list, 16)),
+    ];
+    regs.extend(load(-step as isize));
+
+    let size = |instrs: &Vec<X64MCInstr>| instrs.iter().map(|instr| {
+        instr.encode().expect("va_arg instructions should be encodable").0.len() as i64
+    }).sum::<i64>();
+
+    // the code contains branches, so it mustn't be optimized
+    sink.push( X64MCInstr::with0(Mnemonic::EndOptimization) );
+
+    sink.push( X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(x64Reg::Eax), x64_lower_ptr_offset(list, offset)) );
+    sink.push( X64MCInstr::with2(Mnemonic::Cmp, Operand::Reg(x64Reg::Eax), Operand::Imm(limit)) );
+
+    // the jumps are relative to their end (jmp rel32 is 5 bytes long)
+    sink.push( X64MCInstr::with1(Mnemonic::Jb, Operand::Imm(size(&stack) + 5)) );
+    sink.extend(stack);
+    sink.push( X64MCInstr::with1(Mnemonic::Jmp, Operand::Imm(size(&regs))) );
+    sink.extend(regs);
+
+    sink.push( X64MCInstr::with0(Mnemonic::StartOptimization) );
+}
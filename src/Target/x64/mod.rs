@@ -16,7 +16,7 @@ mod lower;
 
 pub use asm::*;
 
-use crate::{CodeGen::MachineMnemonic, Target::Compiler};
+use crate::{prelude::{AtomicOrdering, AtomicRmwOp, OverflowOp}, CodeGen::MachineMnemonic, Obj::TlsModel, Target::Compiler, IR::TypeMetadata};
 
 /// Initializes the x86-64 target
 pub fn initializeX64Target(call_conv: CallConv) -> TargetBackendDescr {
@@ -47,10 +47,46 @@ fn construct_whitelist() -> WhiteList {
     whitelist.allow(MachineMnemonic::Or);
     whitelist.allow(MachineMnemonic::Sub);
     whitelist.allow(MachineMnemonic::Xor);
+    whitelist.allow(MachineMnemonic::Rem);
+    whitelist.allow(MachineMnemonic::Shl);
+    whitelist.allow(MachineMnemonic::Lshr);
+    whitelist.allow(MachineMnemonic::Ashr);
+    whitelist.allow(MachineMnemonic::Neg);
+    whitelist.allow(MachineMnemonic::Not);
+    whitelist.allow(MachineMnemonic::Bswap);
+    whitelist.allow(MachineMnemonic::Ctpop);
+    whitelist.allow(MachineMnemonic::Ctlz);
+    whitelist.allow(MachineMnemonic::Cttz);
     whitelist.allow(MachineMnemonic::Return);
-
-    //whitelist.allow(MachineMnemonic::Zext); todo!()
-    //whitelist.allow(MachineMnemonic::Downcast); todo!()
+    whitelist.allow(MachineMnemonic::FCast(TypeMetadata::Void));
+    whitelist.allow(MachineMnemonic::Zext);
+    whitelist.allow(MachineMnemonic::Sext);
+    whitelist.allow(MachineMnemonic::Downcast);
+    whitelist.allow(MachineMnemonic::Prolog);
+    whitelist.allow(MachineMnemonic::Epilog);
+    whitelist.allow(MachineMnemonic::Alloca);
+    whitelist.allow(MachineMnemonic::Load);
+    whitelist.allow(MachineMnemonic::Store);
+    whitelist.allow(MachineMnemonic::Gep);
+    whitelist.allow(MachineMnemonic::MemCpy);
+    whitelist.allow(MachineMnemonic::MemSet);
+    whitelist.allow(MachineMnemonic::AtomicLoad(AtomicOrdering::SeqCst));
+    whitelist.allow(MachineMnemonic::AtomicStore(AtomicOrdering::SeqCst));
+    whitelist.allow(MachineMnemonic::AtomicRmw(AtomicRmwOp::Add, AtomicOrdering::SeqCst));
+    whitelist.allow(MachineMnemonic::AtomicCmpXchg(AtomicOrdering::SeqCst));
+    whitelist.allow(MachineMnemonic::Fence(AtomicOrdering::SeqCst));
+    whitelist.allow(MachineMnemonic::CallIndirect);
+    whitelist.allow(MachineMnemonic::TailCall(String::new()));
+    whitelist.allow(MachineMnemonic::Unreachable);
+    whitelist.allow(MachineMnemonic::Switch(String::new(), String::new(), vec![]));
+    whitelist.allow(MachineMnemonic::Select);
+    whitelist.allow(MachineMnemonic::Overflow(OverflowOp::Add));
+    whitelist.allow(MachineMnemonic::SetOverflow);
+    whitelist.allow(MachineMnemonic::BrOverflow(String::new(), String::new()));
+    whitelist.allow(MachineMnemonic::VaStart(CallConv::SystemV));
+    whitelist.allow(MachineMnemonic::VaArg(CallConv::SystemV));
+    whitelist.allow(MachineMnemonic::InlineAsm(String::new()));
+    whitelist.allow(MachineMnemonic::TlsAdressLoad(String::new(), TlsModel::LocalExec));
 
     whitelist
 }
\ No newline at end of file
@@ -24,6 +24,9 @@ pub enum x64Reg {
     R13, R13d, R13w, R13b,
     R14, R14d, R14w, R14b,
     R15, R15d, R15w, R15b,
+
+    Xmm0, Xmm1, Xmm2, Xmm3, Xmm4, Xmm5, Xmm6, Xmm7,
+    Xmm8, Xmm9, Xmm10, Xmm11, Xmm12, Xmm13, Xmm14, Xmm15,
 }
 
 impl x64Reg {
@@ -49,11 +52,23 @@ impl x64Reg {
             "r13" => Some(R13), "r13d" => Some(R13d), "r13w" => Some(R13w), "r13b" => Some(R13b),
             "r14" => Some(R14), "r14d" => Some(R14d), "r14w" => Some(R14w), "r14b" => Some(R14b),
             "r15" => Some(R15), "r15d" => Some(R15d), "r15w" => Some(R15w), "r15b" => Some(R15b),
+
+            "xmm0" => Some(Xmm0), "xmm1" => Some(Xmm1), "xmm2" => Some(Xmm2), "xmm3" => Some(Xmm3),
+            "xmm4" => Some(Xmm4), "xmm5" => Some(Xmm5), "xmm6" => Some(Xmm6), "xmm7" => Some(Xmm7),
+            "xmm8" => Some(Xmm8), "xmm9" => Some(Xmm9), "xmm10" => Some(Xmm10), "xmm11" => Some(Xmm11),
+            "xmm12" => Some(Xmm12), "xmm13" => Some(Xmm13), "xmm14" => Some(Xmm14), "xmm15" => Some(Xmm15),
             
             _ => None,
         }
     }
 
+    /// Returns if the reg can only be encoded with a rex prefix (spl, bpl, sil, dil)
+    /// 
+    /// Without one they would be ah, ch, dh, bh
+    pub fn needs_rex(&self) -> bool {
+        matches!(self, x64Reg::Spl | x64Reg::Bpl | x64Reg::Sil | x64Reg::Dil)
+    }
+
     /// Returns if the reg is in the extendet region (r8->r15)
     pub fn extended(&self) -> bool {
         use x64Reg::*;
@@ -66,6 +81,8 @@ impl x64Reg {
             R13 | R13d | R13w | R13b |
             R14 | R14d | R14w | R14b |
             R15 | R15d | R15w | R15b  => true,
+            Xmm8 | Xmm9 | Xmm10 | Xmm11 |
+            Xmm12 | Xmm13 | Xmm14 | Xmm15 => true,
             _ => false,
         }
     }
@@ -92,6 +109,8 @@ impl x64Reg {
             R13 | R13d | R13w | R13b => R13,
             R14 | R14d | R14w | R14b => R14,
             R15 | R15d | R15w | R15b => R15,
+
+            xmm => *xmm,
         }
     }
 
@@ -117,6 +136,8 @@ impl x64Reg {
             R13 | R13d | R13w | R13b => R13d,
             R14 | R14d | R14w | R14b => R14d,
             R15 | R15d | R15w | R15b => R15d,
+
+            xmm => *xmm,
         }
     }
 
@@ -142,6 +163,8 @@ impl x64Reg {
             R13 | R13d | R13w | R13b => R13w,
             R14 | R14d | R14w | R14b => R14w,
             R15 | R15d | R15w | R15b => R15w,
+
+            xmm => *xmm,
         }
     }
 
@@ -167,6 +190,8 @@ impl x64Reg {
             R13 | R13d | R13w | R13b => R13b,
             R14 | R14d | R14w | R14b => R14b,
             R15 | R15d | R15w | R15b => R15b,
+
+            xmm => *xmm,
         }
     }
     
@@ -182,6 +207,21 @@ impl x64Reg {
         }
     }
 
+    /// Is the register (or better the subvariant) preserved across calls by every calling convention (rbx, rsp, rbp, r12->r15)?
+    pub fn is_callee_saved(&self) -> bool {
+        use x64Reg::*;
+        !self.is_xmm() && matches!(self.sub64(), Rbx | Rsp | Rbp | R12 | R13 | R14 | R15)
+    }
+
+    /// Is the register a sse register (xmm0->xmm15)?
+    pub fn is_xmm(&self) -> bool {
+        use x64Reg::*;
+        matches!(self, 
+            Xmm0 | Xmm1 | Xmm2 | Xmm3 | Xmm4 | Xmm5 | Xmm6 | Xmm7 |
+            Xmm8 | Xmm9 | Xmm10 | Xmm11 | Xmm12 | Xmm13 | Xmm14 | Xmm15
+        )
+    }
+
     /// Is the register (or better the subvariant) 64 bit wide?
     pub fn is_gr64(&self) -> bool {
         use x64Reg::*;
@@ -251,6 +291,15 @@ impl x64Reg {
             x64Reg::R13 | x64Reg::R13d | x64Reg::R13w | x64Reg::R13b => 5,
             x64Reg::R14 | x64Reg::R14d | x64Reg::R14w | x64Reg::R14b => 6,
             x64Reg::R15 | x64Reg::R15d | x64Reg::R15w | x64Reg::R15b => 7,
+
+            x64Reg::Xmm0 | x64Reg::Xmm8 => 0,
+            x64Reg::Xmm1 | x64Reg::Xmm9 => 1,
+            x64Reg::Xmm2 | x64Reg::Xmm10 => 2,
+            x64Reg::Xmm3 | x64Reg::Xmm11 => 3,
+            x64Reg::Xmm4 | x64Reg::Xmm12 => 4,
+            x64Reg::Xmm5 | x64Reg::Xmm13 => 5,
+            x64Reg::Xmm6 | x64Reg::Xmm14 => 6,
+            x64Reg::Xmm7 | x64Reg::Xmm15 => 7,
         }
     }
     
@@ -1,30 +1,286 @@
 use ygen::prelude::*;
+use ygen::Target::initializeAllTargets;
+
+/// Adds the function `name` to the module and builds its entry block with `build`
+fn build_func<'a, R>(module: &'a mut Module, name: &str, ty: &FunctionType, build: impl FnOnce(&mut IRBuilder<'_>) -> R) -> (&'a mut Function, R) {
+    let func = module.add(name, ty);
+
+    let mut builder = IRBuilder();
+    builder.positionAtEnd(func.addBlock("entry"));
+
+    let res = build(&mut builder);
+
+    (func, res)
+}
 
 #[test]
 pub fn ir_optimization() {
     let mut module = Module();
 
-    let mut builder = IRBuilder();
-
     let other = module.add("cfunc", &FnTy(vec![TypeMetadata::i32, TypeMetadata::i32], TypeMetadata::i32));
     other.import();
     let other = other.clone();
 
     let ty = FnTy(vec![TypeMetadata::i32, TypeMetadata::i32], TypeMetadata::i32);
-    
-    let func = module.add(
-        "add", &ty
-    );
 
-    func.extrn();
+    let (func, _) = build_func(&mut module, "add", &ty, |builder| {
+        let val = builder.BuildCall( &other, vec![ty.arg(0), ty.arg(1)] );
+        let val = builder.BuildAdd(val, ty.arg(0));
 
-    let entry = func.addBlock("entry");
-    builder.positionAtEnd(entry); 
+        builder.BuildRet( val );
+    });
 
-    let val = builder.BuildCall( &other, vec![ty.arg(0), ty.arg(1)] );
-    let val = builder.BuildAdd(val, ty.arg(0));
-    
-    builder.BuildRet( val );
+    func.extrn();
 
     //assert_eq!(module.dump(), "define i32 @add(i32 %0, i32 %1) {\n entry:\n\t%2 = call i32 cfunc i32 %0 i32 %1 \n\tadd = %3 i32 %2, %0\n\tret i32 %3\n\n}\ndeclare i32 @cfunc(i32 %0, i32 %1)\n\n".to_string());
-}
\ No newline at end of file
+}
+#[test]
+pub fn function_attributes() {
+    let mut module = Module();
+
+    let ty = FnTy(vec![], TypeMetadata::i32);
+
+    let (func, _) = build_func(&mut module, "f", &ty, |builder| builder.BuildRet( Type::i32(5) ));
+
+    func.add_attr(FunctionAttr::NoInline);
+    func.add_attr(FunctionAttr::Cold);
+
+    assert!(func.dump().contains("@f() #noinline #cold {"));
+    assert_eq!(func.verify(), Ok(()));
+
+    func.add_attr(FunctionAttr::Inline);
+    assert_eq!(func.verify(), Err(VerifyError::ConflictingAttributes(FunctionAttr::Inline, FunctionAttr::NoInline)));
+
+    let (func, _) = build_func(&mut module, "g", &ty, |builder| builder.BuildRet( Type::i32(5) ));
+
+    func.add_attr(FunctionAttr::NoReturn);
+
+    assert_eq!(func.verify(), Err(VerifyError::ReturnInNoReturnFunc));
+
+    let ty = FnTy(vec![TypeMetadata::ptr, TypeMetadata::ptr, TypeMetadata::u64], TypeMetadata::Void);
+
+    let (func, _) = build_func(&mut module, "h", &ty, |builder| {
+        builder.BuildMemCpy(ty.arg(0), ty.arg(1), ty.arg(2));
+        builder.BuildRet( Type::Void );
+    });
+
+    func.add_attr(FunctionAttr::Naked);
+
+    assert_eq!(func.verify(), Err(VerifyError::StackInNakedFunc));
+
+    let mut ty = FnTy(vec![TypeMetadata::i32], TypeMetadata::i32);
+    ty.activate_dynamic_arguments();
+
+    let (func, _) = build_func(&mut module, "v", &ty, |builder| builder.BuildRet( ty.arg(0) ));
+
+    func.add_attr(FunctionAttr::Naked);
+
+    assert_eq!(func.verify(), Err(VerifyError::StackInNakedFunc));
+}
+
+#[test]
+pub fn def_use_chains() {
+    let mut module = Module();
+
+    let ty = FnTy(vec![TypeMetadata::i32, TypeMetadata::i32], TypeMetadata::i32);
+
+    let (func, (sum, square)) = build_func(&mut module, "f", &ty, |builder| {
+        let sum = builder.BuildAdd(ty.arg(0), ty.arg(1));
+        let square = builder.BuildMul(sum.clone(), sum.clone());
+
+        builder.BuildRet( square.clone() );
+
+        (sum, square)
+    });
+
+    let mut chains = func.def_use();
+
+    assert_eq!(chains.definition_of(&sum), Some(NodeRef { block: 0, node: 0 }));
+    assert_eq!(chains.definition_of(&square), Some(NodeRef { block: 0, node: 1 }));
+    assert_eq!(chains.definition_of(&ty.arg(0)), None);
+
+    assert_eq!(chains.users_of(&sum), &[NodeRef { block: 0, node: 1 }]);
+    assert_eq!(chains.users_of(&square), &[NodeRef { block: 0, node: 2 }]);
+    assert_eq!(chains.users_of(&ty.arg(0)), &[NodeRef { block: 0, node: 0 }]);
+
+    chains.replace_all_uses_with(func, &sum, &ty.arg(0));
+
+    assert!(chains.users_of(&sum).is_empty());
+    assert_eq!(chains.users_of(&ty.arg(0)), &[NodeRef { block: 0, node: 0 }, NodeRef { block: 0, node: 1 }]);
+    assert_eq!(chains, func.def_use());
+
+    assert!(func.dump().contains("= mul i32 %0, %0"));
+}
+
+#[test]
+pub fn cfg_and_dominators() {
+    use std::rc::Rc;
+    use ygen::Optimizations::AnalysisManager;
+
+    let ty = FnTy(vec![TypeMetadata::i32], TypeMetadata::i32);
+
+    let mut func = Func("f".into(), ty.clone());
+
+    let left = Block("left", &func);
+    let right = Block("right", &func);
+    let body = Block("loop", &func);
+    let join = Block("join", &func);
+
+    {
+        let mut builder = IRBuilder();
+        builder.positionAtEnd(func.addBlock("entry"));
+        BuildBrCond::BuildBr(&mut builder, ty.arg(0), &left, &right);
+    }
+    {
+        let mut builder = IRBuilder();
+        builder.positionAtEnd(func.addBlock("left"));
+        BuildBr::BuildBr(&mut builder, &join);
+    }
+    {
+        let mut builder = IRBuilder();
+        builder.positionAtEnd(func.addBlock("right"));
+        BuildBr::BuildBr(&mut builder, &body);
+    }
+    {
+        let mut builder = IRBuilder();
+        builder.positionAtEnd(func.addBlock("loop"));
+        BuildBrCond::BuildBr(&mut builder, ty.arg(0), &body, &join);
+    }
+    {
+        let mut builder = IRBuilder();
+        builder.positionAtEnd(func.addBlock("join"));
+        builder.BuildRet( Type::i32(0) );
+    }
+    {
+        let mut builder = IRBuilder();
+        builder.positionAtEnd(func.addBlock("dead"));
+        builder.BuildRet( Type::i32(1) );
+    }
+
+    let cfg = func.cfg();
+
+    assert_eq!(cfg.block("loop"), Some(3));
+    assert_eq!(cfg.successors(0), &[1, 2]);
+    assert_eq!(cfg.successors(3), &[3, 4]);
+    assert_eq!(cfg.predecessors(3), &[2, 3]);
+    assert_eq!(cfg.predecessors(4), &[1, 3]);
+    assert_eq!(cfg.reverse_post_order(), &[0, 2, 3, 1, 4]);
+    assert!(cfg.is_reachable(4));
+    assert!(!cfg.is_reachable(5));
+
+    let dom = DomTree::new(&cfg);
+
+    assert_eq!(dom.idom(0), None);
+    assert_eq!(dom.idom(3), Some(2));
+    assert_eq!(dom.idom(4), Some(0));
+    assert_eq!(dom.idom(5), None);
+    assert_eq!(dom.children(0), &[2, 1, 4]);
+    assert!(dom.dominates(2, 3));
+    assert!(dom.dominates(3, 3));
+    assert!(!dom.dominates(1, 4));
+    assert!(!dom.dominates(0, 5));
+
+    let frontiers = DominanceFrontiers::new(&cfg, &dom);
+
+    assert!(frontiers.frontier(0).is_empty());
+    assert_eq!(frontiers.frontier(1), &[4]);
+    assert_eq!(frontiers.frontier(2), &[4]);
+    assert_eq!(frontiers.frontier(3), &[3, 4]);
+
+    let mut analyses = AnalysisManager::new();
+
+    let cached = analyses.get::<DominanceFrontiers>(&func);
+
+    assert!(analyses.is_cached::<Cfg>());
+    assert!(analyses.is_cached::<DomTree>());
+    assert!(Rc::ptr_eq(&cached, &analyses.get::<DominanceFrontiers>(&func)));
+    assert_eq!(*cached, frontiers);
+
+    analyses.invalidate();
+
+    assert!(!analyses.is_cached::<Cfg>());
+}
+
+#[test]
+pub fn inline_asm_registers() {
+    let triple = Triple::parse("x86_64-unknown-linux").unwrap();
+
+    let ty = FnTy(vec![TypeMetadata::i32], TypeMetadata::Void);
+
+    for (constraint, expected) in [
+        ("ecx", None),
+        ("foo", Some(VerifyError::UnknownAsmReg("foo".to_owned()))),
+        ("r12d", Some(VerifyError::AsmRegCalleeSaved("r12d".to_owned()))),
+    ] {
+        let mut module = Module();
+
+        let (func, _) = build_func(&mut module, "f", &ty, |builder| {
+            builder.BuildInlineAsm("nop", &[(constraint, ty.arg(0))], None, &["rdx"]);
+            builder.BuildRet( Type::Void );
+        });
+
+        // the register names depend on the architecture, so they are checked by the backend
+        assert_eq!(func.verify(), Ok(()));
+
+        let result = module.emitMachineInstrs(triple, &mut initializeAllTargets(triple).unwrap());
+
+        assert_eq!(result.err().and_then(|err| err.downcast_ref::<VerifyError>().cloned()), expected);
+    }
+}
+
+#[test]
+pub fn int_only_operations() {
+    let mut module = Module();
+
+    let ty = FnTy(vec![TypeMetadata::f64, TypeMetadata::f64], TypeMetadata::f64);
+
+    let (func, _) = build_func(&mut module, "f", &ty, |builder| {
+        let val = builder.BuildAdd(ty.arg(0), ty.arg(1));
+        builder.BuildRet( val );
+    });
+
+    assert_eq!(func.verify(), Ok(()));
+
+    let (func, _) = build_func(&mut module, "g", &ty, |builder| {
+        let val = builder.BuildRem(ty.arg(0), ty.arg(1));
+        builder.BuildRet( val );
+    });
+
+    assert_eq!(func.verify(), Err(VerifyError::IntOnlyOperation(TypeMetadata::f64)));
+
+    let (func, _) = build_func(&mut module, "h", &ty, |builder| {
+        let val = builder.BuildNot(ty.arg(0));
+        builder.BuildRet( val );
+    });
+
+    assert_eq!(func.verify(), Err(VerifyError::IntOnlyOperation(TypeMetadata::f64)));
+}
+
+#[test]
+pub fn invalid_casts() {
+    let mut module = Module();
+
+    let ty = FnTy(vec![TypeMetadata::f64], TypeMetadata::ptr);
+
+    let (func, _) = build_func(&mut module, "f", &ty, |builder| {
+        let val = builder.BuildCast(ty.arg(0), TypeMetadata::ptr);
+        builder.BuildRet( val );
+    });
+
+    assert_eq!(func.verify(), Err(VerifyError::InvalidCast(TypeMetadata::f64, TypeMetadata::ptr)));
+}
+
+#[test]
+pub fn tail_call_arguments() {
+    let mut module = Module();
+
+    let callee = module.add("callee", &FnTy(vec![TypeMetadata::i32, TypeMetadata::i32], TypeMetadata::i32));
+    callee.import();
+    let callee = callee.clone();
+
+    let ty = FnTy(vec![TypeMetadata::i32], TypeMetadata::i32);
+
+    let (func, _) = build_func(&mut module, "f", &ty, |builder| builder.BuildTailCall(&callee, vec![ty.arg(0)]));
+
+    assert_eq!(func.verify(), Err(VerifyError::InvalidArgumentTypeFound));
+}
@@ -26,6 +26,304 @@ pub fn test_ret() {
     assert_eq!(instr.encode(), Ok((vec![0xC3], None)));
 }
 
+#[test]
+pub fn test_byte_regs() {
+    let instr = X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(x64Reg::Al), Operand::Reg(x64Reg::Sil));
+
+    assert_eq!(instr.encode(), Ok((vec![0x40, 0x8A, 0xC6], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Movzx, Operand::Reg(x64Reg::Eax), Operand::Reg(x64Reg::Dil));
+
+    assert_eq!(instr.encode(), Ok((vec![0x40, 0x0F, 0xB6, 0xC7], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Movsx, Operand::Reg(x64Reg::Rax), Operand::Reg(x64Reg::R9w));
+
+    assert_eq!(instr.encode(), Ok((vec![0x49, 0x0F, 0xBF, 0xC1], None)));
+
+    let instr = X64MCInstr::with1(Mnemonic::Sete, Operand::Reg(x64Reg::R10b));
+
+    assert_eq!(instr.encode(), Ok((vec![0x41, 0x0F, 0x94, 0xC2], None)));
+
+    let instr = X64MCInstr::with1(Mnemonic::Setnp, Operand::Reg(x64Reg::Al));
+
+    assert_eq!(instr.encode(), Ok((vec![0x0F, 0x9B, 0xC0], None)));
+}
+
+#[test]
+pub fn test_mem() {
+    let mem = |base: Option<x64Reg>, index: Option<x64Reg>, scale: isize, displ: isize| {
+        Operand::Mem(MemOp { base, index, scale, displ, rip: false })
+    };
+
+    let instr = X64MCInstr::with2(Mnemonic::Mov, mem(Some(x64Reg::Rbp), None, 1, -8), Operand::Reg(x64Reg::Eax));
+
+    assert_eq!(instr.encode(), Ok((vec![0x89, 0x45, 0xF8], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(x64Reg::Rax), mem(Some(x64Reg::Rbp), None, 1, -16));
+
+    assert_eq!(instr.encode(), Ok((vec![0x48, 0x8B, 0x45, 0xF0], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Lea, Operand::Reg(x64Reg::Rax), mem(Some(x64Reg::Rbp), None, 1, -8));
+
+    assert_eq!(instr.encode(), Ok((vec![0x48, 0x8D, 0x45, 0xF8], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Mov, mem(Some(x64Reg::Rsi), None, 1, 0), Operand::Reg(x64Reg::Sil));
+
+    assert_eq!(instr.encode(), Ok((vec![0x40, 0x88, 0x36], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Mov, mem(Some(x64Reg::R13), None, 1, 0), Operand::Reg(x64Reg::Cx));
+
+    assert_eq!(instr.encode(), Ok((vec![0x66, 0x41, 0x89, 0x4D, 0x00], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Mov, mem(Some(x64Reg::Rsp), None, 1, 8), Operand::Reg(x64Reg::R12));
+
+    assert_eq!(instr.encode(), Ok((vec![0x4C, 0x89, 0x64, 0x24, 0x08], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(x64Reg::Rax), mem(Some(x64Reg::Rcx), Some(x64Reg::Rdx), 4, 0x100));
+
+    assert_eq!(instr.encode(), Ok((vec![0x48, 0x8B, 0x84, 0x91, 0x00, 0x01, 0x00, 0x00], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Lea, Operand::Reg(x64Reg::R9), mem(Some(x64Reg::R13), Some(x64Reg::Rax), 8, 0));
+
+    assert_eq!(instr.encode(), Ok((vec![0x4D, 0x8D, 0x4C, 0xC5, 0x00], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Movsxd, Operand::Reg(x64Reg::Rax), Operand::Reg(x64Reg::Ecx));
+
+    assert_eq!(instr.encode(), Ok((vec![0x48, 0x63, 0xC1], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Movss, Operand::Reg(x64Reg::Xmm8), mem(Some(x64Reg::Rax), None, 1, 0));
+
+    assert_eq!(instr.encode(), Ok((vec![0xF3, 0x44, 0x0F, 0x10, 0x00], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Movsd, mem(Some(x64Reg::R12), None, 1, 0), Operand::Reg(x64Reg::Xmm1));
+
+    assert_eq!(instr.encode(), Ok((vec![0xF2, 0x41, 0x0F, 0x11, 0x0C, 0x24], None)));
+}
+
+#[test]
+pub fn test_sse() {
+    let instr = X64MCInstr::with2(Mnemonic::Addsd, Operand::Reg(x64Reg::Xmm0), Operand::Reg(x64Reg::Xmm1));
+
+    assert_eq!(instr.encode(), Ok((vec![0xF2, 0x0F, 0x58, 0xC1], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Mulss, Operand::Reg(x64Reg::Xmm15), Operand::Reg(x64Reg::Xmm2));
+
+    assert_eq!(instr.encode(), Ok((vec![0xF3, 0x44, 0x0F, 0x59, 0xFA], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Movq, Operand::Reg(x64Reg::Xmm8), Operand::Reg(x64Reg::Rax));
+
+    assert_eq!(instr.encode(), Ok((vec![0x66, 0x4C, 0x0F, 0x6E, 0xC0], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Movd, Operand::Reg(x64Reg::Eax), Operand::Reg(x64Reg::Xmm1));
+
+    assert_eq!(instr.encode(), Ok((vec![0x66, 0x0F, 0x7E, 0xC8], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Cvttsd2si, Operand::Reg(x64Reg::Rax), Operand::Reg(x64Reg::Xmm3));
+
+    assert_eq!(instr.encode(), Ok((vec![0xF2, 0x48, 0x0F, 0x2C, 0xC3], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Ucomisd, Operand::Reg(x64Reg::Xmm0), Operand::Reg(x64Reg::Xmm9));
+
+    assert_eq!(instr.encode(), Ok((vec![0x66, 0x41, 0x0F, 0x2E, 0xC1], None)));
+}
+
+#[test]
+pub fn test_jumps() {
+    let instr = X64MCInstr::with1(Mnemonic::Je, Operand::Imm(0x10));
+
+    assert_eq!(instr.encode(), Ok((vec![0x0F, 0x84, 0x10, 0x00, 0x00, 0x00], None)));
+
+    let instr = X64MCInstr::with1(Mnemonic::Jg, Operand::Imm(-2));
+
+    assert_eq!(instr.encode(), Ok((vec![0x0F, 0x8F, 0xFE, 0xFF, 0xFF, 0xFF], None)));
+
+    let instr = X64MCInstr::with1(Mnemonic::Ja, Operand::Imm(0x100));
+
+    assert_eq!(instr.encode(), Ok((vec![0x0F, 0x87, 0x00, 0x01, 0x00, 0x00], None)));
+
+    let instr = X64MCInstr::with1(Mnemonic::Jo, Operand::Imm(0x10));
+
+    assert_eq!(instr.encode(), Ok((vec![0x0F, 0x80, 0x10, 0x00, 0x00, 0x00], None)));
+
+    let instr = X64MCInstr::with1(Mnemonic::Jb, Operand::Imm(0x10));
+
+    assert_eq!(instr.encode(), Ok((vec![0x0F, 0x82, 0x10, 0x00, 0x00, 0x00], None)));
+
+    let instr = X64MCInstr::with1(Mnemonic::Seto, Operand::Reg(x64Reg::Cl));
+
+    assert_eq!(instr.encode(), Ok((vec![0x0F, 0x90, 0xC1], None)));
+
+    let instr = X64MCInstr::with1(Mnemonic::Setb, Operand::Reg(x64Reg::Sil));
+
+    assert_eq!(instr.encode(), Ok((vec![0x40, 0x0F, 0x92, 0xC6], None)));
+
+    let instr = X64MCInstr::with1(Mnemonic::Jmp, Operand::Reg(x64Reg::Rax));
+
+    assert_eq!(instr.encode(), Ok((vec![0xFF, 0xE0], None)));
+
+    let instr = X64MCInstr::with1(Mnemonic::Call, Operand::Reg(x64Reg::R11));
+
+    assert_eq!(instr.encode(), Ok((vec![0x41, 0xFF, 0xD3], None)));
+
+    let instr = X64MCInstr::with0(Mnemonic::Ud2);
+
+    assert_eq!(instr.encode(), Ok((vec![0x0F, 0x0B], None)));
+}
+
+#[test]
+pub fn test_div() {
+    let instr = X64MCInstr::with1(Mnemonic::Idiv, Operand::Reg(x64Reg::Ebx));
+
+    assert_eq!(instr.encode(), Ok((vec![0xF7, 0xFB], None)));
+
+    let instr = X64MCInstr::with1(Mnemonic::Div, Operand::Reg(x64Reg::R11));
+
+    assert_eq!(instr.encode(), Ok((vec![0x49, 0xF7, 0xF3], None)));
+
+    let instr = X64MCInstr::with0(Mnemonic::Cdq);
+
+    assert_eq!(instr.encode(), Ok((vec![0x99], None)));
+
+    let instr = X64MCInstr::with0(Mnemonic::Cqo);
+
+    assert_eq!(instr.encode(), Ok((vec![0x48, 0x99], None)));
+
+    let instr = X64MCInstr::with1(Mnemonic::Neg, Operand::Reg(x64Reg::Rax));
+
+    assert_eq!(instr.encode(), Ok((vec![0x48, 0xF7, 0xD8], None)));
+
+    let instr = X64MCInstr::with1(Mnemonic::Not, Operand::Reg(x64Reg::Ecx));
+
+    assert_eq!(instr.encode(), Ok((vec![0xF7, 0xD1], None)));
+}
+
+#[test]
+pub fn test_shifts() {
+    let instr = X64MCInstr::with2(Mnemonic::Shl, Operand::Reg(x64Reg::Eax), Operand::Reg(x64Reg::Cl));
+
+    assert_eq!(instr.encode(), Ok((vec![0xD3, 0xE0], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Sar, Operand::Reg(x64Reg::R9), Operand::Imm(3));
+
+    assert_eq!(instr.encode(), Ok((vec![0x49, 0xC1, 0xF9, 0x03], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Shr, Operand::Reg(x64Reg::Al), Operand::Imm(1));
+
+    assert_eq!(instr.encode(), Ok((vec![0xC0, 0xE8, 0x01], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Shr, Operand::Reg(x64Reg::Sil), Operand::Reg(x64Reg::Cl));
+
+    assert_eq!(instr.encode(), Ok((vec![0x40, 0xD2, 0xEE], None)));
+}
+
+#[test]
+pub fn test_cmov() {
+    let instr = X64MCInstr::with2(Mnemonic::Cmovne, Operand::Reg(x64Reg::Eax), Operand::Reg(x64Reg::Ebx));
+
+    assert_eq!(instr.encode(), Ok((vec![0x0F, 0x45, 0xC3], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Cmove, Operand::Reg(x64Reg::Rax), Operand::Reg(x64Reg::R9));
+
+    assert_eq!(instr.encode(), Ok((vec![0x49, 0x0F, 0x44, 0xC1], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Cmovl, Operand::Reg(x64Reg::R10w), Operand::Reg(x64Reg::Cx));
+
+    assert_eq!(instr.encode(), Ok((vec![0x66, 0x44, 0x0F, 0x4C, 0xD1], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Cmovne, Operand::Reg(x64Reg::Al), Operand::Reg(x64Reg::Bl));
+
+    assert!(instr.verify().is_err());
+}
+
+#[test]
+pub fn test_bit_manipulation() {
+    let instr = X64MCInstr::with2(Mnemonic::Popcnt, Operand::Reg(x64Reg::Eax), Operand::Reg(x64Reg::Ecx));
+
+    assert_eq!(instr.encode(), Ok((vec![0xF3, 0x0F, 0xB8, 0xC1], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Popcnt, Operand::Reg(x64Reg::Rax), Operand::Reg(x64Reg::R9));
+
+    assert_eq!(instr.encode(), Ok((vec![0xF3, 0x49, 0x0F, 0xB8, 0xC1], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Bsr, Operand::Reg(x64Reg::Eax), Operand::Reg(x64Reg::Ecx));
+
+    assert_eq!(instr.encode(), Ok((vec![0x0F, 0xBD, 0xC1], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Bsf, Operand::Reg(x64Reg::R10), Operand::Reg(x64Reg::Rax));
+
+    assert_eq!(instr.encode(), Ok((vec![0x4C, 0x0F, 0xBC, 0xD0], None)));
+
+    let instr = X64MCInstr::with1(Mnemonic::Bswap, Operand::Reg(x64Reg::Eax));
+
+    assert_eq!(instr.encode(), Ok((vec![0x0F, 0xC8], None)));
+
+    let instr = X64MCInstr::with1(Mnemonic::Bswap, Operand::Reg(x64Reg::R12));
+
+    assert_eq!(instr.encode(), Ok((vec![0x49, 0x0F, 0xCC], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Popcnt, Operand::Reg(x64Reg::Ax), Operand::Reg(x64Reg::Cx));
+
+    assert!(instr.verify().is_err());
+}
+
+#[test]
+pub fn test_atomics() {
+    let mem = |base| Operand::Mem(MemOp { base: Some(base), index: None, scale: 1, displ: 0, rip: false });
+
+    let instr = X64MCInstr::with0(Mnemonic::Lock);
+
+    assert_eq!(instr.encode(), Ok((vec![0xF0], None)));
+
+    let instr = X64MCInstr::with0(Mnemonic::Mfence);
+
+    assert_eq!(instr.encode(), Ok((vec![0x0F, 0xAE, 0xF0], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Xchg, mem(x64Reg::Rdi), Operand::Reg(x64Reg::Rax));
+
+    assert_eq!(instr.encode(), Ok((vec![0x48, 0x87, 0x07], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Xchg, mem(x64Reg::Rdi), Operand::Reg(x64Reg::Ax));
+
+    assert_eq!(instr.encode(), Ok((vec![0x66, 0x87, 0x07], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Cmpxchg, mem(x64Reg::Rdi), Operand::Reg(x64Reg::Ecx));
+
+    assert_eq!(instr.encode(), Ok((vec![0x0F, 0xB1, 0x0F], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Cmpxchg, mem(x64Reg::Rdi), Operand::Reg(x64Reg::Cl));
+
+    assert_eq!(instr.encode(), Ok((vec![0x0F, 0xB0, 0x0F], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Xadd, mem(x64Reg::Rsi), Operand::Reg(x64Reg::R8));
+
+    assert_eq!(instr.encode(), Ok((vec![0x4C, 0x0F, 0xC1, 0x06], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Xchg, Operand::Reg(x64Reg::Rax), Operand::Reg(x64Reg::Rcx));
+
+    assert!(instr.verify().is_err());
+}
+
+#[test]
+pub fn test_segment_prefix() {
+    let thread_pointer = Operand::Mem(MemOp { base: None, index: None, scale: 1, displ: 0, rip: false });
+
+    let instr = X64MCInstr::with0(Mnemonic::Fs);
+
+    assert_eq!(instr.encode(), Ok((vec![0x64], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Mov, Operand::Reg(x64Reg::Rax), thread_pointer.clone());
+
+    assert_eq!(instr.encode(), Ok((vec![0x48, 0x8B, 0x04, 0x25, 0x00, 0x00, 0x00, 0x00], None)));
+
+    let instr = X64MCInstr::with2(Mnemonic::Add, Operand::Reg(x64Reg::Rax), thread_pointer);
+
+    assert_eq!(instr.encode(), Ok((vec![0x48, 0x03, 0x04, 0x25, 0x00, 0x00, 0x00, 0x00], None)));
+
+    let instr = X64MCInstr::with1(Mnemonic::Fs, Operand::Reg(x64Reg::Rax));
+
+    assert!(instr.verify().is_err());
+}
+
 #[test]
 pub fn test_optimization() {
     let mut instrs = vec![
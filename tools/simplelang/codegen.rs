@@ -161,7 +161,7 @@ impl CodeGenerator {
             args.push(var);
         }
 
-        builder.BuildCall(&fun, args)
+        builder.BuildCall(fun, args)
     }
 
     fn gen_string(&mut self, builder: &mut IRBuilder, string: &String) -> Var {
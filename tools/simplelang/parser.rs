@@ -131,12 +131,15 @@ impl Parser {
             }
 
             ret = match tystring.as_str() {
+                "u8" => Some(TypeMetadata::u8),
                 "u16" => Some(TypeMetadata::u16),
                 "u32" => Some(TypeMetadata::u32),
                 "u64" => Some(TypeMetadata::u64),
+                "i8" => Some(TypeMetadata::i8),
                 "i16" => Some(TypeMetadata::i16),
                 "i32" => Some(TypeMetadata::i32),
                 "i64" => Some(TypeMetadata::i64),
+                "bool" => Some(TypeMetadata::bool),
                 "string" => Some(TypeMetadata::ptr),
                 "void" => Some(TypeMetadata::Void),
                 any => {
@@ -211,12 +214,15 @@ impl Parser {
             } else { return None; }
     
             ty = Some(match tystring.as_str() {
+                "u8" => Some(TypeMetadata::u8),
                 "u16" => Some(TypeMetadata::u16),
                 "u32" => Some(TypeMetadata::u32),
                 "u64" => Some(TypeMetadata::u64),
+                "i8" => Some(TypeMetadata::i8),
                 "i16" => Some(TypeMetadata::i16),
                 "i32" => Some(TypeMetadata::i32),
                 "i64" => Some(TypeMetadata::i64),
+                "bool" => Some(TypeMetadata::bool),
                 "string" => Some(TypeMetadata::ptr),
                 any => {
                     err!(self.error, "unknown type: {}", any);
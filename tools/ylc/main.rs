@@ -77,7 +77,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     let mut infile = match File::open(&infile) {
         Ok(file) => file,
         Err(err) => {
-            println!("{}: {} {}", "Error".red().bold(), infile, err);
+            eprintln!("{}: {} {}", "Error".red().bold(), infile, err);
             exit(-1);
         },
     };
@@ -85,7 +85,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     let mut outfile = match File::options().create(true).write(true).open(&outfile) {
         Ok(file) => file,
         Err(err) => {
-            println!("{}: {} {}", "Error".red().bold(), outfile, err);
+            eprintln!("{}: {} {}", "Error".red().bold(), outfile, err);
             exit(-1);
         },
     };
@@ -97,7 +97,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     match lexer.lex() {
         Ok(_) => {},
         Err(err) => {
-            println!("{}", err);
+            eprintln!("{}", err);
             exit(-1)
         },
     }
@@ -111,7 +111,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     match parser.parse() {
         Ok(_) => {},
         Err(err) => {
-            println!("{}", err);
+            eprintln!("{}", err);
             exit(-1)
         }
     }
@@ -123,7 +123,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     match IrSemnatic::new(&parser.out).verify() {
         Ok(_) => {},
         Err(err) => {
-            println!("{}", err);
+            eprintln!("{}", err);
             exit(-1)
         }
     }
@@ -138,6 +138,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         let mut opts = PassManager::new();
 
         opts.add( Passes::PreComputeValue() );
+        opts.add( Passes::PruneDeadPaths() );
 
         module.runPassMngr(opts);
     }